@@ -0,0 +1,69 @@
+// archive - Archivformate für den Export von Backups
+pub mod zip_archive;
+pub mod sevenz;
+pub mod tar_zst;
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::cancel::CancellationToken;
+
+// Eine Datei im Archiv
+#[derive(Clone, Debug)]
+pub struct ArchiveEntry {
+    // Pfad im Archiv, z.B. "Chrome/bookmarks_20240101_120000.json"
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+pub trait Archiver {
+    // Prüft `cancel` vor jeder Datei; die halb geschriebene Datei räumt der Aufrufer weg
+    fn write(&self, output_path: &Path, entries: &[ArchiveEntry], cancel: &CancellationToken) -> Result<(), String>;
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ArchiveFormat {
+    Zip,
+    // Komprimiert SQLite-Datenbanken deutlich besser als ZIP
+    SevenZip,
+    // Für Backup-Skripte unter Linux
+    TarZst,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 3] = [ArchiveFormat::Zip, ArchiveFormat::SevenZip, ArchiveFormat::TarZst];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "ZIP",
+            ArchiveFormat::SevenZip => "7z",
+            ArchiveFormat::TarZst => "tar.zst",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::SevenZip => "7z",
+            ArchiveFormat::TarZst => "tar.zst",
+        }
+    }
+
+    pub fn archiver(&self) -> Box<dyn Archiver> {
+        match self {
+            ArchiveFormat::Zip => Box::new(zip_archive::ZipArchiver),
+            ArchiveFormat::SevenZip => Box::new(sevenz::SevenZipArchiver),
+            ArchiveFormat::TarZst => Box::new(tar_zst::TarZstArchiver { level: tar_zst::BEST_LEVEL }),
+        }
+    }
+
+    // Schneller statt kleiner, für Exporte nach jedem Backup. ZIP und 7z
+    // packen ohnehin mit mittlerer Stufe.
+    pub fn quick_archiver(&self) -> Box<dyn Archiver> {
+        match self {
+            ArchiveFormat::TarZst => Box::new(tar_zst::TarZstArchiver { level: tar_zst::QUICK_LEVEL }),
+            _ => self.archiver(),
+        }
+    }
+}
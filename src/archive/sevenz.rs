@@ -0,0 +1,28 @@
+// sevenz.rs - 7z-Archive (LZMA2)
+use super::{ArchiveEntry, Archiver};
+use sevenz_rust::{SevenZArchiveEntry, SevenZWriter};
+use std::fs;
+use std::path::Path;
+
+use crate::cancel::CancellationToken;
+
+pub struct SevenZipArchiver;
+
+impl Archiver for SevenZipArchiver {
+    fn write(&self, output_path: &Path, entries: &[ArchiveEntry], cancel: &CancellationToken) -> Result<(), String> {
+        let mut writer = SevenZWriter::create(output_path)
+            .map_err(|e| format!("Fehler beim Erstellen der 7z-Datei: {}", e))?;
+
+        for entry in entries {
+            cancel.check()?;
+            let file = fs::File::open(&entry.path)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+            writer
+                .push_archive_entry(SevenZArchiveEntry::from_path(&entry.path, entry.name.clone()), Some(file))
+                .map_err(|e| format!("7z Fehler: {}", e))?;
+        }
+
+        writer.finish().map_err(|e| format!("Fehler beim Finalisieren: {}", e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+// tar_zst.rs - tar-Archive mit Zstandard-Kompression
+use super::{ArchiveEntry, Archiver};
+use std::fs;
+use std::path::Path;
+
+use crate::cancel::CancellationToken;
+
+// Stufe 19 ist langsam, aber Backups sind klein und werden selten exportiert
+pub const BEST_LEVEL: i32 = 19;
+// Für den automatischen Export nach jedem Backup
+pub const QUICK_LEVEL: i32 = 3;
+
+pub struct TarZstArchiver {
+    pub level: i32,
+}
+
+impl Archiver for TarZstArchiver {
+    fn write(&self, output_path: &Path, entries: &[ArchiveEntry], cancel: &CancellationToken) -> Result<(), String> {
+        let file = fs::File::create(output_path)
+            .map_err(|e| format!("Fehler beim Erstellen der tar.zst-Datei: {}", e))?;
+        let encoder = zstd::Encoder::new(file, self.level)
+            .map_err(|e| format!("zstd Fehler: {}", e))?;
+
+        let mut builder = tar::Builder::new(encoder);
+        for entry in entries {
+            cancel.check()?;
+            builder
+                .append_path_with_name(&entry.path, &entry.name)
+                .map_err(|e| format!("Fehler beim Hinzufügen von {}: {}", entry.name, e))?;
+        }
+
+        let encoder = builder
+            .into_inner()
+            .map_err(|e| format!("Fehler beim Finalisieren: {}", e))?;
+        encoder.finish().map_err(|e| format!("Fehler beim Finalisieren: {}", e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+// zip_archive.rs - ZIP-Archive (Deflate)
+use super::{ArchiveEntry, Archiver};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use crate::cancel::CancellationToken;
+
+pub struct ZipArchiver;
+
+impl Archiver for ZipArchiver {
+    fn write(&self, output_path: &Path, entries: &[ArchiveEntry], cancel: &CancellationToken) -> Result<(), String> {
+        let file = fs::File::create(output_path)
+            .map_err(|e| format!("Fehler beim Erstellen der ZIP-Datei: {}", e))?;
+
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        for entry in entries {
+            cancel.check()?;
+            zip.start_file(entry.name.as_str(), options)
+                .map_err(|e| format!("ZIP Fehler: {}", e))?;
+
+            let mut file = fs::File::open(&entry.path)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+            let mut buffer = Vec::new();
+            file.read_to_end(&mut buffer)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+
+            zip.write_all(&buffer)
+                .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+        }
+
+        zip.finish().map_err(|e| format!("Fehler beim Finalisieren: {}", e))?;
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::i18n::tr;
+
 #[cfg(target_os = "windows")]
 pub fn setup_autostart(enable: bool) -> Result<(), Box<dyn std::error::Error>> {
     use winreg::enums::*;
@@ -21,4 +23,99 @@ pub fn setup_autostart(enable: bool) -> Result<(), Box<dyn std::error::Error>> {
 pub fn setup_autostart(_enable: bool) -> Result<(), Box<dyn std::error::Error>> {
     // Not implemented for non-Windows platforms
     Ok(())
-}
\ No newline at end of file
+}
+
+// Geplante Aufgabe in der Windows-Aufgabenplanung, die `--backup-now` startet.
+// Zuverlässiger als die Tray-App, weil nichts dauerhaft laufen muss.
+#[cfg(target_os = "windows")]
+const TASK_NAME: &str = "BrowserBackup";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TaskTrigger {
+    AtLogon,
+    Daily { hour: u32, minute: u32 },
+}
+
+impl TaskTrigger {
+    pub fn label(&self) -> String {
+        match self {
+            TaskTrigger::AtLogon => tr("Bei Anmeldung").to_string(),
+            TaskTrigger::Daily { hour, minute } => format!("{} {:02}:{:02}", tr("Täglich"), hour, minute),
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn setup_scheduled_task(trigger: Option<TaskTrigger>) -> Result<(), Box<dyn std::error::Error>> {
+    let trigger = match trigger {
+        Some(trigger) => trigger,
+        None => {
+            // Nicht vorhandene Aufgabe ist kein Fehler
+            if scheduled_task().is_some() {
+                schtasks(&["/Delete", "/F", "/TN", TASK_NAME])?;
+            }
+            return Ok(());
+        }
+    };
+    
+    let exe_path = std::env::current_exe()?;
+    let command = format!("\"{}\" --backup-now", exe_path.display());
+    let start_time;
+    let mut args = vec!["/Create", "/F", "/TN", TASK_NAME, "/TR", &command];
+    match trigger {
+        TaskTrigger::AtLogon => args.extend(["/SC", "ONLOGON"]),
+        TaskTrigger::Daily { hour, minute } => {
+            start_time = format!("{:02}:{:02}", hour, minute);
+            args.extend(["/SC", "DAILY", "/ST", &start_time]);
+        }
+    }
+    schtasks(&args)?;
+    
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn setup_scheduled_task(_trigger: Option<TaskTrigger>) -> Result<(), Box<dyn std::error::Error>> {
+    // Not implemented for non-Windows platforms
+    Ok(())
+}
+
+// Liest den Auslöser der vorhandenen Aufgabe aus ihrer XML-Beschreibung
+#[cfg(target_os = "windows")]
+pub fn scheduled_task() -> Option<TaskTrigger> {
+    // Die Ausgabe kann UTF-16 sein, für die Suche reichen die ASCII-Zeichen
+    let xml = schtasks(&["/Query", "/TN", TASK_NAME, "/XML"]).ok()?.replace('\0', "");
+    if xml.contains("<LogonTrigger>") {
+        return Some(TaskTrigger::AtLogon);
+    }
+    
+    // <StartBoundary>2024-01-01T12:00:00</StartBoundary>
+    let start = xml.split("<StartBoundary>").nth(1)?;
+    let time = start.split('T').nth(1)?;
+    let mut parts = time.split(':');
+    let hour = parts.next()?.parse().ok()?;
+    let minute = parts.next()?.parse().ok()?;
+    Some(TaskTrigger::Daily { hour, minute })
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn scheduled_task() -> Option<TaskTrigger> {
+    // Not implemented for non-Windows platforms
+    None
+}
+
+#[cfg(target_os = "windows")]
+fn schtasks(args: &[&str]) -> Result<String, String> {
+    use std::os::windows::process::CommandExt;
+    
+    let output = std::process::Command::new("schtasks")
+        .args(args)
+        .creation_flags(0x08000000) // CREATE_NO_WINDOW
+        .output()
+        .map_err(|e| format!("schtasks konnte nicht gestartet werden: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(format!("schtasks fehlgeschlagen: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
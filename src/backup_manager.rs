@@ -1,30 +1,79 @@
 // backup_manager.rs - Fixed version
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::{self, Read, Write};
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use html_escape::encode_text;
 use std::thread;
-use std::time::Duration;
-use rusqlite::{Connection, Result as SqlResult};
-use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use rusqlite::{Connection, OpenFlags};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
+use crate::removable_drive;
+use crate::ntfs_compression;
+use crate::differential::{self, BookmarkDiff};
+use crate::storage::git::{self, GitRepository};
+use crate::storage::rclone::{self, RcloneStatus};
+use crate::repository_format;
+use crate::schedule::{self, ScheduleMode};
+use crate::catalog::{self, Catalog};
+use crate::browser_processes;
+use crate::bookmarks::{self, BookmarkFilter, BookmarkFormat, BookmarkLayout, BookmarkTree, FolderPath, RootMapping};
+use crate::export::{self, ExportFormat};
+use crate::favicons::{self, FaviconMap};
+use crate::mozlz4;
+use crate::hooks;
+use crate::cancel::CancellationToken;
+use crate::history::{self, RunRecord, RunTrigger};
+use crate::i18n::Language;
+use crate::theme::Theme;
+use crate::archive::{ArchiveEntry, ArchiveFormat};
+use crate::destinations::{self, DestinationStatus, RemoteDestination, StatusMap, UploadOptions};
+use crate::destinations::google_drive::GoogleDriveConfig;
+use crate::proxy::{self, Proxy, ProxyConfig};
 
-#[derive(Debug)]
-enum BackupError {
-    IoError(std::io::Error),
-    JsonError(serde_json::Error),
-    BrowserNotFound(String),
-}
+// Dateiname (ohne Endung) der festen Kopie des neuesten Backups
+const LATEST_FILE_STEM: &str = "latest";
 
-#[derive(Debug, Clone)]
+// Ordner für beschädigte Backups, die nicht mehr wiederhergestellt werden sollen
+const QUARANTINE_DIR: &str = "Quarantine";
+
+// Stand der Favoriten vor einer Wiederherstellung, je Browser die neuesten PRE_RESTORE_KEEP
+const PRE_RESTORE_DIR: &str = "PreRestore";
+const PRE_RESTORE_KEEP: usize = 10;
+
+// Unterordner mit Firefox' eigenen Sicherungen, im Profil wie im Backup-Ordner
+const FIREFOX_BOOKMARKBACKUPS_DIR: &str = "bookmarkbackups";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupResult {
     pub browser: String,
     pub success: bool,
     pub message: String,
+    pub path: Option<PathBuf>,
 }
 
+// Fortschritt eines laufenden Backups, z.B. für die Anzeige in der GUI
 #[derive(Debug, Clone)]
+pub enum BackupProgress {
+    Started(String),
+    Finished(BackupResult),
+    // Git, Uploads, rclone usw. nach den eigentlichen Kopien
+    PostProcessing,
+}
+
+// Teilweise Wiederherstellung in die aktuellen Favoriten
+enum PartialRestore<'a> {
+    // Gewählte Ordner auf den Stand des Backups bringen
+    Folders(&'a [FolderPath]),
+    // Fehlende Lesezeichen ergänzen, doppelte URLs auslassen
+    Merge,
+    // Nur das Lesezeichen mit dieser URL ergänzen, falls es fehlt
+    Link(&'a str),
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct BackupFile {
     pub name: String,
     pub path: PathBuf,
@@ -32,11 +81,146 @@ pub struct BackupFile {
     pub size: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+// Browserprofil, in das wiederhergestellt werden kann
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowserProfile {
+    // Ordnername, z.B. "Default", "Profile 2" oder "abcd1234.default-release"
+    pub dir: String,
+    // Anzeigename aus dem Browser, sonst der Ordnername
+    pub name: String,
+    pub bookmarks_path: PathBuf,
+    // Das Profil, aus dem die Backups stammen
+    pub default: bool,
+}
+
+// Was eine Wiederherstellung ändern würde, für die Zusammenfassung vor dem Bestätigen
+#[derive(Debug, Clone)]
+pub struct RestoreSummary {
+    pub backup_folders: usize,
+    pub backup_links: usize,
+    // None, wenn das Zielprofil noch keine lesbaren Favoriten hat
+    pub current_links: Option<usize>,
+    // URLs, die durch das Backup hinzukommen bzw. beim Ersetzen wegfallen
+    pub added: usize,
+    pub removed: usize,
+    pub target_path: PathBuf,
+    // Nur bei Wiederherstellung in einem anderen Browser
+    pub mappings: Vec<RootMapping>,
+}
+
+// Kennzahlen eines Browsers für die Statistik-Ansicht
+#[derive(Debug, Clone)]
+pub struct BrowserStatistics {
+    pub browser: &'static str,
+    pub backups: usize,
+    pub total_size: u64,
+    pub last_success: Option<chrono::DateTime<Local>>,
+    // (Ordner, Lesezeichen) im neuesten Backup; Err, wenn es nicht lesbar ist
+    pub bookmarks: Result<(usize, usize), String>,
+    // Anzahl Backups pro Tag, für die Zeitleiste
+    pub days: BTreeMap<chrono::NaiveDate, usize>,
+}
+
+// Welche Backups ein Archiv-Export enthält; Grenzen sind optional,
+// `to` ist ausschließlich
+#[derive(Clone, Debug)]
+pub struct ArchiveSelection {
+    pub browsers: Vec<String>,
+    pub from: Option<chrono::DateTime<Local>>,
+    pub to: Option<chrono::DateTime<Local>>,
+    // Nur diese Dateien, z.B. aus der Mehrfachauswahl der Backup-Liste
+    pub names: Option<Vec<String>>,
+}
+
+impl ArchiveSelection {
+    pub fn includes(&self, backup: &BackupFile) -> bool {
+        self.from.is_none_or(|from| backup.date >= from)
+            && self.to.is_none_or(|to| backup.date < to)
+            && self.names.as_ref().is_none_or(|names| names.contains(&backup.name))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct BackupConfig {
     pub backup_chrome: bool,
     pub backup_edge: bool,
     pub backup_firefox: bool,
+    pub removable_drive_enabled: bool,
+    pub removable_drive_path: Option<PathBuf>,
+    pub removable_drive_last_sync: Option<chrono::DateTime<Local>>,
+    pub auto_cleanup: bool,
+    pub retention_days: i64,
+    pub ntfs_compression: bool,
+    pub differential_backups: bool,
+    pub full_backup_interval: u32,
+    pub git_enabled: bool,
+    pub git_repository_path: Option<PathBuf>,
+    pub git_auto_push: bool,
+    // Nach jedem geplanten Backup ein Archiv aller Backups in diesem Ordner aktualisieren
+    pub auto_export_enabled: bool,
+    pub auto_export_path: Option<PathBuf>,
+    pub auto_export_format: ArchiveFormat,
+    // Server, auf die jedes neue Backup zusätzlich hochgeladen wird
+    pub destinations: Vec<RemoteDestination>,
+    // Weitere Versuche nach einem abgebrochenen Upload
+    pub upload_retries: u32,
+    // Obergrenze für Uploads in KB/s, 0 = unbegrenzt
+    pub upload_limit_kbps: u64,
+    // Gilt für alle Netzwerkfunktionen (Remote-Ziele, rclone)
+    pub proxy: ProxyConfig,
+    // Nach jedem Backup "rclone copy <Backup-Ordner> <Remote>" ausführen
+    pub rclone_enabled: bool,
+    pub rclone_remote: String,
+    // Leer: rclone aus dem PATH
+    pub rclone_executable: Option<PathBuf>,
+    // Mehrere Rechner sichern in denselben (synchronisierten) Ordner:
+    // Dateinamen, latest-Kopie und Konfigurationsdatei erhalten den Rechnernamen
+    pub shared_backup_folder: bool,
+    // Lokale REST-API auf 127.0.0.1; ein leeres Token wird beim Start erzeugt
+    pub rest_api_enabled: bool,
+    pub rest_api_port: u16,
+    pub rest_api_token: String,
+    // Nach jedem Backup die Ergebnisse als JSON per POST an diese URL; leer = aus
+    pub webhook_url: String,
+    // healthchecks.io-Ping nach jedem geplanten Backup, bei Fehlern an <URL>/fail
+    pub healthcheck_url: String,
+    // Shell-Befehle vor und nach jedem Backup-Lauf; leer = keiner.
+    // Schlägt der Vorher-Befehl fehl, wird nicht gesichert.
+    pub pre_backup_command: String,
+    pub post_backup_command: String,
+    // Danach wird ein Befehl abgebrochen und gilt als fehlgeschlagen
+    pub hook_timeout_secs: u64,
+    // Systemweites Tastenkürzel für ein sofortiges Backup, z.B. "Strg+Alt+B"; leer = aus
+    pub backup_hotkey: String,
+    // Sprache der Oberfläche; None = Systemsprache
+    pub language: Option<Language>,
+    pub theme: Theme,
+    pub backup_interval_minutes: u64,
+    // Abweichendes Intervall pro Browser ("Chrome" -> 60), nur im Intervall-Modus
+    pub browser_interval_minutes: BTreeMap<String, u64>,
+    pub schedule_mode: ScheduleMode,
+    pub schedule_times: Vec<chrono::NaiveTime>,
+    pub last_scheduled_run: Option<chrono::DateTime<Local>>,
+    pub backup_on_start: bool,
+    pub backup_on_lock: bool,
+    pub backup_on_shutdown: bool,
+    pub shutdown_backup_timeout_secs: u64,
+    pub watch_bookmark_files: bool,
+    pub watch_debounce_minutes: u64,
+    pub backup_on_browser_exit: bool,
+    pub idle_only: bool,
+    pub idle_minutes: u64,
+}
+
+impl BackupConfig {
+    // Felder, die das Programm selbst fortschreibt, aus `stored` übernehmen: ein
+    // Entwurf aus den Einstellungen hat sonst den Stand beim Öffnen und würde
+    // sie beim Speichern zurücksetzen
+    pub fn keep_runtime_state(&mut self, stored: &BackupConfig) {
+        self.last_scheduled_run = stored.last_scheduled_run;
+        self.removable_drive_last_sync = stored.removable_drive_last_sync;
+    }
 }
 
 impl Default for BackupConfig {
@@ -45,13 +229,82 @@ impl Default for BackupConfig {
             backup_chrome: true,
             backup_edge: true,
             backup_firefox: true,
+            removable_drive_enabled: false,
+            removable_drive_path: None,
+            removable_drive_last_sync: None,
+            auto_cleanup: false,
+            retention_days: 30,
+            ntfs_compression: false,
+            differential_backups: false,
+            full_backup_interval: 10,
+            git_enabled: false,
+            git_repository_path: None,
+            git_auto_push: false,
+            auto_export_enabled: false,
+            auto_export_path: None,
+            auto_export_format: ArchiveFormat::Zip,
+            destinations: Vec::new(),
+            upload_retries: 3,
+            upload_limit_kbps: 0,
+            proxy: ProxyConfig::default(),
+            rclone_enabled: false,
+            rclone_remote: "remote:bookmarks".to_string(),
+            rclone_executable: None,
+            shared_backup_folder: false,
+            rest_api_enabled: false,
+            rest_api_port: 7878,
+            rest_api_token: String::new(),
+            webhook_url: String::new(),
+            healthcheck_url: String::new(),
+            pre_backup_command: String::new(),
+            post_backup_command: String::new(),
+            hook_timeout_secs: 300,
+            backup_hotkey: String::new(),
+            language: None,
+            theme: Theme::System,
+            backup_interval_minutes: 24 * 60,
+            browser_interval_minutes: BTreeMap::new(),
+            schedule_mode: ScheduleMode::Interval,
+            schedule_times: Vec::new(),
+            last_scheduled_run: None,
+            backup_on_start: false,
+            backup_on_lock: false,
+            backup_on_shutdown: false,
+            shutdown_backup_timeout_secs: 5,
+            watch_bookmark_files: false,
+            watch_debounce_minutes: 3,
+            backup_on_browser_exit: false,
+            idle_only: false,
+            idle_minutes: 5,
         }
     }
 }
 
+// Hintergrund-Threads arbeiten auf einer Kopie (siehe snapshot), damit der
+// gemeinsame Mutex nur kurz gesperrt ist; die Felder hinter Arc teilen alle Kopien
+#[derive(Clone)]
 pub struct BackupManager {
     backup_dir: PathBuf,
     config: BackupConfig,
+    removable_drive_error: Option<String>,
+    // Letzter rclone-Lauf; Mutex, weil Backups auch über &self laufen
+    rclone_status: Arc<Mutex<Option<RcloneStatus>>>,
+    // Nur zur Laufzeit: nach einem Neustart laufen automatische Backups wieder
+    paused: bool,
+    // Vom Zeitplan-Thread gesetzt
+    next_scheduled_run: Option<chrono::DateTime<Local>>,
+    // Empfänger für RunSummary, z.B. der Tray-Tooltip
+    run_listeners: Arc<Mutex<Vec<mpsc::Sender<RunSummary>>>>,
+    // Gehalten, solange ein Backup läuft: Läufe aus verschiedenen Kopien nacheinander
+    run_lock: Arc<Mutex<()>>,
+}
+
+// Abschluss eines Backup-Laufs, egal ob Zeitplan, Tray, GUI oder Tastenkürzel
+#[derive(Clone, Copy, Debug)]
+pub struct RunSummary {
+    pub finished: chrono::DateTime<Local>,
+    pub successful: usize,
+    pub total: usize,
 }
 
 impl BackupManager {
@@ -59,13 +312,88 @@ impl BackupManager {
         let mut manager = Self {
             backup_dir: Self::get_default_backup_dir(),
             config: BackupConfig::default(),
+            removable_drive_error: None,
+            rclone_status: Arc::default(),
+            paused: false,
+            next_scheduled_run: None,
+            run_listeners: Arc::default(),
+            run_lock: Arc::default(),
         };
         
         manager.ensure_backup_dir().ok();
         manager.load_config();
+        if let Err(e) = manager.migrate_repository() {
+            log::error!("Migration des Backup-Ordners fehlgeschlagen: {}", e);
+        }
+        if manager.config.shared_backup_folder {
+            manager.merge_catalog();
+        }
         manager
     }
     
+    // Bringt einen Backup-Ordner älterer Versionen schrittweise auf das aktuelle Layout
+    fn migrate_repository(&self) -> Result<(), String> {
+        let browsers = ["Chrome", "Edge", "Firefox"];
+        let version = match repository_format::read_version(&self.backup_dir) {
+            Some(version) => version,
+            None if repository_format::is_new_repository(&self.backup_dir, &browsers) => {
+                repository_format::CURRENT_VERSION
+            }
+            None => 1,
+        };
+        
+        if version > repository_format::CURRENT_VERSION {
+            return Err(format!(
+                "Backup-Ordner hat Format-Version {}, unterstützt wird bis Version {}",
+                version, repository_format::CURRENT_VERSION
+            ));
+        }
+        
+        for from in version..repository_format::CURRENT_VERSION {
+            match from {
+                1 => self.migrate_v1_to_v2(&browsers)?,
+                2 => {
+                    self.rebuild_catalog()?;
+                }
+                _ => {}
+            }
+            repository_format::write_version(&self.backup_dir, from + 1)
+                .map_err(|e| format!("Fehler beim Schreiben der Versionskennung: {}", e))?;
+            log::info!("Backup-Ordner auf Format-Version {} migriert", from + 1);
+        }
+        
+        if version == repository_format::CURRENT_VERSION && repository_format::read_version(&self.backup_dir).is_none() {
+            repository_format::write_version(&self.backup_dir, version)
+                .map_err(|e| format!("Fehler beim Schreiben der Versionskennung: {}", e))?;
+        }
+        
+        Ok(())
+    }
+    
+    // Version 2 führt latest.<ext> ein: aus dem neuesten vorhandenen Backup erzeugen
+    fn migrate_v1_to_v2(&self, browsers: &[&str]) -> Result<(), String> {
+        for browser in browsers {
+            let latest = match self.get_backup_list(browser).into_iter().next() {
+                Some(latest) => latest,
+                None => continue,
+            };
+            let browser_dir = self.backup_dir.join(browser);
+            
+            if Self::is_differential_backup(&latest.path) {
+                let content = self.read_backup_content(&latest.path)?;
+                fs::write(browser_dir.join(format!("{}.json", self.latest_stem())), content)
+                    .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+            } else {
+                let extension = latest.path.extension()
+                    .map(|e| e.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.update_latest_copy(&browser_dir, &latest.path, &extension)
+                    .map_err(|e| format!("Fehler beim Kopieren: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+    
     fn get_default_backup_dir() -> PathBuf {
         let user_profile = std::env::var("USERPROFILE")
             .unwrap_or_else(|_| dirs::home_dir()
@@ -85,8 +413,19 @@ impl BackupManager {
         Ok(())
     }
     
+    // Im gemeinsamen Ordner hat jeder Rechner seine eigene Konfiguration
+    // (Pfade, Zeitplan, Zugangsdaten); config.json ist die ohne Rechnernamen
+    fn machine_config_file(&self) -> PathBuf {
+        self.backup_dir.join(format!("config_{}.json", Self::machine_name()))
+    }
+    
     fn load_config(&mut self) {
-        let config_file = self.backup_dir.join("config.json");
+        let machine_file = self.machine_config_file();
+        let config_file = if machine_file.exists() {
+            machine_file
+        } else {
+            self.backup_dir.join("config.json")
+        };
         if config_file.exists() {
             if let Ok(content) = fs::read_to_string(&config_file) {
                 if let Ok(config) = serde_json::from_str(&content) {
@@ -97,7 +436,14 @@ impl BackupManager {
     }
     
     pub fn save_config(&self) {
-        let config_file = self.backup_dir.join("config.json");
+        let machine_file = self.machine_config_file();
+        let config_file = if self.config.shared_backup_folder {
+            machine_file
+        } else {
+            // Sonst würde die alte Datei beim nächsten Start wieder gelesen
+            fs::remove_file(&machine_file).ok();
+            self.backup_dir.join("config.json")
+        };
         if let Ok(content) = serde_json::to_string_pretty(&self.config) {
             fs::write(config_file, content).ok();
         }
@@ -107,6 +453,34 @@ impl BackupManager {
         &self.config
     }
     
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    
+    // None, wenn pausiert oder kein Zeitplan eingerichtet ist
+    pub fn next_scheduled_backup(&self) -> Option<chrono::DateTime<Local>> {
+        self.next_scheduled_run
+    }
+    
+    // Kopie für lange Vorgänge außerhalb des Mutex; Änderungen an der Kopie
+    // (Konfiguration, Pause) wirken nicht auf den gemeinsamen Manager zurück
+    pub fn snapshot(backup_manager: &Mutex<BackupManager>) -> Option<BackupManager> {
+        backup_manager.lock().ok().map(|manager| manager.clone())
+    }
+    
+    pub fn subscribe_runs(&self) -> mpsc::Receiver<RunSummary> {
+        let (sender, receiver) = mpsc::channel();
+        self.run_listeners.lock().unwrap().push(sender);
+        receiver
+    }
+    
+    pub fn set_paused(&mut self, paused: bool) {
+        if self.paused != paused {
+            log::info!("Automatische Backups {}", if paused { "pausiert" } else { "fortgesetzt" });
+        }
+        self.paused = paused;
+    }
+    
     pub fn set_config(&mut self, config: BackupConfig) {
         self.config = config;
         self.save_config();
@@ -116,84 +490,676 @@ impl BackupManager {
         &self.backup_dir
     }
     
-    pub fn backup_all(&self) -> Vec<BackupResult> {
+    // NTFS-Komprimierung entsprechend der Konfiguration auf den Backup-Ordner anwenden
+    pub fn apply_ntfs_compression(&self) -> Result<usize, String> {
+        ntfs_compression::set_compression_recursive(&self.backup_dir, self.config.ntfs_compression)
+            .map_err(|e| format!("NTFS-Komprimierung konnte nicht geändert werden: {}", e))
+    }
+    
+    pub fn backup_all(&self, trigger: RunTrigger) -> Vec<BackupResult> {
+        self.backup_browsers(&self.enabled_browsers(), trigger)
+    }
+    
+    pub fn enabled_browsers(&self) -> Vec<&'static str> {
+        let mut browsers = Vec::new();
+        if self.config.backup_chrome { browsers.push("Chrome"); }
+        if self.config.backup_edge { browsers.push("Edge"); }
+        if self.config.backup_firefox { browsers.push("Firefox"); }
+        browsers
+    }
+    
+    pub fn interval_minutes_for(&self, browser: &str) -> u64 {
+        self.config.browser_interval_minutes
+            .get(browser)
+            .copied()
+            .unwrap_or(self.config.backup_interval_minutes)
+    }
+    
+    pub fn backup_browsers(&self, browsers: &[&str], trigger: RunTrigger) -> Vec<BackupResult> {
+        self.backup_browsers_with_progress(browsers, trigger, false, &CancellationToken::default(), &|_| {})
+    }
+    
+    // Nach einem Abbruch gelten die restlichen Browser als fehlgeschlagen; bereits
+    // geschriebene Backups bleiben, nur Uploads und rclone entfallen.
+    // Probelauf (`dry_run`): nur melden, was gesichert würde; das gilt auch für
+    // Bereinigung und Wiederherstellung und immer nur für den einzelnen Aufruf.
+    pub fn backup_browsers_with_progress(&self, browsers: &[&str], trigger: RunTrigger, dry_run: bool, cancel: &CancellationToken, progress: &dyn Fn(BackupProgress)) -> Vec<BackupResult> {
+        if dry_run {
+            return browsers.iter().map(|browser| self.plan_backup(browser)).collect();
+        }
+        
+        let _running = self.run_lock.lock().unwrap_or_else(PoisonError::into_inner);
+        
+        if !self.config.pre_backup_command.trim().is_empty() {
+            if let Err(e) = hooks::run("Vorher-Befehl", &self.config.pre_backup_command, &self.hook_env(browsers, None), self.hook_timeout()) {
+                // z.B. Container nicht eingehängt: lieber gar nicht sichern als an den falschen Ort
+                log::error!("{}", e);
+                let results: Vec<BackupResult> = browsers.iter()
+                    .map(|browser| BackupResult {
+                        browser: browser.to_string(),
+                        success: false,
+                        message: format!("Nicht gesichert: {}", e),
+                        path: None,
+                    })
+                    .collect();
+                self.finish_run(trigger, &results);
+                return results;
+            }
+        }
+        
         let mut results = Vec::new();
         
-        if self.config.backup_chrome {
-            results.push(self.backup_chrome());
+        for browser in browsers {
+            if cancel.is_cancelled() {
+                let result = BackupResult {
+                    browser: browser.to_string(),
+                    success: false,
+                    message: "Abgebrochen".to_string(),
+                    path: None,
+                };
+                progress(BackupProgress::Finished(result.clone()));
+                results.push(result);
+                continue;
+            }
+            progress(BackupProgress::Started(browser.to_string()));
+            let result = match *browser {
+                "Chrome" => self.backup_chrome(cancel),
+                "Edge" => self.backup_edge(cancel),
+                "Firefox" => self.backup_firefox(cancel),
+                _ => continue,
+            };
+            // Jeder Lauf landet im Protokoll, egal ob Zeitplan, Tray, GUI oder Kommandozeile
+            if result.success {
+                log::info!("✓ {} backup successful: {}", result.browser, result.message);
+            } else {
+                log::error!("✗ {} backup failed: {}", result.browser, result.message);
+            }
+            progress(BackupProgress::Finished(result.clone()));
+            results.push(result);
+        }
+        progress(BackupProgress::PostProcessing);
+        
+        let now = Local::now();
+        self.update_catalog(|catalog| {
+            for result in results.iter().filter(|r| r.success) {
+                if let Some(path) = &result.path {
+                    catalog.record_backup(&result.browser, path, now);
+                    // Für die Wiederherstellung unter einem anderen Benutzer oder Profil
+                    if let (Some(name), Ok(source)) = (path.file_name(), Self::current_bookmarks_path(&result.browser)) {
+                        catalog.set_source(&result.browser, &name.to_string_lossy(), &source);
+                    }
+                }
+            }
+        });
+        
+        if self.config.git_enabled {
+            self.commit_to_git(&mut results);
+        }
+        
+        if cancel.is_cancelled() {
+            log::info!("Backup abgebrochen, Uploads und rclone entfallen");
+        } else {
+            self.upload_to_destinations(&mut results);
+            
+            if self.config.rclone_enabled && results.iter().any(|r| r.success) {
+                self.run_rclone();
+            }
         }
         
-        if self.config.backup_edge {
-            results.push(self.backup_edge());
+        if !self.config.post_backup_command.trim().is_empty() {
+            if let Err(e) = hooks::run("Nachher-Befehl", &self.config.post_backup_command, &self.hook_env(browsers, Some(&results)), self.hook_timeout()) {
+                log::error!("{}", e);
+                for result in results.iter_mut().filter(|r| r.success) {
+                    result.message.push_str(&format!(" ({})", e));
+                }
+            }
         }
         
-        if self.config.backup_firefox {
-            results.push(self.backup_firefox());
+        if !self.config.webhook_url.is_empty() {
+            if let Err(e) = self.send_webhook(&self.config.webhook_url, &results) {
+                log::error!("{}", e);
+            }
         }
         
+        self.finish_run(trigger, &results);
         results
     }
     
-    fn backup_chrome(&self) -> BackupResult {
+    // Lauf in den Verlauf schreiben und an Tray usw. melden
+    fn finish_run(&self, trigger: RunTrigger, results: &[BackupResult]) {
+        let finished = Local::now();
+        let record = RunRecord { finished, trigger, results: results.to_vec() };
+        if let Err(e) = history::append(&self.backup_dir, &record) {
+            log::error!("{}", e);
+        }
+        
+        let summary = RunSummary {
+            finished,
+            successful: results.iter().filter(|r| r.success).count(),
+            total: results.len(),
+        };
+        // Abgemeldete Empfänger fallen dabei heraus
+        self.run_listeners.lock().unwrap().retain(|listener| listener.send(summary).is_ok());
+    }
+    
+    pub fn load_history(&self) -> Result<Vec<RunRecord>, String> {
+        history::load(&self.backup_dir)
+    }
+    
+    fn hook_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.hook_timeout_secs.max(1))
+    }
+    
+    // Umgebung für Vorher-/Nachher-Befehl; die Ergebnisse gibt es nur nachher
+    fn hook_env(&self, browsers: &[&str], results: Option<&[BackupResult]>) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("BBB_BACKUP_DIR", self.backup_dir.display().to_string()),
+            ("BBB_BROWSERS", browsers.join(",")),
+        ];
+        if let Some(results) = results {
+            let successful = results.iter().filter(|r| r.success).count();
+            let result = if successful == results.len() {
+                "success"
+            } else if successful == 0 {
+                "failed"
+            } else {
+                "partial"
+            };
+            let files: Vec<String> = results.iter()
+                .filter_map(|r| r.path.as_ref())
+                .map(|path| path.display().to_string())
+                .collect();
+            env.push(("BBB_RESULT", result.to_string()));
+            env.push(("BBB_SUCCESSFUL", successful.to_string()));
+            env.push(("BBB_FAILED", (results.len() - successful).to_string()));
+            env.push(("BBB_FILES", files.join(";")));
+        }
+        env
+    }
+    
+    // Ohne Ergebnisse dient der Aufruf als Test aus den Einstellungen
+    pub fn send_webhook(&self, url: &str, results: &[BackupResult]) -> Result<(), String> {
+        let entries: Vec<serde_json::Value> = results.iter()
+            .map(|result| serde_json::json!({
+                "browser": result.browser,
+                "success": result.success,
+                "message": result.message,
+                "path": result.path,
+                "size": result.path.as_ref().and_then(|p| fs::metadata(p).ok()).map(|m| m.len()),
+            }))
+            .collect();
+        let successful = results.iter().filter(|r| r.success).count();
+        let payload = serde_json::json!({
+            "machine": Self::machine_name(),
+            "finished": Local::now(),
+            "successful": successful,
+            "failed": results.len() - successful,
+            "results": entries,
+        });
+        
+        proxy::agent(self.proxy().as_ref(), url)
+            .post(url)
+            .timeout(Duration::from_secs(15))
+            .send_json(payload)
+            .map_err(|e| format!("Webhook {} fehlgeschlagen: {}", url, e))?;
+        Ok(())
+    }
+    
+    pub fn destination_status(&self) -> StatusMap {
+        destinations::load_status(&self.backup_dir)
+    }
+    
+    // Ergebnis eines Verbindungstests aus den Einstellungen festhalten
+    pub fn record_destination_check(&self, label: &str, result: &Result<String, String>) {
+        self.update_destination_status(label, |status| {
+            status.last_check = Some(Local::now());
+            status.last_error = result.as_ref().err().cloned();
+        });
+    }
+    
+    fn update_destination_status<F: FnOnce(&mut DestinationStatus)>(&self, label: &str, update: F) {
+        let mut status = destinations::load_status(&self.backup_dir);
+        update(status.entry(label.to_string()).or_default());
+        if let Err(e) = destinations::save_status(&self.backup_dir, &status) {
+            log::error!("Status der Remote-Ziele konnte nicht gespeichert werden: {}", e);
+        }
+    }
+    
+    // Aktivierte Ziele, deren letzter Upload oder Test fehlgeschlagen ist
+    pub fn failing_destinations(&self) -> Vec<(String, String)> {
+        let status = self.destination_status();
+        self.config.destinations.iter()
+            .filter(|d| d.enabled)
+            .filter_map(|d| {
+                let label = d.config.destination().label();
+                let error = status.get(&label)?.last_error.clone()?;
+                Some((label, error))
+            })
+            .collect()
+    }
+    
+    pub fn proxy(&self) -> Option<Proxy> {
+        self.config.proxy.resolve()
+    }
+    
+    fn run_rclone(&self) {
+        let result = rclone::copy(
+            self.config.rclone_executable.as_deref(),
+            &self.backup_dir,
+            &self.config.rclone_remote,
+            self.config.proxy.mode,
+            self.proxy().as_ref(),
+        );
+        if let Err(e) = &result {
+            log::error!("rclone fehlgeschlagen: {}", e);
+        }
+        *self.rclone_status.lock().unwrap() = Some(RcloneStatus { finished: Local::now(), result });
+    }
+    
+    pub fn rclone_status(&self) -> Option<String> {
+        if !self.config.rclone_enabled {
+            return None;
+        }
+        let status = self.rclone_status.lock().unwrap().clone();
+        match status {
+            None => Some(format!("rclone nach {}: seit dem Start noch nicht ausgeführt", self.config.rclone_remote)),
+            Some(RcloneStatus { finished, result: Ok(summary) }) => Some(format!(
+                "rclone nach {} am {}: {}",
+                self.config.rclone_remote,
+                finished.format("%d.%m.%Y %H:%M"),
+                if summary.is_empty() { "erfolgreich" } else { summary.as_str() }
+            )),
+            Some(RcloneStatus { finished, result: Err(e) }) => Some(format!(
+                "rclone nach {} am {} fehlgeschlagen: {}",
+                self.config.rclone_remote,
+                finished.format("%d.%m.%Y %H:%M"),
+                e
+            )),
+        }
+    }
+    
+    pub fn get_catalog(&self) -> Catalog {
+        Catalog::load(&self.backup_dir)
+    }
+    
+    fn update_catalog<F: FnOnce(&mut Catalog)>(&self, update: F) {
+        let mut catalog = Catalog::load(&self.backup_dir);
+        update(&mut catalog);
+        if let Err(e) = catalog.save(&self.backup_dir) {
+            log::error!("Katalog konnte nicht gespeichert werden: {}", e);
+        }
+    }
+    
+    // Summen pro Browser aus dem Katalog; nur für die Zahl der Lesezeichen
+    // wird das neueste Backup gelesen
+    pub fn statistics(&self) -> Vec<BrowserStatistics> {
+        let catalog = self.get_catalog();
+        ["Chrome", "Edge", "Firefox"].into_iter()
+            .map(|browser| {
+                let entries: Vec<_> = catalog.entries.iter().filter(|e| e.browser == browser).collect();
+                let bookmarks = match entries.iter().max_by_key(|e| e.created) {
+                    Some(latest) => self
+                        .load_bookmark_tree(browser, &self.backup_dir.join(browser).join(&latest.file_name))
+                        .map(|tree| tree.counts()),
+                    None => Err("Kein Backup vorhanden".to_string()),
+                };
+                let mut days = BTreeMap::new();
+                for entry in &entries {
+                    *days.entry(entry.created.date_naive()).or_insert(0) += 1;
+                }
+                BrowserStatistics {
+                    browser,
+                    backups: entries.len(),
+                    total_size: entries.iter().map(|e| e.size).sum(),
+                    last_success: catalog.last_success.get(&self.success_key(browser)).copied(),
+                    bookmarks,
+                    days,
+                }
+            })
+            .collect()
+    }
+    
+    // Anzahl und Größe je Browser laut Katalog, ohne den Backup-Ordner zu durchsuchen
+    pub fn disk_usage(&self) -> Vec<(&'static str, usize, u64)> {
+        let catalog = self.get_catalog();
+        ["Chrome", "Edge", "Firefox"].into_iter()
+            .map(|browser| {
+                let (count, size) = catalog.usage(browser);
+                (browser, count, size)
+            })
+            .collect()
+    }
+    
+    // Katalog aus den vorhandenen Dateien neu aufbauen
+    pub fn rebuild_catalog(&self) -> Result<usize, String> {
+        let mut catalog = Catalog::default();
+        for browser in &["Chrome", "Edge", "Firefox"] {
+            for backup in self.get_backup_list(browser) {
+                let created = catalog::timestamp_from_file_name(&backup.name).unwrap_or(backup.date);
+                catalog.record_backup(browser, &backup.path, created);
+            }
+        }
+        
+        let count = catalog.entries.len();
+        catalog.save(&self.backup_dir)
+            .map_err(|e| format!("Katalog konnte nicht gespeichert werden: {}", e))?;
+        Ok(count)
+    }
+    
+    // Backups, die ein anderer Rechner in den gemeinsamen Ordner gelegt hat,
+    // ohne dass sein Katalog angekommen ist, nachtragen
+    pub fn merge_catalog(&self) {
+        let mut added = 0;
+        self.update_catalog(|catalog| {
+            for browser in &["Chrome", "Edge", "Firefox"] {
+                for backup in self.get_backup_list(browser) {
+                    if catalog.contains(browser, &backup.name) {
+                        continue;
+                    }
+                    let created = catalog::timestamp_from_file_name(&backup.name).unwrap_or(backup.date);
+                    catalog.record_backup(browser, &backup.path, created);
+                    added += 1;
+                }
+            }
+        });
+        if added > 0 {
+            log::info!("{} Backups anderer Rechner in den Katalog übernommen", added);
+        }
+    }
+    
+    // Rechnername für Dateinamen, nur Buchstaben, Ziffern und Bindestriche
+    pub fn machine_name() -> String {
+        let name = std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_default();
+        let name: String = name.trim()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+            .collect();
+        if name.is_empty() { "Rechner".to_string() } else { name }
+    }
+    
+    fn own_machine(&self) -> Option<String> {
+        self.config.shared_backup_folder.then(Self::machine_name)
+    }
+    
+    // "_LAPTOP" im gemeinsamen Ordner, sonst leer
+    fn machine_suffix(&self) -> String {
+        self.own_machine().map(|m| format!("_{}", m)).unwrap_or_default()
+    }
+    
+    fn success_key(&self, browser: &str) -> String {
+        catalog::success_key(browser, self.own_machine().as_deref())
+    }
+    
+    // Ältestes "letztes erfolgreiches Backup" der aktivierten Browser;
+    // None, wenn ein aktivierter Browser noch nie gesichert wurde
+    pub fn last_successful_backup(&self) -> Option<chrono::DateTime<Local>> {
+        let catalog = self.get_catalog();
+        self.enabled_browsers().iter()
+            .map(|browser| catalog.last_success.get(&self.success_key(browser)).copied())
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()
+    }
+    
+    // Ein Lauf ist überflüssig, wenn alle Browser innerhalb ihres Intervalls gesichert wurden
+    pub fn has_recent_backup(&self) -> bool {
+        let catalog = self.get_catalog();
+        let now = Local::now();
+        self.enabled_browsers().iter().all(|browser| {
+            catalog.last_success.get(&self.success_key(browser))
+                .map(|last| now < schedule::next_interval_run(*last, self.interval_minutes_for(browser)))
+                .unwrap_or(false)
+        })
+    }
+    
+    // Normalisierte Lesezeichen der neuen Backups ins Git-Repository committen
+    fn commit_to_git(&self, results: &mut [BackupResult]) {
+        let repository = match self.config.git_repository_path.as_deref().map(GitRepository::open_or_init) {
+            Some(Ok(repository)) => repository,
+            Some(Err(e)) => {
+                log::error!("Git-Repository nicht verfügbar: {}", e);
+                return;
+            }
+            None => return,
+        };
+        
+        let mut committed = false;
+        for result in results.iter_mut().filter(|r| r.success) {
+            let backup_path = match &result.path {
+                Some(path) => path.clone(),
+                None => continue,
+            };
+            
+            let bookmarks = if result.browser == "Firefox" {
+                git::normalize_firefox(&backup_path)
+            } else {
+                self.read_backup_content(&backup_path)
+                    .and_then(|content| serde_json::from_str(&content)
+                        .map_err(|e| format!("JSON Parse Fehler: {}", e)))
+                    .map(|value| git::normalize_chromium(&value))
+            };
+            
+            match bookmarks.and_then(|b| repository.commit_snapshot(&result.browser, &b)) {
+                Ok(Some(commit_message)) => {
+                    committed = true;
+                    log::info!("Git-Commit erstellt: {}", commit_message);
+                }
+                Ok(None) => {}
+                Err(e) => result.message.push_str(&format!(" (Git: {})", e)),
+            }
+        }
+        
+        // Im Hintergrund, ein langsamer Server soll Uploads und Meldungen nicht aufhalten
+        if committed && self.config.git_auto_push {
+            thread::spawn(move || {
+                if let Err(e) = repository.push() {
+                    log::error!("Git push fehlgeschlagen: {}", e);
+                }
+            });
+        }
+    }
+    
+    // Neue Backups auf alle aktivierten Remote-Ziele hochladen; Fehler
+    // landen in der Meldung, das lokale Backup bleibt erfolgreich
+    fn upload_to_destinations(&self, results: &mut [BackupResult]) {
+        let files: Vec<ArchiveEntry> = results.iter()
+            .filter(|r| r.success)
+            .filter_map(|r| {
+                let path = r.path.as_ref()?;
+                let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                Some(ArchiveEntry {
+                    name: format!("{}/{}", r.browser, path.file_name()?.to_string_lossy()),
+                    path: path.clone(),
+                    size,
+                })
+            })
+            .collect();
+        if files.is_empty() {
+            return;
+        }
+        
+        let options = UploadOptions {
+            retries: self.config.upload_retries,
+            bytes_per_second: self.config.upload_limit_kbps * 1024,
+            proxy: self.proxy(),
+        };
+        for remote in self.config.destinations.iter().filter(|d| d.enabled) {
+            let destination = remote.config.destination();
+            let result = destinations::upload_with_retries(destination, &files, &options);
+            self.update_destination_status(&destination.label(), |status| {
+                match &result {
+                    Ok(_) => {
+                        status.last_upload = Some(Local::now());
+                        status.last_error = None;
+                    }
+                    Err(e) => status.last_error = Some(e.clone()),
+                }
+            });
+            match result {
+                Ok(count) => log::info!("{} Dateien hochgeladen: {}", count, destination.label()),
+                Err(e) => {
+                    log::error!("Upload fehlgeschlagen ({}): {}", destination.label(), e);
+                    for result in results.iter_mut().filter(|r| r.success) {
+                        result.message.push_str(&format!(" (Upload {}: {})", destination.label(), e));
+                    }
+                }
+            }
+        }
+    }
+    
+    fn chrome_bookmarks_path() -> PathBuf {
         let user_profile = std::env::var("USERPROFILE").unwrap_or_default();
-        let bookmarks_path = PathBuf::from(user_profile)
+        PathBuf::from(user_profile)
             .join("AppData")
             .join("Local")
             .join("Google")
             .join("Chrome")
             .join("User Data")
             .join("Default")
-            .join("Bookmarks");
-        
-        self.backup_browser_file("Chrome", &bookmarks_path, "json")
+            .join("Bookmarks")
     }
     
-    fn backup_edge(&self) -> BackupResult {
+    fn edge_bookmarks_path() -> PathBuf {
         let user_profile = std::env::var("USERPROFILE").unwrap_or_default();
-        let bookmarks_path = PathBuf::from(user_profile)
+        PathBuf::from(user_profile)
             .join("AppData")
             .join("Local")
             .join("Microsoft")
             .join("Edge")
             .join("User Data")
             .join("Default")
-            .join("Bookmarks");
-        
-        self.backup_browser_file("Edge", &bookmarks_path, "json")
+            .join("Bookmarks")
     }
     
-    fn backup_firefox(&self) -> BackupResult {
+    fn firefox_profiles_dir() -> PathBuf {
         let user_profile = std::env::var("USERPROFILE").unwrap_or_default();
-        let profiles_path = PathBuf::from(user_profile)
+        PathBuf::from(user_profile)
             .join("AppData")
             .join("Roaming")
             .join("Mozilla")
             .join("Firefox")
-            .join("Profiles");
-        
-        if let Ok(entries) = fs::read_dir(&profiles_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.to_string_lossy().ends_with(".default-release") {
-                    let places_db = path.join("places.sqlite");
-                    return self.backup_browser_file("Firefox", &places_db, "sqlite");
-                }
+            .join("Profiles")
+    }
+    
+    fn find_firefox_profile() -> Option<PathBuf> {
+        fs::read_dir(Self::firefox_profiles_dir()).ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.is_dir() && path.to_string_lossy().ends_with(".default-release"))
+    }
+    
+    fn backup_chrome(&self, cancel: &CancellationToken) -> BackupResult {
+        self.backup_browser_file("Chrome", &Self::chrome_bookmarks_path(), "json", cancel)
+    }
+    
+    fn backup_edge(&self, cancel: &CancellationToken) -> BackupResult {
+        self.backup_browser_file("Edge", &Self::edge_bookmarks_path(), "json", cancel)
+    }
+    
+    fn backup_firefox(&self, cancel: &CancellationToken) -> BackupResult {
+        if let Some(profile) = Self::find_firefox_profile() {
+            let places_db = profile.join("places.sqlite");
+            let mut result = self.backup_browser_file("Firefox", &places_db, "sqlite", cancel);
+            
+            // Firefox' eigene komprimierte Sicherungen zusätzlich übernehmen; sie
+            // lassen sich direkt über "Wiederherstellen" in der Bibliothek einspielen
+            match self.copy_firefox_bookmarkbackups(&profile, cancel) {
+                Ok(0) => {}
+                Ok(count) => result.message.push_str(&format!(" (+{} Firefox-Sicherung(en))", count)),
+                Err(e) => log::warn!("Firefox-Sicherungen nicht übernommen: {}", e),
             }
+            return result;
         }
         
         BackupResult {
             browser: "Firefox".to_string(),
             success: false,
             message: "Firefox Profil nicht gefunden".to_string(),
+            path: None,
+        }
+    }
+    
+    // Kopiert neue Dateien aus <Profil>/bookmarkbackups nach Firefox/bookmarkbackups
+    fn copy_firefox_bookmarkbackups(&self, profile: &Path, cancel: &CancellationToken) -> Result<usize, String> {
+        let source_dir = profile.join(FIREFOX_BOOKMARKBACKUPS_DIR);
+        let entries = match fs::read_dir(&source_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(0),
+        };
+        
+        let target_dir = self.backup_dir.join("Firefox").join(FIREFOX_BOOKMARKBACKUPS_DIR);
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+        
+        // Was die Bereinigung schon gelöscht hätte, nicht erneut übernehmen
+        let cutoff = self.config.auto_cleanup
+            .then(|| SystemTime::now() - Duration::from_secs(self.config.retention_days.max(0) as u64 * 24 * 60 * 60));
+        let mut copied = 0;
+        for entry in entries.flatten() {
+            cancel.check()?;
+            let path = entry.path();
+            let is_jsonlz4 = path.extension().map(|e| e == "jsonlz4").unwrap_or(false);
+            let target = target_dir.join(entry.file_name());
+            if !is_jsonlz4 || target.exists() {
+                continue;
+            }
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            if let (Some(cutoff), Some(modified)) = (cutoff, modified) {
+                if modified < cutoff {
+                    continue;
+                }
+            }
+            fs::copy(&path, &target)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            copied += 1;
+        }
+        Ok(copied)
+    }
+    
+    // Probelauf: beschreibt das Backup eines Browsers, ohne etwas zu schreiben
+    fn plan_backup(&self, browser: &str) -> BackupResult {
+        let (source_path, extension) = match browser {
+            "Chrome" => (Some(Self::chrome_bookmarks_path()), "json"),
+            "Edge" => (Some(Self::edge_bookmarks_path()), "json"),
+            _ => (Self::find_firefox_profile().map(|profile| profile.join("places.sqlite")), "sqlite"),
+        };
+        let source_path = match source_path.filter(|path| path.exists()) {
+            Some(path) => path,
+            None => {
+                return BackupResult {
+                    browser: browser.to_string(),
+                    success: false,
+                    message: "Favoriten nicht gefunden".to_string(),
+                    path: None,
+                };
+            }
+        };
+        
+        let size = fs::metadata(&source_path).map(|m| m.len()).unwrap_or(0);
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+        let target = self.backup_dir.join(browser)
+            .join(format!("bookmarks_{}{}.{}", timestamp, self.machine_suffix(), extension));
+        let kind = if extension == "json" && self.config.differential_backups { " (ggf. als Differenz)" } else { "" };
+        BackupResult {
+            browser: browser.to_string(),
+            success: true,
+            message: format!(
+                "Probelauf: würde {} ({:.1} KB) nach {}{} sichern",
+                source_path.display(), size as f64 / 1024.0, target.display(), kind
+            ),
+            path: None,
         }
     }
     
-    fn backup_browser_file(&self, browser: &str, source_path: &Path, extension: &str) -> BackupResult {
+    fn backup_browser_file(&self, browser: &str, source_path: &Path, extension: &str, cancel: &CancellationToken) -> BackupResult {
         if !source_path.exists() {
             return BackupResult {
                 browser: browser.to_string(),
                 success: false,
                 message: "Favoriten nicht gefunden".to_string(),
+                path: None,
             };
         }
         
@@ -203,27 +1169,313 @@ impl BackupManager {
                 browser: browser.to_string(),
                 success: false,
                 message: format!("Fehler beim Erstellen des Verzeichnisses: {}", e),
+                path: None,
             };
         }
         
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let backup_filename = format!("bookmarks_{}.{}", timestamp, extension);
+        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+        
+        if extension == "json" && self.config.differential_backups {
+            match self.create_differential_backup(browser, source_path, &browser_backup_dir, &timestamp) {
+                Ok(Some(result)) => return result,
+                Ok(None) => {}
+                Err(e) => log::warn!("Differenzielles Backup für {} fehlgeschlagen, erstelle Vollbackup: {}", browser, e),
+            }
+        }
+        
+        let backup_filename = format!("bookmarks_{}{}.{}", timestamp, self.machine_suffix(), extension);
         let backup_path = browser_backup_dir.join(&backup_filename);
         
-        match fs::copy(source_path, &backup_path) {
-            Ok(_) => BackupResult {
-                browser: browser.to_string(),
-                success: true,
-                message: format!("Gesichert: {}", backup_filename),
-            },
-            Err(e) => BackupResult {
+        // Erst als .tmp: Wird der Prozess beim Kopieren beendet (z.B. beim
+        // Herunterfahren) oder der Lauf abgebrochen, bleibt kein halbes Backup
+        // unter gültigem Namen zurück
+        let temp_path = browser_backup_dir.join(format!("{}.tmp", backup_filename));
+        let copied = fs::copy(source_path, &temp_path)
+            .map_err(|e| format!("Fehler: {}", e))
+            .and_then(|_| cancel.check())
+            .and_then(|_| fs::rename(&temp_path, &backup_path).map_err(|e| format!("Fehler: {}", e)));
+        if copied.is_err() {
+            fs::remove_file(&temp_path).ok();
+        }
+        
+        match copied {
+            Ok(_) => {
+                if let Err(e) = self.verify_backup(&backup_path) {
+                    let quarantined = self.quarantine_backup(browser, &backup_path);
+                    return BackupResult {
+                        browser: browser.to_string(),
+                        success: false,
+                        message: match quarantined {
+                            Ok(_) => format!("Backup beschädigt und in Quarantäne verschoben: {}", e),
+                            Err(qe) => format!("Backup beschädigt: {} ({})", e, qe),
+                        },
+                        path: None,
+                    };
+                }
+                
+                let mut message = format!("Gesichert: {}", backup_filename);
+                if let Err(e) = self.update_latest_copy(&browser_backup_dir, &backup_path, extension) {
+                    message.push_str(&format!(" (latest.{} nicht aktualisiert: {})", extension, e));
+                }
+                BackupResult {
+                    browser: browser.to_string(),
+                    success: true,
+                    message,
+                    path: Some(backup_path),
+                }
+            }
+            Err(message) => BackupResult {
                 browser: browser.to_string(),
                 success: false,
-                message: format!("Fehler: {}", e),
+                message,
+                path: None,
             },
         }
     }
     
+    // Speichert nur die Änderungen gegenüber dem letzten Vollbackup.
+    // Ok(None) bedeutet, dass ein neues Vollbackup fällig ist.
+    fn create_differential_backup(
+        &self,
+        browser: &str,
+        source_path: &Path,
+        browser_backup_dir: &Path,
+        timestamp: &str,
+    ) -> Result<Option<BackupResult>, String> {
+        // Dateinamen enthalten den Zeitstempel und sind damit chronologisch sortierbar
+        let own_machine = self.own_machine();
+        let mut names: Vec<String> = fs::read_dir(browser_backup_dir)
+            .map_err(|e| format!("Fehler beim Lesen: {}", e))?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("bookmarks_") && name.ends_with(".json"))
+            // Differenzen nur gegen Vollbackups dieses Rechners
+            .filter(|name| catalog::machine_from_file_name(name) == own_machine.as_deref())
+            .collect();
+        names.sort();
+        
+        let base_name = match names.iter().rev().find(|name| !differential::is_diff_file(name)) {
+            Some(name) => name.clone(),
+            None => return Ok(None),
+        };
+        let diff_count = names.iter()
+            .filter(|name| differential::is_diff_file(name) && name.as_str() > base_name.as_str())
+            .count();
+        if diff_count >= self.config.full_backup_interval as usize {
+            return Ok(None);
+        }
+        
+        let base_content = fs::read_to_string(browser_backup_dir.join(&base_name))
+            .map_err(|e| format!("Fehler beim Lesen von {}: {}", base_name, e))?;
+        let base: serde_json::Value = serde_json::from_str(&base_content)
+            .map_err(|e| format!("JSON Parse Fehler in {}: {}", base_name, e))?;
+        let current_content = fs::read_to_string(source_path)
+            .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+        let current: serde_json::Value = serde_json::from_str(&current_content)
+            .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
+        
+        let diff = differential::create_diff(&base_name, &base, &current)?;
+        let diff_filename = format!("bookmarks_{}{}{}", timestamp, self.machine_suffix(), differential::DIFF_SUFFIX);
+        let diff_content = serde_json::to_string(&diff)
+            .map_err(|e| format!("Fehler beim Serialisieren: {}", e))?;
+        let diff_path = browser_backup_dir.join(&diff_filename);
+        fs::write(&diff_path, diff_content)
+            .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+        
+        let mut message = format!("Differenz gesichert: {} ({})", diff_filename, diff.summary());
+        if let Err(e) = self.update_latest_copy(browser_backup_dir, source_path, "json") {
+            message.push_str(&format!(" (latest.json nicht aktualisiert: {})", e));
+        }
+        
+        Ok(Some(BackupResult {
+            browser: browser.to_string(),
+            success: true,
+            message,
+            path: Some(diff_path),
+        }))
+    }
+    
+    fn is_differential_backup(path: &Path) -> bool {
+        path.file_name()
+            .map(|name| differential::is_diff_file(&name.to_string_lossy()))
+            .unwrap_or(false)
+    }
+    
+    // Liest den Inhalt eines Backups; Differenzen werden mit ihrem
+    // Vollbackup zu einer vollständigen Bookmarks-Datei zusammengesetzt
+    pub fn read_backup_content(&self, backup_path: &Path) -> Result<String, String> {
+        let content = fs::read_to_string(backup_path)
+            .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+        if !Self::is_differential_backup(backup_path) {
+            return Ok(content);
+        }
+        
+        let diff: BookmarkDiff = serde_json::from_str(&content)
+            .map_err(|e| format!("Ungültige Differenz: {}", e))?;
+        let base_content = fs::read_to_string(backup_path.with_file_name(&diff.base))
+            .map_err(|e| format!("Vollbackup {} nicht lesbar: {}", diff.base, e))?;
+        let base: serde_json::Value = serde_json::from_str(&base_content)
+            .map_err(|e| format!("JSON Parse Fehler in {}: {}", diff.base, e))?;
+        
+        let restored = differential::apply_diff(&base, &diff)?;
+        serde_json::to_string_pretty(&restored)
+            .map_err(|e| format!("Fehler beim Serialisieren: {}", e))
+    }
+    
+    // Prüft, ob sich ein Backup lesen lässt: JSON muss parsebar sein,
+    // SQLite-Datenbanken müssen die Integritätsprüfung bestehen
+    pub fn verify_backup(&self, backup_path: &Path) -> Result<(), String> {
+        let extension = backup_path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        
+        match extension.as_str() {
+            "json" => self.verify_bookmark_file(backup_path, BookmarkFormat::Chromium),
+            "sqlite" => self.verify_bookmark_file(backup_path, BookmarkFormat::Firefox),
+            _ => Ok(()),
+        }
+    }
+    
+    // Wie verify_backup, aber das Format ist vorgegeben statt aus der Endung
+    // abgeleitet, z.B. für die Bookmarks-Datei im Profil
+    fn verify_bookmark_file(&self, path: &Path, format: BookmarkFormat) -> Result<(), String> {
+        match format {
+            BookmarkFormat::Chromium => {
+                let content = self.read_backup_content(path)?;
+                let value: serde_json::Value = serde_json::from_str(&content)
+                    .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
+                if value.get("roots").is_none() {
+                    return Err("Keine Chromium-Favoriten-Datei (\"roots\" fehlt)".to_string());
+                }
+                Ok(())
+            }
+            BookmarkFormat::Firefox => {
+                let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                    .map_err(|e| format!("Datenbank nicht lesbar: {}", e))?;
+                let check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+                    .map_err(|e| format!("Datenbank nicht lesbar: {}", e))?;
+                if check != "ok" {
+                    return Err(format!("Integritätsprüfung fehlgeschlagen: {}", check));
+                }
+                conn.query_row("SELECT COUNT(*) FROM moz_bookmarks", [], |row| row.get::<_, i64>(0))
+                    .map_err(|e| format!("Keine Firefox-Lesezeichen-Datenbank: {}", e))?;
+                Ok(())
+            }
+        }
+    }
+    
+    // Übernimmt den Inhalt von <Datei>-wal in die Datenbank und leert das Journal
+    fn checkpoint_sqlite(path: &Path) -> Result<(), String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Datenbank {} nicht lesbar: {}", path.display(), e))?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| format!("Journal von {} konnte nicht übernommen werden: {}", path.display(), e))
+    }
+    
+    // Vor dem Wiederherstellen: die Datei muss zum Browser passen, darf nicht leer
+    // sein und muss verify_backup bestehen
+    fn verify_restore_source(&self, browser: &str, backup_path: &Path) -> Result<(), String> {
+        let name = backup_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| backup_path.display().to_string());
+        let size = fs::metadata(backup_path)
+            .map_err(|e| format!("Backup {} nicht lesbar: {}", name, e))?
+            .len();
+        if size == 0 {
+            return Err(format!("Backup {} ist leer (0 Bytes) und wird nicht wiederhergestellt", name));
+        }
+        
+        let expected = if browser == "Firefox" { "sqlite" } else { "json" };
+        let extension = backup_path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if extension != expected {
+            return Err(format!("{} ist kein {}-Backup (erwartet wird eine .{}-Datei)", name, browser, expected));
+        }
+        
+        self.verify_backup(backup_path)
+            .map_err(|e| format!("Backup {} ist beschädigt und wird nicht wiederhergestellt: {}", name, e))
+    }
+    
+    fn quarantine_backup(&self, browser: &str, backup_path: &Path) -> Result<PathBuf, String> {
+        let quarantine_dir = self.backup_dir.join(QUARANTINE_DIR).join(browser);
+        fs::create_dir_all(&quarantine_dir)
+            .map_err(|e| format!("Fehler beim Erstellen des Quarantäne-Ordners: {}", e))?;
+        
+        let file_name = backup_path.file_name().ok_or("Ungültiger Dateiname")?;
+        let target = quarantine_dir.join(file_name);
+        fs::rename(backup_path, &target)
+            .map_err(|e| format!("Fehler beim Verschieben in die Quarantäne: {}", e))?;
+        self.update_catalog(|catalog| catalog.remove(browser, &file_name.to_string_lossy()));
+        log::warn!("Beschädigtes Backup in Quarantäne verschoben: {}", target.display());
+        Ok(target)
+    }
+    
+    // Prüft alle vorhandenen Backups und verschiebt beschädigte in die Quarantäne.
+    // Gibt die Beschreibung der verschobenen Dateien zurück.
+    pub fn quarantine_corrupted_backups(&self) -> Vec<String> {
+        let mut quarantined = Vec::new();
+        
+        for browser in &["Chrome", "Edge", "Firefox"] {
+            // Erst die Differenzen prüfen, solange ihre Vollbackups noch am Platz sind
+            let mut backups = self.get_backup_list(browser);
+            backups.sort_by_key(|b| !Self::is_differential_backup(&b.path));
+            
+            for backup in backups {
+                if let Err(e) = self.verify_backup(&backup.path) {
+                    match self.quarantine_backup(browser, &backup.path) {
+                        Ok(_) => quarantined.push(format!("{}/{}: {}", browser, backup.name, e)),
+                        Err(qe) => log::error!("{}", qe),
+                    }
+                }
+            }
+        }
+        
+        quarantined
+    }
+    
+    pub fn get_quarantined_backups(&self) -> Vec<BackupFile> {
+        let mut files = Vec::new();
+        for browser in &["Chrome", "Edge", "Firefox"] {
+            let quarantine_dir = Path::new(QUARANTINE_DIR).join(browser);
+            let browser_key = quarantine_dir.to_string_lossy().to_string();
+            files.extend(self.get_backup_list(&browser_key));
+        }
+        files
+    }
+    
+    // Feste Kopie des neuesten Backups (z.B. Chrome/latest.json) für andere Tools
+    // und Sync-Software. Wird erst als .tmp geschrieben und dann umbenannt,
+    // damit nie eine halb geschriebene Datei unter dem festen Namen liegt.
+    fn update_latest_copy(&self, browser_backup_dir: &Path, backup_path: &Path, extension: &str) -> io::Result<()> {
+        let latest_path = browser_backup_dir.join(format!("{}.{}", self.latest_stem(), extension));
+        let temp_path = latest_path.with_extension("tmp");
+        fs::copy(backup_path, &temp_path)?;
+        fs::rename(&temp_path, &latest_path)
+    }
+    
+    // Im gemeinsamen Ordner hat jeder Rechner seine eigene latest-Kopie
+    fn latest_stem(&self) -> String {
+        format!("{}{}", LATEST_FILE_STEM, self.machine_suffix())
+    }
+    
+    fn is_latest_copy(path: &Path) -> bool {
+        path.file_stem()
+            .map(|stem| {
+                let stem = stem.to_string_lossy();
+                stem == LATEST_FILE_STEM || stem.starts_with(&format!("{}_", LATEST_FILE_STEM))
+            })
+            .unwrap_or(false)
+    }
+    
+    // Nur Dateien mit der Endung des Browsers zählen als Backup, nicht z.B. .tmp
+    // (Backup oder Download noch nicht fertig) oder -wal/-shm, die SQLite beim
+    // Öffnen einer Firefox-Sicherung anlegen kann
+    fn is_backup_file(browser: &str, path: &Path) -> bool {
+        let extension = BookmarkFormat::for_browser(browser).extension();
+        path.extension().is_some_and(|ext| ext == extension) && !Self::is_latest_copy(path)
+    }
+    
     pub fn get_backup_list(&self, browser: &str) -> Vec<BackupFile> {
         let browser_dir = self.backup_dir.join(browser);
         let mut backups = Vec::new();
@@ -231,7 +1483,7 @@ impl BackupManager {
         if let Ok(entries) = fs::read_dir(&browser_dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.is_file() {
+                if path.is_file() && Self::is_backup_file(browser, &path) {
                     if let Ok(metadata) = entry.metadata() {
                         if let Ok(modified) = metadata.modified() {
                             let datetime: chrono::DateTime<Local> = modified.into();
@@ -247,63 +1499,98 @@ impl BackupManager {
             }
         }
         
-        backups.sort_by(|a, b| b.date.cmp(&a.date));
+        backups.sort_by_key(|b| std::cmp::Reverse(b.date));
         backups
     }
     
-    pub fn restore_backup(&self, browser: &str, backup_path: &Path) -> Result<String, String> {
-        let user_profile = std::env::var("USERPROFILE")
-            .map_err(|_| "USERPROFILE environment variable not found".to_string())?;
-        
-        let target_path = match browser {
-            "Chrome" => PathBuf::from(&user_profile)
-                .join("AppData")
-                .join("Local")
-                .join("Google")
-                .join("Chrome")
-                .join("User Data")
-                .join("Default")
-                .join("Bookmarks"),
-            "Edge" => PathBuf::from(&user_profile)
-                .join("AppData")
-                .join("Local")
-                .join("Microsoft")
-                .join("Edge")
-                .join("User Data")
-                .join("Default")
-                .join("Bookmarks"),
-            "Firefox" => {
-                let profiles_path = PathBuf::from(&user_profile)
-                    .join("AppData")
-                    .join("Roaming")
-                    .join("Mozilla")
-                    .join("Firefox")
-                    .join("Profiles");
-                if let Ok(entries) = fs::read_dir(&profiles_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_dir() && path.to_string_lossy().ends_with(".default-release") {
-                            return Ok(path.join("places.sqlite").to_string_lossy().to_string());
-                        }
-                    }
-                }
-                return Err("Firefox Profil nicht gefunden".to_string());
+    pub fn restore_backup(&self, browser: &str, backup_path: &Path, dry_run: bool) -> Result<String, String> {
+        self.restore_backup_to(browser, backup_path, &Self::current_bookmarks_path(browser)?, dry_run)
+    }
+    
+    // Schreibt das Backup als eigenständige Datei nach `target_path`, ohne die Favoriten
+    // im Browser anzufassen, z.B. zum Nachsehen oder für einen anderen Rechner.
+    // Differenzielle Backups werden dabei zu einer vollständigen Datei.
+    pub fn save_backup_copy(&self, browser: &str, backup_path: &Path, target_path: &Path, dry_run: bool) -> Result<String, String> {
+        self.verify_restore_source(browser, backup_path)?;
+        if fs::canonicalize(target_path).ok() == fs::canonicalize(backup_path).ok() {
+            return Err("Das Ziel ist das Backup selbst".to_string());
+        }
+        
+        if dry_run {
+            return Ok(format!("Probelauf: {} würde nach {} geschrieben", backup_path.display(), target_path.display()));
+        }
+        
+        let format = BookmarkFormat::for_browser(browser);
+        let result = self.write_bookmark_copy(backup_path, target_path, format)
+            .and_then(|_| match format {
+                // Ohne WAL-Modus kommt die Kopie ohne -wal/-shm-Dateien aus; Firefox
+                // schaltet beim Öffnen selbst wieder um
+                BookmarkFormat::Firefox => Connection::open(target_path)
+                    .and_then(|conn| conn.query_row("PRAGMA journal_mode=DELETE", [], |_| Ok(())))
+                    .map_err(|e| format!("Datenbank nicht beschreibbar: {}", e)),
+                BookmarkFormat::Chromium => Ok(()),
+            })
+            .and_then(|_| self.verify_bookmark_file(target_path, format));
+        if let Err(e) = result {
+            fs::remove_file(target_path).ok();
+            return Err(format!("Kopie konnte nicht gespeichert werden: {}", e));
+        }
+        
+        log::info!("{}-Backup {} nach {} gespeichert", browser, backup_path.display(), target_path.display());
+        Ok(format!("Backup gespeichert unter {}", target_path.display()))
+    }
+    
+    // Wie restore_backup, aber in die Favoriten-Datei eines beliebigen Profils,
+    // siehe browser_profiles
+    pub fn restore_backup_to(&self, browser: &str, backup_path: &Path, target_path: &Path, dry_run: bool) -> Result<String, String> {
+        // Eine kaputte Datei würde die Favoriten im Browser zerstören
+        self.verify_restore_source(browser, backup_path)?;
+        
+        let format = BookmarkFormat::for_browser(browser);
+        if !target_path.parent().is_some_and(Path::is_dir) {
+            return Err(format!("Profilordner für {} nicht gefunden", target_path.display()));
+        }
+        
+        if dry_run {
+            let size = fs::metadata(backup_path).map(|m| m.len()).unwrap_or(0);
+            let mut message = format!(
+                "Probelauf: {} würde durch {} ({:.1} KB) ersetzt",
+                target_path.display(), backup_path.display(), size as f64 / 1024.0
+            );
+            if target_path.exists() {
+                message.push_str(&format!(
+                    "; die aktuelle Datei würde in {} gesichert und als Backup \"Vor Wiederherstellung\" abgelegt",
+                    self.backup_dir.join(PRE_RESTORE_DIR).join(browser).display()
+                ));
             }
-            _ => return Err("Unbekannter Browser".to_string()),
-        };
+            if browser_processes::is_running(browser) {
+                message.push_str(&format!("; {} läuft noch und müsste vorher beendet werden", browser));
+            }
+            return Ok(message);
+        }
+        
+        // Chrome/Edge schreiben ihre Favoriten beim Beenden zurück, Firefox sperrt places.sqlite
+        if browser_processes::is_running(browser) {
+            return Err(format!("{} läuft noch. Bitte den Browser vor der Wiederherstellung beenden.", browser));
+        }
         
-        // Backup der aktuellen Datei
+        let mut safety_backup = None;
         if target_path.exists() {
-            let backup_current = target_path.with_extension("bak");
-            fs::copy(&target_path, backup_current)
-                .map_err(|e| format!("Fehler beim Sichern der aktuellen Datei: {}", e))?;
+            // Noch nicht übernommene Änderungen aus places.sqlite-wal gehören in die Sicherung
+            if format == BookmarkFormat::Firefox {
+                Self::checkpoint_sqlite(target_path)?;
+            }
+            // Stand vor der Wiederherstellung, für "Wiederherstellung rückgängig machen"
+            self.snapshot_before_restore(browser, target_path)?;
+            safety_backup = self.backup_before_restore(browser, target_path);
         }
         
-        // Wiederherstellen
-        fs::copy(backup_path, &target_path)
-            .map_err(|e| format!("Fehler beim Wiederherstellen: {}", e))?;
+        self.replace_bookmark_file(backup_path, target_path, format)?;
         
         let mut message = format!("{} Favoriten erfolgreich wiederhergestellt", browser);
+        if let Some(name) = safety_backup.as_deref().and_then(Path::file_name) {
+            message.push_str(&format!("\nVorheriger Stand als Backup {} gesichert", name.to_string_lossy()));
+        }
         if browser == "Firefox" {
             message.push_str("\n(Firefox muss neu gestartet werden)");
         }
@@ -311,351 +1598,1402 @@ impl BackupManager {
         Ok(message)
     }
 
-    // Static method for scheduling that doesn't create new instances
-    pub fn start_scheduled_backups(backup_manager: Arc<Mutex<BackupManager>>, interval_hours: u64) {
+    // Schreibt den vollständigen Inhalt von `source` nach `path`; Differenzen werden
+    // aufgelöst, Chrome/Edge-Dateien bekommen eine passende Prüfsumme
+    fn write_bookmark_copy(&self, source: &Path, path: &Path, format: BookmarkFormat) -> Result<(), String> {
+        if format == BookmarkFormat::Firefox {
+            return fs::copy(source, path)
+                .map(|_| ())
+                .map_err(|e| format!("Fehler beim Schreiben: {}", e));
+        }
+        // Ein älterer Stand trägt seine alte Prüfsumme oder gar keine; Chrome/Edge
+        // würden die Datei sonst verwerfen und auf ihre eigene Sicherung zurückgreifen
+        let mut value: serde_json::Value = serde_json::from_str(&self.read_backup_content(source)?)
+            .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
+        bookmarks::update_chromium_checksum(&mut value);
+        let content = serde_json::to_string_pretty(&value)
+            .map_err(|e| format!("Fehler beim Serialisieren: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Fehler beim Schreiben: {}", e))
+    }
+    
+    // Schreibt `source` neben das Ziel, prüft die Kopie und ersetzt dann das Ziel in
+    // einem Schritt; bei einem Fehler bleibt die bisherige Datei unverändert
+    fn replace_bookmark_file(&self, source: &Path, target_path: &Path, format: BookmarkFormat) -> Result<(), String> {
+        let file_name = target_path.file_name().ok_or("Ungültiger Zielpfad")?.to_string_lossy().to_string();
+        let temp_path = target_path.with_file_name(format!("{}.tmp", file_name));
+        let result = self.write_bookmark_copy(source, &temp_path, format)
+            .and_then(|_| {
+                self.verify_bookmark_file(&temp_path, format)
+                    .map_err(|e| format!("Die wiederhergestellte Datei ist fehlerhaft: {}", e))
+            })
+            .and_then(|_| {
+                // Übrig gebliebene -wal/-shm-Dateien würde SQLite auf die neue Datenbank anwenden
+                if format == BookmarkFormat::Firefox {
+                    for suffix in ["-wal", "-shm"] {
+                        let journal = target_path.with_file_name(format!("{}{}", file_name, suffix));
+                        if journal.exists() {
+                            fs::remove_file(&journal)
+                                .map_err(|e| format!("{} konnte nicht entfernt werden: {}", journal.display(), e))?;
+                        }
+                    }
+                }
+                fs::rename(&temp_path, target_path)
+                    .map_err(|e| format!("Fehler beim Wiederherstellen: {}", e))
+            });
+        if result.is_err() {
+            fs::remove_file(&temp_path).ok();
+        } else if format == BookmarkFormat::Chromium {
+            // Chrome/Edge greifen bei Problemen auf Bookmarks.bak zurück; ein alter
+            // Stand dort könnte die Wiederherstellung beim nächsten Start überschreiben
+            let stale = target_path.with_file_name(format!("{}.bak", file_name));
+            if stale.exists() {
+                if let Err(e) = fs::remove_file(&stale) {
+                    log::warn!("{} konnte nicht entfernt werden: {}", stale.display(), e);
+                }
+            }
+        }
+        result
+    }
+    
+    // Ermittelt die fälligen Browser und merkt sich den nächsten geplanten Lauf.
+    // `last_runs` enthält die Läufe des Zeitplans seit `started`; auch manuelle
+    // Backups und solche vor einem Neustart zählen über den Katalog mit.
+    pub fn plan_scheduled_backups(
+        &mut self,
+        last_runs: &HashMap<&'static str, chrono::DateTime<Local>>,
+        started: chrono::DateTime<Local>,
+        now: chrono::DateTime<Local>,
+    ) -> Vec<&'static str> {
+        let mut due = Vec::new();
+        let mut next_run = None;
+        match self.config.schedule_mode {
+            ScheduleMode::Interval => {
+                let catalog = self.get_catalog();
+                for browser in self.enabled_browsers() {
+                    // Nach einem Neustart zählt das letzte Backup aus dem Katalog, damit ein
+                    // in der Zwischenzeit abgelaufenes Intervall sofort nachgeholt wird;
+                    // der Programmstart nur, wenn es noch kein erfolgreiches Backup gibt
+                    let last_success = catalog.last_success.get(&self.success_key(browser)).copied();
+                    let last_run = match (last_runs.get(browser).copied(), last_success) {
+                        (Some(last_run), Some(last_success)) => last_run.max(last_success),
+                        (Some(last_run), None) => last_run,
+                        (None, Some(last_success)) => last_success,
+                        (None, None) => started,
+                    };
+                    let browser_next_run = schedule::next_interval_run(last_run, self.interval_minutes_for(browser));
+                    if now >= browser_next_run {
+                        if schedule::is_overdue(browser_next_run, now) {
+                            log::info!("Verpasstes {}-Backup (fällig um {}) wird nachgeholt", browser, browser_next_run.format("%d.%m.%Y %H:%M"));
+                        }
+                        due.push(browser);
+                    }
+                    next_run = Some(next_run.map_or(browser_next_run, |n: chrono::DateTime<Local>| n.min(browser_next_run)));
+                }
+            }
+            ScheduleMode::TimesOfDay => {
+                let is_due = schedule::is_time_of_day_due(
+                    &self.config.schedule_times,
+                    self.config.last_scheduled_run.max(self.last_successful_backup()),
+                    now,
+                );
+                if is_due {
+                    due = self.enabled_browsers();
+                }
+                next_run = schedule::next_slot(&self.config.schedule_times, now);
+            }
+        }
+        
+        if self.paused {
+            due.clear();
+            next_run = None;
+        }
+        self.next_scheduled_run = next_run;
+        due
+    }
+    
+    // Zeitpunkt speichern, damit feste Uhrzeiten einen Neustart überstehen.
+    // Am gemeinsamen Manager aufrufen, bevor run_scheduled_backup auf einer Kopie läuft.
+    pub fn mark_scheduled_run(&mut self) {
+        self.config.last_scheduled_run = Some(Local::now());
+        self.save_config();
+    }
+    
+    pub fn run_scheduled_backup(&self, browsers: &[&str]) {
+        if self.config.shared_backup_folder {
+            self.merge_catalog();
+        }
+        
+        let results = self.backup_browsers(browsers, RunTrigger::Scheduled);
+        
+        log::info!("Automatisches Backup durchgeführt: {:?}", results);
+        
+        if results.iter().any(|r| r.success) {
+            match self.auto_export() {
+                Some(Ok(path)) => log::info!("Automatischer Export aktualisiert: {}", path.display()),
+                Some(Err(e)) => log::error!("Automatischer Export fehlgeschlagen: {}", e),
+                None => {}
+            }
+        }
+        
+        if self.config.auto_cleanup {
+            match self.cleanup_old_backups(self.config.retention_days, false) {
+                Ok(count) => log::info!("Automatische Bereinigung: {} alte Backups gelöscht", count),
+                Err(e) => log::error!("Automatische Bereinigung fehlgeschlagen: {}", e),
+            }
+        }
+        
+        self.report_healthcheck(&results);
+    }
+    
+    // Ergebnis eines geplanten Laufs an healthchecks.io melden, auch bei
+    // `--backup-now` aus der Windows-Aufgabenplanung
+    pub fn report_healthcheck(&self, results: &[BackupResult]) {
+        if self.config.healthcheck_url.is_empty() {
+            return;
+        }
+        let log: Vec<String> = results.iter()
+            .map(|r| format!("{} {}: {}", if r.success { "✓" } else { "✗" }, r.browser, r.message))
+            .collect();
+        let success = results.iter().all(|r| r.success);
+        if let Err(e) = self.ping_healthcheck(&self.config.healthcheck_url, success, &log.join("\n")) {
+            log::error!("{}", e);
+        }
+    }
+    
+    // Der Text erscheint bei healthchecks.io im Protokoll des Pings
+    pub fn ping_healthcheck(&self, url: &str, success: bool, log: &str) -> Result<(), String> {
+        let url = url.trim_end_matches('/');
+        let url = if success { url.to_string() } else { format!("{}/fail", url) };
+        proxy::agent(self.proxy().as_ref(), &url)
+            .post(&url)
+            .timeout(Duration::from_secs(15))
+            .send_string(log)
+            .map_err(|e| format!("Ping an {} fehlgeschlagen: {}", url, e))?;
+        Ok(())
+    }
+    
+    // Schreibt das Archiv aller Backups in den Exportordner, z.B. einen
+    // OneDrive-Ordner. Erst als .tmp, damit Sync-Programme nie ein halbes
+    // Archiv hochladen. None bedeutet: nicht konfiguriert. Ist kein Backup
+    // neuer als das vorhandene Archiv, bleibt es unverändert.
+    pub fn auto_export(&self) -> Option<Result<PathBuf, String>> {
+        if !self.config.auto_export_enabled {
+            return None;
+        }
+        let export_dir = self.config.auto_export_path.as_ref()?;
+        
+        let format = self.config.auto_export_format;
+        let target = export_dir.join(format!("browser_backups.{}", format.extension()));
+        let temp_path = export_dir.join(format!("browser_backups.{}.tmp", format.extension()));
+        let selection = ArchiveSelection {
+            browsers: vec!["Chrome".to_string(), "Edge".to_string(), "Firefox".to_string()],
+            from: None,
+            to: None,
+            names: None,
+        };
+        
+        let files = self.archive_files(&selection);
+        if files.is_empty() {
+            return Some(Err("Keine Backups vorhanden".to_string()));
+        }
+        
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        let newest_backup = files.iter().filter_map(|entry| modified(&entry.path)).max();
+        if let (Some(archive), Some(newest)) = (modified(&target), newest_backup) {
+            if archive >= newest {
+                return Some(Ok(target));
+            }
+        }
+        
+        let result = format.quick_archiver().write(&temp_path, &files, &CancellationToken::default())
+            .and_then(|_| fs::rename(&temp_path, &target)
+                .map_err(|e| format!("Fehler beim Ersetzen von {}: {}", target.display(), e)));
+        if result.is_err() {
+            fs::remove_file(&temp_path).ok();
+        }
+        Some(result.map(|_| target))
+    }
+    
+    // Backups auf den Wechseldatenträger kopieren, falls er angeschlossen ist.
+    // None bedeutet: nicht konfiguriert oder Laufwerk fehlt.
+    // Läuft ohne Sperre auf einer Kopie; das Ergebnis übernimmt
+    // record_removable_drive_sync in den gemeinsamen Manager
+    pub fn sync_removable_drive(&self) -> Option<Result<usize, String>> {
+        if !self.config.removable_drive_enabled {
+            return None;
+        }
+        let target = self.config.removable_drive_path.clone()?;
+        if !removable_drive::is_present(&target) {
+            return None;
+        }
+        
+        let result = removable_drive::sync_backups(
+            &self.backup_dir,
+            &target,
+            &["Chrome", "Edge", "Firefox"],
+            self.config.removable_drive_last_sync,
+            &Self::is_latest_copy,
+        );
+        Some(result)
+    }
+    
+    pub fn record_removable_drive_sync(&mut self, result: &Result<usize, String>) {
+        match result {
+            Ok(_) => {
+                self.removable_drive_error = None;
+                self.config.removable_drive_last_sync = Some(Local::now());
+                self.save_config();
+            }
+            Err(e) => self.removable_drive_error = Some(e.clone()),
+        }
+    }
+    
+    pub fn removable_drive_status(&self) -> Option<String> {
+        if !self.config.removable_drive_enabled {
+            return None;
+        }
+        let target = self.config.removable_drive_path.as_ref()?;
+        let drive = removable_drive::drive_name(target);
+        
+        if let Some(error) = &self.removable_drive_error {
+            return Some(format!("Synchronisation auf {} fehlgeschlagen: {}", drive, error));
+        }
+        
+        let last_sync = match self.config.removable_drive_last_sync {
+            Some(date) => date.format("%d.%m.%Y %H:%M").to_string(),
+            None => "noch nie".to_string(),
+        };
+        
+        if removable_drive::is_present(target) {
+            Some(format!("Letzte Synchronisation auf {}: {}", drive, last_sync))
+        } else {
+            Some(format!("Wechseldatenträger {} nicht angeschlossen (letzte Synchronisation: {})", drive, last_sync))
+        }
+    }
+    
+    // Prüft regelmäßig, ob der Wechseldatenträger angeschlossen ist, und
+    // überträgt beim Einstecken sowie nach neuen Backups die fehlenden Dateien
+    pub fn start_removable_drive_watcher(backup_manager: Arc<Mutex<BackupManager>>) {
         thread::spawn(move || {
+            let mut was_present = false;
             loop {
-                thread::sleep(Duration::from_secs(interval_hours * 3600));
+                // Kopieren ohne Sperre, ein großer Backup-Ordner braucht auf USB-Sticks eine Weile
+                let (present, due) = match backup_manager.lock() {
+                    Ok(manager) => {
+                        let target = manager.config.removable_drive_path.clone()
+                            .filter(|_| manager.config.removable_drive_enabled);
+                        let present = target.as_deref().map(removable_drive::is_present).unwrap_or(false);
+                        let due = present && (!was_present || manager.has_backups_since_last_sync());
+                        (present, due.then(|| manager.clone()))
+                    }
+                    Err(_) => (false, None),
+                };
+                was_present = present;
                 
-                if let Ok(manager) = backup_manager.lock() {
-                    let results = manager.backup_all();
-                    
-                    println!("Automatisches Backup durchgeführt: {:?}", results);
+                if let Some(result) = due.and_then(|manager| manager.sync_removable_drive()) {
+                    match &result {
+                        Ok(count) => log::info!("Wechseldatenträger synchronisiert: {} Dateien kopiert", count),
+                        Err(e) => log::error!("Synchronisation auf Wechseldatenträger fehlgeschlagen: {}", e),
+                    }
+                    if let Ok(mut manager) = backup_manager.lock() {
+                        manager.record_removable_drive_sync(&result);
+                    }
+                }
+                
+                thread::sleep(Duration::from_secs(30));
+            }
+        });
+    }
+    
+    fn has_backups_since_last_sync(&self) -> bool {
+        let last_sync = match self.config.removable_drive_last_sync {
+            Some(date) => date,
+            None => return true,
+        };
+        
+        ["Chrome", "Edge", "Firefox"].iter().any(|browser| {
+            self.get_backup_list(browser)
+                .first()
+                .map(|latest| latest.date > last_sync)
+                .unwrap_or(false)
+        })
+    }
+    
+    // Backup, sobald ein Browser beendet wurde: dann sind die Dateien
+    // vollständig geschrieben und nicht mehr gesperrt
+    pub fn start_browser_exit_watcher(backup_manager: Arc<Mutex<BackupManager>>) {
+        thread::spawn(move || {
+            let mut running = browser_processes::running_browsers();
+            loop {
+                thread::sleep(Duration::from_secs(10));
+                
+                let now_running = browser_processes::running_browsers();
+                let exited: Vec<&str> = running.difference(&now_running).copied().collect();
+                running = now_running;
+                
+                if exited.is_empty() {
+                    continue;
+                }
+                
+                if let Some(manager) = Self::snapshot(&backup_manager) {
+                    if !manager.config.backup_on_browser_exit || manager.paused {
+                        continue;
+                    }
                     
-                    for result in &results {
-                        if result.success {
-                            println!("✓ {} backup successful: {}", result.browser, result.message);
-                        } else {
-                            eprintln!("✗ {} backup failed: {}", result.browser, result.message);
+                    let enabled = manager.enabled_browsers();
+                    let browsers: Vec<&str> = exited.into_iter().filter(|b| enabled.contains(b)).collect();
+                    let results = manager.backup_browsers(&browsers, RunTrigger::Event);
+                    log::info!("Backup nach Beenden des Browsers durchgeführt: {:?}", results);
+                }
+            }
+        });
+    }
+    
+    // Einmaliges Backup kurz nach dem Programmstart, damit Rechner, die selten
+    // 24 Stunden am Stück laufen, trotzdem regelmäßig gesichert werden
+    pub fn start_startup_backup(backup_manager: Arc<Mutex<BackupManager>>) {
+        thread::spawn(move || {
+            // Dem Browser und der Anmeldung etwas Zeit lassen
+            thread::sleep(Duration::from_secs(15));
+            
+            if let Some(manager) = Self::snapshot(&backup_manager) {
+                if !manager.config.backup_on_start || manager.paused {
+                    return;
+                }
+                if manager.has_recent_backup() {
+                    log::info!("Backup beim Start übersprungen: letztes Backup liegt innerhalb des Intervalls");
+                    return;
+                }
+                
+                log::info!("Backup beim Start");
+                manager.backup_all(RunTrigger::Event);
+            }
+        });
+    }
+    
+    // Beobachtet die Favoriten-Dateien der Browser und erstellt ein Backup,
+    // sobald seit der letzten Änderung einige Minuten Ruhe eingekehrt ist
+    pub fn start_file_watcher(backup_manager: Arc<Mutex<BackupManager>>) {
+        use notify::{RecursiveMode, Watcher};
+        
+        thread::spawn(move || {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let mut watcher = match notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    sender.send(event).ok();
+                }
+            }) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    log::error!("Dateiüberwachung konnte nicht gestartet werden: {}", e);
+                    return;
+                }
+            };
+            
+            // Verzeichnisse statt Dateien beobachten: Chrome ersetzt die
+            // Bookmarks-Datei beim Speichern, womit eine Dateibeobachtung verloren ginge
+            let mut watched_files = Vec::new();
+            let mut targets = vec![Self::chrome_bookmarks_path(), Self::edge_bookmarks_path()];
+            if let Some(profile) = Self::find_firefox_profile() {
+                targets.push(profile.join("places.sqlite"));
+                targets.push(profile.join("places.sqlite-wal"));
+            }
+            for target in targets {
+                if let Some(parent) = target.parent() {
+                    if watcher.watch(parent, RecursiveMode::NonRecursive).is_ok() {
+                        watched_files.push(target);
+                    }
+                }
+            }
+            
+            let mut last_change: Option<std::time::Instant> = None;
+            loop {
+                match receiver.recv_timeout(Duration::from_secs(10)) {
+                    Ok(event) => {
+                        if event.paths.iter().any(|path| watched_files.contains(path)) {
+                            last_change = Some(std::time::Instant::now());
                         }
                     }
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+                
+                let changed_at = match last_change {
+                    Some(changed_at) => changed_at,
+                    None => continue,
+                };
+                
+                if let Some(manager) = Self::snapshot(&backup_manager) {
+                    if !manager.config.watch_bookmark_files || manager.paused {
+                        last_change = None;
+                        continue;
+                    }
+                    let debounce = Duration::from_secs(manager.config.watch_debounce_minutes.max(1) * 60);
+                    if changed_at.elapsed() < debounce {
+                        continue;
+                    }
+                    
+                    last_change = None;
+                    let results = manager.backup_all(RunTrigger::Event);
+                    log::info!("Backup nach Änderung der Favoriten durchgeführt: {:?}", results);
                 }
             }
         });
     }
     
-    // Alte Backups automatisch löschen
-    pub fn cleanup_old_backups(&self, keep_days: i64) -> Result<usize, String> {
-        let mut deleted_count = 0;
+    // Backup mit Zeitlimit, z.B. beim Herunterfahren. Läuft das Backup länger,
+    // wird es abgebrochen und None zurückgegeben: Der gerade kopierte Browser
+    // verwirft seine Datei, Uploads und rclone entfallen.
+    pub fn backup_with_timeout(backup_manager: Arc<Mutex<BackupManager>>, timeout: Duration) -> Option<Vec<BackupResult>> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let cancel = CancellationToken::default();
+        let job_cancel = cancel.clone();
+        thread::spawn(move || {
+            if let Some(manager) = Self::snapshot(&backup_manager) {
+                let browsers = manager.enabled_browsers();
+                let results = manager.backup_browsers_with_progress(&browsers, RunTrigger::Event, false, &job_cancel, &|_| {});
+                sender.send(results).ok();
+            }
+        });
+        let results = receiver.recv_timeout(timeout).ok();
+        if results.is_none() {
+            cancel.cancel();
+        }
+        results
+    }
+    
+    // Backups, die eine Bereinigung mit `keep_days` löschen würde
+    pub fn expired_backups(&self, keep_days: i64) -> Vec<(&'static str, BackupFile)> {
+        let mut expired_backups = Vec::new();
         let cutoff_date = Local::now() - chrono::Duration::days(keep_days);
+        // Im gemeinsamen Ordner räumt jeder Rechner nur seine eigenen Backups auf,
+        // die anderen können eine längere Aufbewahrung eingestellt haben
+        let own_machine = self.own_machine();
         
-        for browser in &["Chrome", "Edge", "Firefox"] {
+        for browser in ["Chrome", "Edge", "Firefox"] {
             let browser_dir = self.backup_dir.join(browser);
+            let mut expired = Vec::new();
+            let mut kept_diffs = Vec::new();
             if let Ok(entries) = fs::read_dir(&browser_dir) {
                 for entry in entries.flatten() {
-                    if let Ok(metadata) = entry.metadata() {
-                        if let Ok(modified) = metadata.modified() {
-                            let datetime: chrono::DateTime<Local> = modified.into();
-                            if datetime < cutoff_date {
-                                if fs::remove_file(entry.path()).is_ok() {
-                                    deleted_count += 1;
-                                }
-                            }
+                    let path = entry.path();
+                    if !Self::is_backup_file(browser, &path) {
+                        continue;
+                    }
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if own_machine.is_some() && catalog::machine_from_file_name(&name) != own_machine.as_deref() {
+                        continue;
+                    }
+                    // Unterordner wie bookmarkbackups werden unten eigens behandelt
+                    let metadata = match entry.metadata() {
+                        Ok(metadata) if metadata.is_file() => metadata,
+                        _ => continue,
+                    };
+                    if let Ok(modified) = metadata.modified() {
+                        let datetime: chrono::DateTime<Local> = modified.into();
+                        if datetime < cutoff_date {
+                            expired.push(BackupFile {
+                                name,
+                                path,
+                                date: datetime,
+                                size: metadata.len(),
+                            });
+                        } else if Self::is_differential_backup(&path) {
+                            kept_diffs.push(path);
                         }
                     }
                 }
             }
+            
+            // Vollbackups, auf denen noch aufbewahrte Differenzen basieren, nicht löschen
+            let required_bases: Vec<String> = kept_diffs.iter()
+                .filter_map(|path| fs::read_to_string(path).ok())
+                .filter_map(|content| serde_json::from_str::<BookmarkDiff>(&content).ok())
+                .map(|diff| diff.base)
+                .collect();
+            
+            expired_backups.extend(expired.into_iter()
+                .filter(|backup| !required_bases.contains(&backup.name))
+                .map(|backup| (browser, backup)));
+        }
+        
+        // Von copy_firefox_bookmarkbackups übernommene Firefox-Sicherungen; das
+        // Änderungsdatum ist beim Kopieren das von Firefox geblieben
+        let firefox_backups = self.backup_dir.join("Firefox").join(FIREFOX_BOOKMARKBACKUPS_DIR);
+        if let Ok(entries) = fs::read_dir(&firefox_backups) {
+            for entry in entries.flatten() {
+                let metadata = match entry.metadata() {
+                    Ok(metadata) if metadata.is_file() => metadata,
+                    _ => continue,
+                };
+                if let Ok(modified) = metadata.modified() {
+                    let datetime: chrono::DateTime<Local> = modified.into();
+                    if datetime < cutoff_date {
+                        expired_backups.push(("Firefox", BackupFile {
+                            name: entry.file_name().to_string_lossy().to_string(),
+                            path: entry.path(),
+                            date: datetime,
+                            size: metadata.len(),
+                        }));
+                    }
+                }
+            }
+        }
+        
+        expired_backups
+    }
+    
+    pub fn cleanup_old_backups(&self, keep_days: i64, dry_run: bool) -> Result<usize, String> {
+        let expired = self.expired_backups(keep_days);
+        if dry_run {
+            for (_, backup) in &expired {
+                log::info!("Probelauf: würde {} ({:.1} KB) löschen", backup.path.display(), backup.size as f64 / 1024.0);
+            }
+            return Ok(expired.len());
+        }
+        
+        let mut deleted_count = 0;
+        for (browser, backup) in expired {
+            if fs::remove_file(&backup.path).is_ok() {
+                deleted_count += 1;
+                self.update_catalog(|catalog| catalog.remove(browser, &backup.name));
+            }
         }
         
         Ok(deleted_count)
     }
     
-    // Export als ZIP
-    pub fn export_backups(&self, export_path: &Path) -> Result<(), String> {
-        use zip::write::FileOptions;
-        use zip::ZipWriter;
+    // Einzelnes Backup löschen, z.B. ein beschädigtes. Vollbackups, auf denen
+    // noch Differenzen aufbauen, bleiben erhalten.
+    pub fn delete_backup(&self, browser: &str, path: &Path) -> Result<(), String> {
+        let browser_dir = self.backup_dir.join(browser);
+        let name = path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or("Ungültiger Dateiname")?;
+        if path.parent() != Some(browser_dir.as_path()) {
+            return Err(format!("{} liegt nicht im Backup-Ordner von {}", name, browser));
+        }
         
-        let file = fs::File::create(export_path)
-            .map_err(|e| format!("Fehler beim Erstellen der ZIP-Datei: {}", e))?;
+        let dependents = fs::read_dir(&browser_dir)
+            .map(|entries| entries.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| Self::is_differential_backup(path))
+                .filter_map(|path| fs::read_to_string(path).ok())
+                .filter_map(|content| serde_json::from_str::<BookmarkDiff>(&content).ok())
+                .filter(|diff| diff.base == name)
+                .count())
+            .unwrap_or(0);
+        if dependents > 0 {
+            return Err(format!("{} wird noch von {} differenziellen Backups benötigt", name, dependents));
+        }
         
-        let mut zip = ZipWriter::new(file);
-        let options = FileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated);
+        fs::remove_file(path)
+            .map_err(|e| format!("{} konnte nicht gelöscht werden: {}", name, e))?;
+        self.update_catalog(|catalog| catalog.remove(browser, &name));
+        log::info!("Backup gelöscht: {}", path.display());
+        Ok(())
+    }
+    
+    // Differenzen zuerst, damit ihre mit ausgewählten Vollbackups danach frei sind.
+    // Gibt die Zahl der gelöschten Backups und die Fehler zurück.
+    pub fn delete_backups(&self, browser: &str, paths: &[PathBuf]) -> (usize, Vec<String>) {
+        let mut paths: Vec<&PathBuf> = paths.iter().collect();
+        paths.sort_by_key(|path| !Self::is_differential_backup(path));
         
-        for browser in &["Chrome", "Edge", "Firefox"] {
-            let browser_dir = self.backup_dir.join(browser);
-            if let Ok(entries) = fs::read_dir(&browser_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_file() {
-                        let name = format!("{}/{}", browser, entry.file_name().to_string_lossy());
-                        zip.start_file(name, options)
-                            .map_err(|e| format!("ZIP Fehler: {}", e))?;
-                        
-                        let mut file = fs::File::open(&path)
-                            .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
-                        let mut buffer = Vec::new();
-                        file.read_to_end(&mut buffer)
-                            .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
-                        
-                        zip.write_all(&buffer)
-                            .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+        let mut deleted = 0;
+        let mut errors = Vec::new();
+        for path in paths {
+            match self.delete_backup(browser, path) {
+                Ok(()) => deleted += 1,
+                Err(e) => errors.push(e),
+            }
+        }
+        (deleted, errors)
+    }
+    
+    // Dateien, die ein Archiv-Export mit dieser Auswahl enthält. Differenzielle
+    // Backups ziehen ihr Vollbackup mit, auch wenn es außerhalb des Zeitraums liegt.
+    pub fn archive_files(&self, selection: &ArchiveSelection) -> Vec<ArchiveEntry> {
+        let mut files = Vec::new();
+        for browser in &selection.browsers {
+            let backups = self.get_backup_list(browser);
+            let mut names: Vec<String> = Vec::new();
+            for backup in backups.iter().filter(|b| selection.includes(b)) {
+                if Self::is_differential_backup(&backup.path) {
+                    let base = fs::read_to_string(&backup.path)
+                        .ok()
+                        .and_then(|content| serde_json::from_str::<BookmarkDiff>(&content).ok())
+                        .map(|diff| diff.base);
+                    if let Some(base) = base.filter(|base| !names.contains(base)) {
+                        names.push(base);
                     }
                 }
+                if !names.contains(&backup.name) {
+                    names.push(backup.name.clone());
+                }
+            }
+            
+            for backup in backups.iter().filter(|b| names.contains(&b.name)) {
+                files.push(ArchiveEntry {
+                    name: format!("{}/{}", browser, backup.name),
+                    path: backup.path.clone(),
+                    size: backup.size,
+                });
             }
         }
+        files
+    }
+    
+    // Grobe Schätzung der Archivgröße: JSON schrumpft beim Komprimieren auf
+    // etwa ein Viertel, SQLite-Datenbanken etwa auf die Hälfte
+    pub fn estimate_archive_size(&self, selection: &ArchiveSelection) -> u64 {
+        self.archive_files(selection)
+            .iter()
+            .map(|entry| match entry.path.extension().and_then(|e| e.to_str()) {
+                Some("json") => entry.size / 4,
+                Some("sqlite") => entry.size / 2,
+                _ => entry.size,
+            })
+            .sum()
+    }
+    
+    pub fn export_backups(&self, export_path: &Path, selection: &ArchiveSelection, format: ArchiveFormat, cancel: &CancellationToken) -> Result<usize, String> {
+        let files = self.archive_files(selection);
+        if files.is_empty() {
+            return Err("Keine Backups im gewählten Zeitraum".to_string());
+        }
         
-        zip.finish().map_err(|e| format!("Fehler beim Finalisieren: {}", e))?;
-        Ok(())
+        // Abgebrochen oder fehlgeschlagen: kein halbes Archiv liegen lassen
+        if let Err(e) = format.archiver().write(export_path, &files, cancel) {
+            fs::remove_file(export_path).ok();
+            return Err(e);
+        }
+        Ok(files.len())
     }
     
-    // Favoriten als HTML exportieren
-    pub fn export_as_html(&self, browser: &str, output_path: &Path) -> Result<(), String> {
-        let latest_backup = self.get_backup_list(browser)
-            .into_iter()
-            .next()
-            .ok_or("Kein Backup gefunden")?;
+    // Gegenstück zu export_backups, z.B. nach einer Neuinstallation. Vorhandene
+    // Dateien werden nicht überschrieben; danach wird der Katalog neu aufgebaut.
+    // Gibt (übernommen, übersprungen) zurück.
+    pub fn import_archive(&self, archive_path: &Path) -> Result<(usize, usize), String> {
+        let file = fs::File::open(archive_path)
+            .map_err(|e| format!("Fehler beim Öffnen der ZIP-Datei: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| format!("Keine gültige ZIP-Datei: {}", e))?;
         
+        let mut imported = 0;
+        let mut skipped = 0;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| format!("ZIP Fehler: {}", e))?;
+            if entry.is_dir() {
+                continue;
+            }
+            
+            // Nur <Browser>/<Datei>, wie von export_backups geschrieben
+            let relative = match entry.enclosed_name() {
+                Some(path) => path.to_path_buf(),
+                None => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let parts: Vec<String> = relative.iter().map(|p| p.to_string_lossy().to_string()).collect();
+            let (browser, file_name) = match parts.as_slice() {
+                [browser, file_name] if ["Chrome", "Edge", "Firefox"].contains(&browser.as_str()) => (browser, file_name),
+                _ => {
+                    log::warn!("Unbekannter Eintrag im Archiv übersprungen: {}", relative.display());
+                    skipped += 1;
+                    continue;
+                }
+            };
+            
+            let target_dir = self.backup_dir.join(browser);
+            let target = target_dir.join(file_name);
+            if target.exists() {
+                skipped += 1;
+                continue;
+            }
+            fs::create_dir_all(&target_dir)
+                .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+            
+            let mut buffer = Vec::new();
+            entry.read_to_end(&mut buffer)
+                .map_err(|e| format!("Fehler beim Entpacken von {}: {}", relative.display(), e))?;
+            fs::write(&target, &buffer)
+                .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+            
+            restore_file_time(&target, file_name);
+            imported += 1;
+        }
+        
+        self.rebuild_catalog()?;
+        log::info!("{} Backups aus {} importiert, {} übersprungen", imported, archive_path.display(), skipped);
+        Ok((imported, skipped))
+    }
+    
+    // Backups aus Google Drive in den Backup-Ordner laden, z.B. auf einem
+    // neuen Rechner; vorhandene Dateien werden übersprungen
+    pub fn download_from_drive(&self, drive: &GoogleDriveConfig) -> Result<(usize, usize), String> {
+        let mut imported = 0;
+        let mut skipped = 0;
+        let proxy = self.proxy();
+        for file in drive.list_backups(proxy.as_ref())? {
+            let (browser, file_name) = match file.name.split_once('/') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let target_dir = self.backup_dir.join(browser);
+            let target = target_dir.join(file_name);
+            if target.exists() {
+                skipped += 1;
+                continue;
+            }
+            fs::create_dir_all(&target_dir)
+                .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+            
+            // Erst als .tmp, damit ein abgebrochener Download kein kaputtes Backup hinterlässt
+            let temp_path = target_dir.join(format!("{}.tmp", file_name));
+            drive.download(&file, &temp_path, proxy.as_ref())
+                .and_then(|_| fs::rename(&temp_path, &target)
+                    .map_err(|e| format!("Fehler beim Schreiben: {}", e)))
+                .inspect_err(|_| {
+                    fs::remove_file(&temp_path).ok();
+                })?;
+            restore_file_time(&target, file_name);
+            imported += 1;
+        }
+        
+        self.rebuild_catalog()?;
+        log::info!("{} Backups aus Google Drive geladen, {} bereits vorhanden", imported, skipped);
+        Ok((imported, skipped))
+    }
+    
+    // Lesezeichen eines Backups als browserunabhängigen Baum laden
+    pub fn load_bookmark_tree(&self, browser: &str, backup_path: &Path) -> Result<BookmarkTree, String> {
         match browser {
             "Chrome" | "Edge" => {
-                let content = fs::read_to_string(&latest_backup.path)
-                    .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
-                
-                // Parse JSON und konvertiere zu HTML
-                let bookmarks: serde_json::Value = serde_json::from_str(&content)
+                let content = self.read_backup_content(backup_path)?;
+                let value: serde_json::Value = serde_json::from_str(&content)
                     .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
-                
-                let html = self.json_to_html(&bookmarks);
-                fs::write(output_path, html)
-                    .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+                bookmarks::from_chromium(&value)
             }
-            "Firefox" => {
-                // Firefox SQLite to HTML conversion
-                let html = self.firefox_sqlite_to_html(&latest_backup.path)?;
-                fs::write(output_path, html)
-                    .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+            "Firefox" => bookmarks::from_firefox(backup_path),
+            _ => Err("Unbekannter Browser".to_string()),
+        }
+    }
+    
+    pub fn latest_bookmark_tree(&self, browser: &str) -> Result<BookmarkTree, String> {
+        let latest_backup = self.get_backup_list(browser)
+            .into_iter()
+            .next()
+            .ok_or("Kein Backup gefunden")?;
+        self.load_bookmark_tree(browser, &latest_backup.path)
+    }
+    
+    pub fn export_bookmarks(&self, browser: &str, format: ExportFormat, filter: &BookmarkFilter, layout: &BookmarkLayout, output_path: &Path) -> Result<(), String> {
+        let tree = self.latest_bookmark_tree(browser)?;
+        self.write_export(&layout.apply(&filter.apply(&tree)), browser, &[browser], format, output_path)
+    }
+    
+    // Neueste Backups aller Browser, zusammengeführt und ohne doppelte URLs
+    pub fn merged_bookmark_tree(&self) -> Result<BookmarkTree, String> {
+        let mut trees = Vec::new();
+        for browser in ["Chrome", "Edge", "Firefox"] {
+            if self.get_backup_list(browser).is_empty() {
+                continue;
             }
-            _ => return Err("Unbekannter Browser".to_string()),
+            trees.push((browser, self.latest_bookmark_tree(browser)?));
         }
-        
-        Ok(())
+        if trees.is_empty() {
+            return Err("Kein Backup gefunden".to_string());
+        }
+        Ok(bookmarks::merge(&trees))
     }
- 
-    fn firefox_sqlite_to_html(&self, db_path: &Path) -> Result<String, String> {
-        // Open the SQLite database
-        let conn = Connection::open(db_path)
-            .map_err(|e| format!("Fehler beim Öffnen der Firefox-Datenbank: {}", e))?;
-        
-        // Query to get bookmarks with folder structure
-        let query = r#"
-            WITH RECURSIVE
-            bookmark_tree(id, parent, title, url, position, level, path) AS (
-                -- Root folders
-                SELECT 
-                    b.id,
-                    b.parent,
-                    b.title,
-                    p.url,
-                    b.position,
-                    0 as level,
-                    b.title as path
-                FROM moz_bookmarks b
-                LEFT JOIN moz_places p ON b.fk = p.id
-                WHERE b.parent IN (1, 2, 3, 4, 5)  -- Standard Firefox root folders
-                
-                UNION ALL
-                
-                -- Recursive part
-                SELECT 
-                    b.id,
-                    b.parent,
-                    b.title,
-                    p.url,
-                    b.position,
-                    bt.level + 1,
-                    bt.path || ' > ' || b.title
-                FROM moz_bookmarks b
-                LEFT JOIN moz_places p ON b.fk = p.id
-                JOIN bookmark_tree bt ON b.parent = bt.id
-            )
-            SELECT id, parent, title, url, position, level, path
-            FROM bookmark_tree
-            WHERE title IS NOT NULL
-            ORDER BY parent, position
-        "#;
-        
-        let mut stmt = conn.prepare(query)
-            .map_err(|e| format!("Fehler beim Vorbereiten der SQL-Abfrage: {}", e))?;
-        
-        #[derive(Debug)]
-        struct Bookmark {
-            id: i64,
-            parent: i64,
-            title: String,
-            url: Option<String>,
-            position: i32,
-            level: i32,
-        }
-        
-        let bookmarks_iter = stmt.query_map([], |row| {
-            Ok(Bookmark {
-                id: row.get(0)?,
-                parent: row.get(1)?,
-                title: row.get(2)?,
-                url: row.get(3)?,
-                position: row.get(4)?,
-                level: row.get(5)?,
-            })
-        }).map_err(|e| format!("Fehler beim Ausführen der SQL-Abfrage: {}", e))?;
-        
-        let mut bookmarks: Vec<Bookmark> = Vec::new();
-        for bookmark_result in bookmarks_iter {
-            bookmarks.push(bookmark_result.map_err(|e| format!("Fehler beim Lesen der Lesezeichen: {}", e))?);
-        }
-        
-        // Build HTML
-        let mut html = String::from(
-            "<!DOCTYPE html>\n\
-            <html>\n\
-            <head>\n\
-                <meta charset=\"UTF-8\">\n\
-                <title>Firefox Favoriten</title>\n\
-                <style>\n\
-                    body { font-family: Arial, sans-serif; margin: 20px; }\n\
-                    ul { list-style-type: none; padding-left: 20px; }\n\
-                    li { margin: 5px 0; }\n\
-                    a { text-decoration: none; color: #0066cc; }\n\
-                    a:hover { text-decoration: underline; }\n\
-                    .folder { font-weight: bold; margin: 10px 0; }\n\
-                    .root { margin-left: 0; padding-left: 0; }\n\
-                </style>\n\
-            </head>\n\
-            <body>\n\
-                <h1>Firefox Favoriten</h1>\n"
-        );
+    
+    pub fn export_merged(&self, format: ExportFormat, filter: &BookmarkFilter, layout: &BookmarkLayout, output_path: &Path) -> Result<(), String> {
+        let tree = self.merged_bookmark_tree()?;
+        self.write_export(&layout.apply(&filter.apply(&tree)), "Alle Browser", &["Chrome", "Edge", "Firefox"], format, output_path)
+    }
+    
+    // `browsers` bestimmt, aus welchen Profilen Favicons geladen werden
+    fn write_export(&self, tree: &BookmarkTree, label: &str, browsers: &[&str], format: ExportFormat, output_path: &Path) -> Result<(), String> {
+        let content = match format {
+            ExportFormat::Html => {
+                let mut favicons = FaviconMap::new();
+                for browser in browsers {
+                    favicons.extend(self.load_favicons(browser));
+                }
+                let template = export::html::load_template(&self.backup_dir)?;
+                export::html::render(
+                    &template,
+                    &format!("{} Favoriten", label),
+                    &Local::now().format("%d.%m.%Y %H:%M").to_string(),
+                    &export::html::tree_to_html(tree, &favicons),
+                )
+                .into_bytes()
+            }
+            ExportFormat::NetscapeHtml => export::netscape::to_netscape_html(tree).into_bytes(),
+            ExportFormat::Xbel => export::xbel::to_xbel(tree).into_bytes(),
+            // Ordner werden zu verschachtelten outline-Knoten, Lesezeichen zu Einträgen mit URL
+            ExportFormat::Opml => export::opml::to_opml(tree, &format!("{} Favoriten", label)).into_bytes(),
+            ExportFormat::Canonical => export::canonical::to_json(tree, label)?.into_bytes(),
+            ExportFormat::FirefoxJsonlz4 => {
+                let json = serde_json::to_vec(&bookmarks::to_firefox_json(tree))
+                    .map_err(|e| format!("Fehler beim Erstellen des JSON: {}", e))?;
+                mozlz4::compress(&json)
+            }
+            ExportFormat::Csv => export::csv::to_csv(tree).into_bytes(),
+            ExportFormat::Markdown => export::markdown::to_markdown(tree, &format!("{} Favoriten", label)).into_bytes(),
+        };
+        fs::write(output_path, content)
+            .map_err(|e| format!("Fehler beim Schreiben: {}", e))
+    }
+    
+    // Liest eine Lesezeichen-Datei: bookmarks.html (Netscape-Format),
+    // Chrome/Edge-Bookmarks, Firefox-Sicherungen (.json/.jsonlz4) oder das
+    // browserunabhängige JSON-Format
+    pub fn read_bookmark_file(&self, path: &Path) -> Result<BookmarkTree, String> {
+        let mut data = fs::read(path)
+            .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+        if mozlz4::is_mozlz4(&data) {
+            data = mozlz4::decompress(&data)?;
+        }
+        let content = String::from_utf8(data)
+            .map_err(|_| "Die Datei ist keine Textdatei (UTF-8)".to_string())?;
         
-        // Group bookmarks by parent
-        use std::collections::HashMap;
-        let mut children_map: HashMap<i64, Vec<&Bookmark>> = HashMap::new();
-        for bookmark in &bookmarks {
-            children_map.entry(bookmark.parent).or_insert_with(Vec::new).push(bookmark);
+        if content.trim_start().starts_with('{') {
+            let value: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
+            if value.get("roots").is_some() {
+                return bookmarks::from_chromium(&value);
+            }
+            if value.get("guid").is_some() {
+                return bookmarks::from_firefox_json(&value);
+            }
+            return Ok(export::canonical::from_json(&content)?.to_tree());
         }
+        export::netscape::from_netscape_html(&content)
+    }
+    
+    // Legt eine Lesezeichen-Datei als gewöhnliches Backup von `browser` ab,
+    // damit sie über die normale Wiederherstellung zurückgespielt werden kann
+    pub fn import_bookmarks(&self, browser: &str, source_path: &Path) -> Result<String, String> {
+        let tree = self.read_bookmark_file(source_path)?;
         
-        // Recursive function to build HTML
-        fn build_html_tree(
-            parent_id: i64,
-            children_map: &HashMap<i64, Vec<&Bookmark>>,
-            level: usize
-        ) -> String {
-            let mut result = String::new();
-            
-            if let Some(children) = children_map.get(&parent_id) {
-                let indent = "    ".repeat(level);
-                result.push_str(&format!("{}<ul{}>\n", 
-                    indent, 
-                    if level == 0 { " class=\"root\"" } else { "" }
-                ));
-                
-                for child in children {
-                    if child.url.is_some() {
-                        // It's a bookmark
-                        result.push_str(&format!(
-                            "{}    <li><a href=\"{}\">{}</a></li>\n",
-                            indent,
-                            encode_text(child.url.as_ref().unwrap()).as_ref(),
-                            encode_text(&child.title).as_ref()
-                        ));
-                    } else {
-                        // It's a folder
-                        result.push_str(&format!(
-                            "{}    <li class=\"folder\">{}\n",
-                            indent,
-                            encode_text(&child.title).as_ref()
-                        ));
-                        
-                        // Recursively add children
-                        result.push_str(&build_html_tree(child.id, children_map, level + 2));
-                        
-                        result.push_str(&format!("{}    </li>\n", indent));
-                    }
+        let browser_backup_dir = self.backup_dir.join(browser);
+        fs::create_dir_all(&browser_backup_dir)
+            .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+        
+        let now = Local::now();
+        let format = BookmarkFormat::for_browser(browser);
+        // Firefox-Backups entstehen aus der aktuellen places.sqlite, siehe write_bookmark_file
+        let firefox_template = match format {
+            BookmarkFormat::Firefox => {
+                let current = Self::current_bookmarks_path(browser)?;
+                if !current.exists() {
+                    return Err(format!("Aktuelle Favoriten nicht gefunden: {}", current.display()));
                 }
-                
-                result.push_str(&format!("{}</ul>\n", indent));
+                Some(current)
+            }
+            BookmarkFormat::Chromium => None,
+        };
+        let backup_filename = format!("bookmarks_{}{}.{}", now.format("%Y%m%d_%H%M%S"), self.machine_suffix(), format.extension());
+        let backup_path = browser_backup_dir.join(&backup_filename);
+        if backup_path.exists() {
+            return Err("Es existiert bereits ein Backup mit diesem Zeitstempel, bitte erneut versuchen".to_string());
+        }
+        
+        if let Err(e) = self.write_bookmark_file(&tree, format, &backup_path, firefox_template.as_deref()) {
+            fs::remove_file(&backup_path).ok();
+            return Err(e);
+        }
+        
+        // Kein echtes Backup des Browsers, daher zählt es nicht als letzter erfolgreicher Lauf
+        self.update_catalog(|catalog| catalog.add_entry(browser, &backup_path, now));
+        log::info!("{} als {}-Backup importiert: {}", source_path.display(), browser, backup_filename);
+        Ok(format!("Importiert als {}", backup_filename))
+    }
+    
+    // Schreibt den Baum im Dateiformat eines Browsers und prüft das Ergebnis.
+    // Für Firefox in eine Kopie von `firefox_template` (places.sqlite): eine neue
+    // Datenbank enthielte nur Lesezeichen, beim Zurückspielen ginge der Verlauf verloren.
+    fn write_bookmark_file(&self, tree: &BookmarkTree, format: BookmarkFormat, path: &Path, firefox_template: Option<&Path>) -> Result<(), String> {
+        match format {
+            BookmarkFormat::Firefox => {
+                let template = firefox_template.ok_or("Keine places.sqlite als Vorlage für Firefox")?;
+                // Sonst fehlt der Kopie alles, was noch in places.sqlite-wal steht
+                Self::checkpoint_sqlite(template)?;
+                fs::copy(template, path)
+                    .map_err(|e| format!("Fehler beim Kopieren der aktuellen Favoriten: {}", e))?;
+                bookmarks::replace_firefox(path, tree)?;
+            }
+            BookmarkFormat::Chromium => {
+                let content = serde_json::to_string_pretty(&bookmarks::to_chromium(tree))
+                    .map_err(|e| format!("Fehler beim Serialisieren: {}", e))?;
+                fs::write(path, content)
+                    .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
             }
-            
-            result
         }
+        self.verify_backup(path)
+    }
+    
+    // Eindeutige Datei im Temp-Ordner: Oberfläche, CLI und REST-API, auch aus einer
+    // zweiten Windows-Sitzung, können gleichzeitig wiederherstellen
+    fn temp_file(name: &str, extension: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let number = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("browser_backup_{}_{}_{}.{}", name, std::process::id(), number, extension))
+    }
+    
+    // Vergleicht das Backup mit den aktuellen Favoriten im Ziel; `target_path` wie bei
+    // restore_backup_to, ohne Angabe das Standardprofil von `target_browser`
+    pub fn restore_summary(&self, source_browser: &str, backup_path: &Path, target_browser: &str, target_path: Option<&Path>) -> Result<RestoreSummary, String> {
+        let backup = self.load_bookmark_tree(source_browser, backup_path)?;
+        let target_path = match target_path {
+            Some(path) => path.to_path_buf(),
+            None => Self::current_bookmarks_path(target_browser)?,
+        };
+        let current = if target_path.exists() {
+            self.load_bookmark_tree(target_browser, &target_path).ok()
+        } else {
+            None
+        };
         
-        // Start with root folders (IDs 1-5 are standard Firefox roots)
-        for root_id in 1..=5 {
-            html.push_str(&build_html_tree(root_id, &children_map, 0));
+        let (backup_folders, backup_links) = backup.counts();
+        let (added, removed) = match &current {
+            Some(current) => bookmarks::url_changes(current, &backup),
+            None => (backup_links, 0),
+        };
+        let mappings = if source_browser == target_browser {
+            Vec::new()
+        } else {
+            bookmarks::root_mapping(&backup, BookmarkFormat::for_browser(target_browser))
+        };
+        Ok(RestoreSummary {
+            backup_folders,
+            backup_links,
+            current_links: current.map(|tree| tree.counts().1),
+            added,
+            removed,
+            target_path,
+            mappings,
+        })
+    }
+    
+    // Stellt ein Backup von `source_browser` in einem anderen Browser wieder her,
+    // z.B. beim Umstieg von Chrome auf Firefox; `target_path` wie bei restore_backup_to,
+    // ohne Angabe in das Standardprofil
+    pub fn restore_converted(&self, source_browser: &str, backup_path: &Path, target_browser: &str, target_path: Option<&Path>, dry_run: bool) -> Result<String, String> {
+        let tree = self.load_bookmark_tree(source_browser, backup_path)?;
+        let format = BookmarkFormat::for_browser(target_browser);
+        
+        let converted_path = Self::temp_file(&target_browser.to_lowercase(), format.extension());
+        // In Firefox ersetzt das Backup nur die Lesezeichen der aktuellen places.sqlite
+        let current_path = match target_path {
+            Some(target_path) => target_path.to_path_buf(),
+            None => Self::current_bookmarks_path(target_browser)?,
+        };
+        if format == BookmarkFormat::Firefox && !current_path.exists() {
+            return Err(format!("Aktuelle Favoriten nicht gefunden: {}", current_path.display()));
         }
         
-        html.push_str("</body>\n</html>");
+        let result = self.write_bookmark_file(&tree, format, &converted_path, Some(&current_path))
+            .and_then(|_| match target_path {
+                Some(target_path) => self.restore_backup_to(target_browser, &converted_path, target_path, dry_run),
+                None => self.restore_backup(target_browser, &converted_path, dry_run),
+            });
+        fs::remove_file(&converted_path).ok();
         
-        Ok(html)
+        let message = result?;
+        log::info!("{}-Backup {} nach {} übertragen", source_browser, backup_path.display(), target_browser);
+        Ok(message)
     }
-
-    fn json_to_html(&self, bookmarks: &serde_json::Value) -> String {
-        let mut html = String::from(
-            "<!DOCTYPE html>\n\
-            <html>\n\
-            <head>\n\
-                <meta charset=\"UTF-8\">\n\
-                <title>Browser Favoriten</title>\n\
-                <style>\n\
-                    body { font-family: Arial, sans-serif; margin: 20px; }\n\
-                    ul { list-style-type: none; }\n\
-                    a { text-decoration: none; color: #0066cc; }\n\
-                    a:hover { text-decoration: underline; }\n\
-                    .folder { font-weight: bold; margin: 10px 0; }\n\
-                </style>\n\
-            </head>\n\
-            <body>\n\
-                <h1>Browser Favoriten</h1>\n"
-        );
+    
+    // Setzt nur die gewählten Ordner auf den Stand des Backups zurück
+    pub fn restore_folders(&self, browser: &str, backup_path: &Path, folders: &[FolderPath], dry_run: bool) -> Result<String, String> {
+        if folders.is_empty() {
+            return Err("Keine Ordner ausgewählt".to_string());
+        }
+        let (restored, message) = self.restore_partial(browser, backup_path, PartialRestore::Folders(folders), dry_run)?;
+        if dry_run {
+            return Ok(message);
+        }
+        log::info!("{} Ordner aus {} in {} wiederhergestellt", restored, backup_path.display(), browser);
+        let mut message = format!("{} Ordner in {} wiederhergestellt, alle übrigen Favoriten bleiben unverändert", restored, browser);
+        if browser == "Firefox" {
+            message.push_str("\n(Firefox muss neu gestartet werden)");
+        }
+        Ok(message)
+    }
+    
+    // Ergänzt die aktuellen Favoriten um die Lesezeichen des Backups, die fehlen;
+    // nichts wird entfernt oder überschrieben
+    pub fn restore_merged(&self, browser: &str, backup_path: &Path, dry_run: bool) -> Result<String, String> {
+        let (added, message) = self.restore_partial(browser, backup_path, PartialRestore::Merge, dry_run)?;
+        if added == 0 {
+            return Ok(format!("Alle Lesezeichen des Backups sind in {} bereits vorhanden", browser));
+        }
+        if dry_run {
+            return Ok(format!("{}; {} fehlende Lesezeichen würden ergänzt", message, added));
+        }
+        log::info!("{} Lesezeichen aus {} in {} ergänzt", added, backup_path.display(), browser);
+        let mut message = format!("{} fehlende Lesezeichen in {} ergänzt, vorhandene Favoriten bleiben erhalten", added, browser);
+        if browser == "Firefox" {
+            message.push_str("\n(Firefox muss neu gestartet werden)");
+        }
+        Ok(message)
+    }
+    
+    // Fügt ein einzelnes Lesezeichen aus dem Backup wieder ein, in seinen alten Ordner
+    pub fn restore_bookmark(&self, browser: &str, backup_path: &Path, url: &str, dry_run: bool) -> Result<String, String> {
+        let (added, message) = self.restore_partial(browser, backup_path, PartialRestore::Link(url), dry_run)?;
+        if added == 0 {
+            return Ok(format!("{} enthält {} bereits", browser, url));
+        }
+        if dry_run {
+            return Ok(format!("{}; {} würde ergänzt", message, url));
+        }
+        log::info!("Lesezeichen {} aus {} in {} wieder eingefügt", url, backup_path.display(), browser);
+        let mut message = format!("Lesezeichen {} in {} wieder eingefügt", url, browser);
+        if browser == "Firefox" {
+            message.push_str("\n(Firefox muss neu gestartet werden)");
+        }
+        Ok(message)
+    }
+    
+    // Übernimmt das Backup in eine Kopie der aktuellen Favoriten, die dann wie ein
+    // Backup zurückgespielt wird (mit Prüfung, Sicherheitskopie und Probelauf).
+    // Liefert die Zahl der übernommenen Ordner bzw. Lesezeichen; bei 0 bleibt alles, wie es ist.
+    fn restore_partial(&self, browser: &str, backup_path: &Path, mode: PartialRestore, dry_run: bool) -> Result<(usize, String), String> {
+        self.verify_restore_source(browser, backup_path)?;
         
-        // Rekursive Funktion zum Parsen der Bookmarks
-        fn parse_folder(folder: &serde_json::Value, depth: usize) -> String {
-            let mut result = String::new();
-            let indent = "    ".repeat(depth);
-            
-            if let Some(name) = folder.get("name").and_then(|v| v.as_str()) {
-                if depth > 0 {
-                    result.push_str(&format!("{}<div class=\"folder\">{}</div>\n", indent, encode_text(name)));
-                }
+        let format = BookmarkFormat::for_browser(browser);
+        let current_path = Self::current_bookmarks_path(browser)?;
+        if !current_path.exists() {
+            return Err(format!("Aktuelle Favoriten nicht gefunden: {}", current_path.display()));
+        }
+        
+        let merged_path = Self::temp_file(&format!("{}_teilweise", browser.to_lowercase()), format.extension());
+        let changed = match format {
+            BookmarkFormat::Chromium => {
+                let backup: serde_json::Value = serde_json::from_str(&self.read_backup_content(backup_path)?)
+                    .map_err(|e| format!("JSON Parse Fehler: {}", e))?;
+                let content = fs::read_to_string(&current_path)
+                    .map_err(|e| format!("Fehler beim Lesen der aktuellen Favoriten: {}", e))?;
+                let mut current: serde_json::Value = serde_json::from_str(&content)
+                    .map_err(|e| format!("Aktuelle Favoriten sind kein gültiges JSON: {}", e))?;
+                let changed = match mode {
+                    PartialRestore::Folders(folders) => bookmarks::restore_chromium_folders(&mut current, &backup, folders),
+                    PartialRestore::Merge => bookmarks::merge_chromium(&mut current, &backup),
+                    PartialRestore::Link(url) => bookmarks::single_link(&bookmarks::from_chromium(&backup)?, url)
+                        .ok_or_else(|| format!("Lesezeichen {} nicht im Backup gefunden", url))
+                        .and_then(|single| bookmarks::merge_chromium(&mut current, &bookmarks::to_chromium(&single))),
+                };
+                changed.and_then(|changed| {
+                    let content = serde_json::to_string_pretty(&current)
+                        .map_err(|e| format!("Fehler beim Serialisieren: {}", e))?;
+                    fs::write(&merged_path, content)
+                        .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+                    Ok(changed)
+                })
             }
-            
-            if let Some(children) = folder.get("children").and_then(|v| v.as_array()) {
-                result.push_str(&format!("{}<ul>\n", indent));
-                
-                for child in children {
-                    if let Some(type_) = child.get("type").and_then(|v| v.as_str()) {
-                        match type_ {
-                            "folder" => {
-                                result.push_str(&parse_folder(child, depth + 1));
-                            }
-                            "url" => {
-                                if let (Some(name), Some(url)) = (
-                                    child.get("name").and_then(|v| v.as_str()),
-                                    child.get("url").and_then(|v| v.as_str())
-                                ) {
-                                    result.push_str(&format!(
-                                        "{}    <li><a href=\"{}\">{}</a></li>\n",
-                                        indent,    
-                                        encode_text(url).as_ref(),
-                                        encode_text(name).as_ref()
-                                    ));
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-                
-                result.push_str(&format!("{}</ul>\n", indent));
+            BookmarkFormat::Firefox => {
+                let backup = self.load_bookmark_tree(browser, backup_path)?;
+                // Neue Lesezeichen stehen evtl. noch in places.sqlite-wal
+                Self::checkpoint_sqlite(&current_path)?;
+                fs::copy(&current_path, &merged_path)
+                    .map_err(|e| format!("Fehler beim Kopieren der aktuellen Favoriten: {}", e))
+                    .and_then(|_| match mode {
+                        PartialRestore::Folders(folders) => bookmarks::restore_firefox_folders(&merged_path, &backup, folders),
+                        PartialRestore::Merge => bookmarks::merge_firefox(&merged_path, &backup),
+                        PartialRestore::Link(url) => bookmarks::single_link(&backup, url)
+                            .ok_or_else(|| format!("Lesezeichen {} nicht im Backup gefunden", url))
+                            .and_then(|single| bookmarks::merge_firefox(&merged_path, &single)),
+                    })
             }
-            
-            result
+        };
+        
+        let result = changed.and_then(|changed| match changed {
+            0 => Ok((0, String::new())),
+            _ => self.restore_backup(browser, &merged_path, dry_run).map(|message| (changed, message)),
+        });
+        fs::remove_file(&merged_path).ok();
+        result
+    }
+    
+    // Favoriten-Datei im Profil, in die wiederhergestellt wird
+    fn current_bookmarks_path(browser: &str) -> Result<PathBuf, String> {
+        match browser {
+            "Chrome" => Ok(Self::chrome_bookmarks_path()),
+            "Edge" => Ok(Self::edge_bookmarks_path()),
+            "Firefox" => Self::find_firefox_profile()
+                .map(|profile| profile.join("places.sqlite"))
+                .ok_or_else(|| "Firefox Profil nicht gefunden".to_string()),
+            _ => Err("Unbekannter Browser".to_string()),
         }
+    }
+    
+    // Alle Profile eines Browsers, das Standardprofil zuerst. Chrome/Edge kennen
+    // "Default" und "Profile N" mit den Anzeigenamen aus "Local State", bei Firefox
+    // zählt jeder Profilordner mit einer places.sqlite.
+    pub fn browser_profiles(browser: &str) -> Vec<BrowserProfile> {
+        let default_path = Self::current_bookmarks_path(browser).ok();
+        let mut profiles: Vec<BrowserProfile> = match BookmarkFormat::for_browser(browser) {
+            BookmarkFormat::Chromium => {
+                let user_data = match default_path.as_deref().and_then(Path::parent).and_then(Path::parent) {
+                    Some(dir) => dir.to_path_buf(),
+                    None => return Vec::new(),
+                };
+                let local_state: serde_json::Value = fs::read_to_string(user_data.join("Local State"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str(&content).ok())
+                    .unwrap_or_default();
+                fs::read_dir(&user_data)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|dir| dir == "Default" || dir.starts_with("Profile "))
+                    .map(|dir| BrowserProfile {
+                        name: local_state["profile"]["info_cache"][&dir]["name"]
+                            .as_str()
+                            .unwrap_or(&dir)
+                            .to_string(),
+                        bookmarks_path: user_data.join(&dir).join("Bookmarks"),
+                        default: false,
+                        dir,
+                    })
+                    .collect()
+            }
+            BookmarkFormat::Firefox => fs::read_dir(Self::firefox_profiles_dir())
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path().join("places.sqlite"))
+                .filter(|path| path.is_file())
+                .filter_map(|path| {
+                    let dir = path.parent()?.file_name()?.to_string_lossy().to_string();
+                    // Ordner heißen <Zufall>.<Name>
+                    let name = dir.split_once('.').map_or(dir.as_str(), |(_, name)| name).to_string();
+                    Some(BrowserProfile { dir, name, bookmarks_path: path, default: false })
+                })
+                .collect(),
+        };
+        for profile in &mut profiles {
+            profile.default = Some(&profile.bookmarks_path) == default_path.as_ref();
+        }
+        // Über die Länge kommt "Profile 10" nach "Profile 9"
+        profiles.sort_by_key(|profile| (!profile.default, profile.dir.len(), profile.dir.clone()));
+        profiles
+    }
+    
+    // Favoriten-Datei, aus der ein Backup gesichert wurde, laut Katalog
+    pub fn backup_source(&self, browser: &str, backup_path: &Path) -> Option<PathBuf> {
+        let file_name = backup_path.file_name()?.to_string_lossy().to_string();
+        self.get_catalog().entry(browser, &file_name)?.source.clone()
+    }
+    
+    // Lokales Gegenstück zu einer Favoriten-Datei, die auf einem anderen Rechner oder
+    // unter einem anderen Benutzer gesichert wurde: das Profil mit demselben Ordnernamen,
+    // bei Firefox sonst mit demselben Profilnamen (die Ordner beginnen mit Zufallszeichen),
+    // sonst das Standardprofil
+    pub fn local_profile_for(browser: &str, source: &Path) -> Option<BrowserProfile> {
+        let profiles = Self::browser_profiles(browser);
+        let source_dir = source.parent()?.file_name()?.to_string_lossy().to_string();
+        let source_name = source_dir.split_once('.').map_or(source_dir.as_str(), |(_, name)| name);
+        let firefox = BookmarkFormat::for_browser(browser) == BookmarkFormat::Firefox;
+        profiles.iter()
+            .find(|profile| profile.dir == source_dir)
+            .or_else(|| profiles.iter().find(|profile| firefox && profile.name == source_name))
+            .or_else(|| profiles.iter().find(|profile| profile.default))
+            .cloned()
+    }
+    
+    // Favoriten-Datei, aus der eine Sicherung vor der Wiederherstellung stammt. Sicherungen
+    // aus einem anderen als dem Standardprofil tragen dessen Ordnernamen wie Backups den
+    // Rechnernamen: bookmarks_<JJJJMMTT_HHMMSS>_<Profil>.<ext>
+    fn snapshot_target(browser: &str, snapshot: &BackupFile) -> Result<PathBuf, String> {
+        let profile = snapshot.name
+            .strip_prefix("bookmarks_")
+            .and_then(|rest| rest.get(15..))
+            .and_then(|rest| rest.strip_prefix('_'))
+            .and_then(|rest| rest.rsplit_once('.'))
+            .map(|(profile, _)| profile);
+        match profile {
+            Some(profile) => Self::browser_profiles(browser)
+                .into_iter()
+                .find(|p| p.dir == profile)
+                .map(|p| p.bookmarks_path)
+                .ok_or_else(|| format!("{}-Profil \"{}\" nicht gefunden", browser, profile)),
+            None => Self::current_bookmarks_path(browser),
+        }
+    }
+    
+    // Legt eine Kopie der aktuellen Favoriten unter PreRestore/<Browser> ab;
+    // ältere Kopien über PRE_RESTORE_KEEP hinaus werden gelöscht
+    fn snapshot_before_restore(&self, browser: &str, current_path: &Path) -> Result<PathBuf, String> {
+        let snapshot_dir = self.backup_dir.join(PRE_RESTORE_DIR).join(browser);
+        fs::create_dir_all(&snapshot_dir)
+            .map_err(|e| format!("Fehler beim Sichern der aktuellen Datei: {}", e))?;
+        let extension = BookmarkFormat::for_browser(browser).extension();
+        let mut name = format!("bookmarks_{}", Local::now().format("%Y%m%d_%H%M%S"));
+        if Self::current_bookmarks_path(browser).ok().as_deref() != Some(current_path) {
+            if let Some(profile) = current_path.parent().and_then(Path::file_name) {
+                name = format!("{}_{}", name, profile.to_string_lossy());
+            }
+        }
+        let snapshot = snapshot_dir.join(format!("{}.{}", name, extension));
+        fs::copy(current_path, &snapshot)
+            .map_err(|e| format!("Fehler beim Sichern der aktuellen Datei: {}", e))?;
+        
+        for old in self.pre_restore_snapshots(browser).into_iter().skip(PRE_RESTORE_KEEP) {
+            fs::remove_file(&old.path).ok();
+        }
+        log::info!("Stand vor der Wiederherstellung gesichert: {}", snapshot.display());
+        Ok(snapshot)
+    }
+    
+    // Zusätzlich ein reguläres Backup des aktuellen Stands, im Katalog als
+    // "Vor Wiederherstellung <Zeitpunkt>" beschriftet. Es bleibt wie jedes andere Backup
+    // bis zur Bereinigung erhalten, auch wenn die Sicherung unter PreRestore längst
+    // verdrängt ist. Schlägt es fehl (z.B. weil die aktuellen Favoriten beschädigt sind),
+    // läuft die Wiederherstellung trotzdem weiter.
+    fn backup_before_restore(&self, browser: &str, current_path: &Path) -> Option<PathBuf> {
+        let extension = BookmarkFormat::for_browser(browser).extension();
+        let result = self.backup_browser_file(browser, current_path, extension, &CancellationToken::default());
+        let path = match result.path {
+            Some(path) if result.success => path,
+            _ => {
+                log::warn!("Backup vor der Wiederherstellung fehlgeschlagen: {}", result.message);
+                return None;
+            }
+        };
         
-        if let Some(roots) = bookmarks.get("roots").and_then(|v| v.as_object()) {
-            for (_, folder) in roots {
-                html.push_str(&parse_folder(folder, 0));
+        let now = Local::now();
+        let label = format!("Vor Wiederherstellung {}", now.format("%d.%m.%Y %H:%M:%S"));
+        self.update_catalog(|catalog| {
+            // Kein erfolgreicher Lauf im Sinne von last_success
+            catalog.add_entry(browser, &path, now);
+            if let Some(name) = path.file_name() {
+                let name = name.to_string_lossy();
+                catalog.set_source(browser, &name, current_path);
+                catalog.set_label(browser, &name, &label);
             }
+        });
+        log::info!("{}: {}", label, path.display());
+        Some(path)
+    }
+    
+    // Beschriftungen aus dem Katalog nach Dateiname, z.B. für die Backup-Liste
+    pub fn backup_labels(&self, browser: &str) -> HashMap<String, String> {
+        self.get_catalog().entries
+            .into_iter()
+            .filter(|entry| entry.browser == browser)
+            .filter_map(|entry| Some((entry.file_name, entry.label?)))
+            .collect()
+    }
+    
+    // Neuestes Backup dieses Rechners für "Neuestes Backup wiederherstellen" im Tray.
+    // Backups vor einer Wiederherstellung zählen nicht, sonst käme beim zweiten Klick
+    // genau der Stand zurück, den der erste ersetzt hat.
+    pub fn latest_restorable_backup(&self, browser: &str) -> Option<BackupFile> {
+        let labels = self.backup_labels(browser);
+        let own_machine = self.own_machine();
+        let extension = BookmarkFormat::for_browser(browser).extension();
+        self.get_backup_list(browser)
+            .into_iter()
+            // Ohne -wal/-shm-Dateien neben den Firefox-Backups
+            .filter(|backup| backup.path.extension().is_some_and(|ext| ext == extension))
+            .filter(|backup| catalog::machine_from_file_name(&backup.name) == own_machine.as_deref())
+            .filter(|backup| !labels.contains_key(&backup.name))
+            .max_by_key(|backup| catalog::timestamp_from_file_name(&backup.name).unwrap_or(backup.date))
+    }
+    
+    // Neueste zuerst. `date` ist der Zeitpunkt der Sicherung aus dem Dateinamen,
+    // denn beim Kopieren bleibt das Änderungsdatum der Favoriten-Datei erhalten.
+    pub fn pre_restore_snapshots(&self, browser: &str) -> Vec<BackupFile> {
+        let snapshot_dir = Path::new(PRE_RESTORE_DIR).join(browser);
+        let mut snapshots = self.get_backup_list(&snapshot_dir.to_string_lossy());
+        for snapshot in &mut snapshots {
+            snapshot.date = catalog::timestamp_from_file_name(&snapshot.name).unwrap_or(snapshot.date);
         }
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.date));
+        snapshots
+    }
+    
+    // Browser und Sicherung der zuletzt ausgeführten Wiederherstellung
+    pub fn last_pre_restore_snapshot(&self) -> Option<(&'static str, BackupFile)> {
+        ["Chrome", "Edge", "Firefox"]
+            .into_iter()
+            .filter_map(|browser| self.pre_restore_snapshots(browser).into_iter().next().map(|s| (browser, s)))
+            .max_by_key(|(_, snapshot)| snapshot.date)
+    }
+    
+    // Spielt den Stand vor der letzten Wiederherstellung in `browser` zurück und
+    // entfernt diese Sicherung; ein weiterer Aufruf geht einen Schritt weiter zurück
+    pub fn undo_restore(&self, browser: &str, dry_run: bool) -> Result<String, String> {
+        let snapshot = self.pre_restore_snapshots(browser)
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("Keine Wiederherstellung in {} zum Rückgängigmachen", browser))?;
+        let target_path = Self::snapshot_target(browser, &snapshot)?;
+        let taken = snapshot.date.format("%d.%m.%Y %H:%M:%S");
         
-        html.push_str("</body>\n</html>");
-        html
+        if dry_run {
+            return Ok(format!("Probelauf: {} würde auf den Stand vom {} zurückgesetzt", target_path.display(), taken));
+        }
+        if browser_processes::is_running(browser) {
+            return Err(format!("{} läuft noch. Bitte den Browser vor der Wiederherstellung beenden.", browser));
+        }
+        self.replace_bookmark_file(&snapshot.path, &target_path, BookmarkFormat::for_browser(browser))?;
+        fs::remove_file(&snapshot.path).ok();
+        log::info!("Wiederherstellung in {} rückgängig gemacht, Stand vom {}", browser, taken);
+        
+        let mut message = format!("Wiederherstellung rückgängig gemacht, {} hat wieder den Stand vom {}", browser, taken);
+        if browser == "Firefox" {
+            message.push_str("\n(Firefox muss neu gestartet werden)");
+        }
+        Ok(message)
     }
-}
\ No newline at end of file
+    
+    // Eigene HTML-Vorlage im Backup-Ordner; eine vorhandene bleibt unverändert
+    pub fn create_export_template(&self) -> Result<PathBuf, String> {
+        let path = self.backup_dir.join(export::html::TEMPLATE_FILE);
+        if !path.exists() {
+            fs::write(&path, export::html::DEFAULT_TEMPLATE)
+                .map_err(|e| format!("Vorlage konnte nicht angelegt werden: {}", e))?;
+        }
+        Ok(path)
+    }
+    
+    // Favicons stammen aus dem aktuellen Profil, nicht aus dem Backup;
+    // fehlen sie, wird ohne Icons exportiert
+    fn load_favicons(&self, browser: &str) -> FaviconMap {
+        let result = match browser {
+            "Chrome" => Self::chrome_bookmarks_path().parent().map(favicons::load_chromium),
+            "Edge" => Self::edge_bookmarks_path().parent().map(favicons::load_chromium),
+            "Firefox" => Self::find_firefox_profile().as_deref().map(favicons::load_firefox),
+            _ => None,
+        };
+        
+        match result {
+            Some(Ok(icons)) => icons,
+            Some(Err(e)) => {
+                log::warn!("Favicons für {} nicht verfügbar: {}", browser, e);
+                FaviconMap::new()
+            }
+            None => FaviconMap::new(),
+        }
+    }
+}
+
+// Die Backup-Liste sortiert nach Änderungsdatum; das soll dem
+// Erstellungszeitpunkt entsprechen, nicht dem Zeitpunkt des Imports
+fn restore_file_time(path: &Path, file_name: &str) {
+    if let Some(created) = catalog::timestamp_from_file_name(file_name) {
+        if let Ok(file) = fs::File::options().write(true).open(path) {
+            file.set_modified(created.into()).ok();
+        }
+    }
+}
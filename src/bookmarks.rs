@@ -0,0 +1,1358 @@
+// bookmarks.rs - Browserunabhängiger Lesezeichen-Baum
+//
+// Chrome/Edge-JSON und Firefox-places.sqlite werden in dieselbe Struktur
+// überführt, damit alle Exportformate nur einen Baum kennen müssen.
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::md5::Md5;
+
+// Art eines Wurzelordners, über Browser hinweg vergleichbar
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RootKind {
+    // Lesezeichenleiste bzw. -Symbolleiste
+    Toolbar,
+    // Firefox: Lesezeichen-Menü
+    Menu,
+    // Chrome: "other", Firefox: "unfiled"
+    Other,
+    Mobile,
+}
+
+#[derive(Clone, Debug)]
+pub struct BookmarkFolder {
+    // ID bzw. GUID aus dem Browser, leer wenn unbekannt
+    pub id: String,
+    pub title: String,
+    pub added: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    // Nur bei Wurzelordnern gesetzt
+    pub root: Option<RootKind>,
+    pub children: Vec<BookmarkNode>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BookmarkLink {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub added: Option<DateTime<Utc>>,
+    // Browser, in denen das Lesezeichen vorkommt; nur bei zusammengeführten Bäumen gesetzt
+    pub sources: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum BookmarkNode {
+    Folder(BookmarkFolder),
+    Link(BookmarkLink),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BookmarkTree {
+    pub roots: Vec<BookmarkFolder>,
+}
+
+// Dateiformat, in das ein Baum für die Wiederherstellung geschrieben wird
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BookmarkFormat {
+    // Bookmarks-Datei von Chrome und Edge
+    Chromium,
+    // places.sqlite
+    Firefox,
+}
+
+impl BookmarkFormat {
+    pub fn for_browser(browser: &str) -> Self {
+        if browser == "Firefox" {
+            BookmarkFormat::Firefox
+        } else {
+            BookmarkFormat::Chromium
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            BookmarkFormat::Chromium => "json",
+            BookmarkFormat::Firefox => "sqlite",
+        }
+    }
+
+    // Wurzelordner im Ziel und ob der Quellordner dort als Unterordner landet;
+    // entspricht der Zuordnung in `to_chromium` und `replace_firefox`
+    fn target_root(self, kind: Option<RootKind>) -> (&'static str, bool) {
+        match (self, kind) {
+            (BookmarkFormat::Chromium, Some(RootKind::Toolbar)) => ("Lesezeichenleiste", false),
+            (BookmarkFormat::Chromium, Some(RootKind::Other)) => ("Weitere Lesezeichen", false),
+            (BookmarkFormat::Chromium, Some(RootKind::Mobile)) => ("Mobile Lesezeichen", false),
+            (BookmarkFormat::Chromium, Some(RootKind::Menu) | None) => ("Weitere Lesezeichen", true),
+            (BookmarkFormat::Firefox, Some(RootKind::Toolbar)) => ("Lesezeichen-Symbolleiste", false),
+            (BookmarkFormat::Firefox, Some(RootKind::Menu)) => ("Lesezeichen-Menü", false),
+            (BookmarkFormat::Firefox, Some(RootKind::Other)) => ("Weitere Lesezeichen", false),
+            (BookmarkFormat::Firefox, Some(RootKind::Mobile)) => ("Mobile Lesezeichen", false),
+            (BookmarkFormat::Firefox, None) => ("Weitere Lesezeichen", true),
+        }
+    }
+}
+
+// Eine Zeile der Vorschau vor einer browserübergreifenden Wiederherstellung
+#[derive(Clone, Debug)]
+pub struct RootMapping {
+    pub source: String,
+    pub target: String,
+    pub folders: usize,
+    pub links: usize,
+}
+
+fn count_nodes(nodes: &[BookmarkNode]) -> (usize, usize) {
+    nodes.iter().fold((0, 0), |(folders, links), node| match node {
+        BookmarkNode::Folder(folder) => {
+            let (f, l) = count_nodes(&folder.children);
+            (folders + 1 + f, links + l)
+        }
+        BookmarkNode::Link(_) => (folders, links + 1),
+    })
+}
+
+impl BookmarkTree {
+    // (Ordner, Lesezeichen) ohne die Wurzelordner selbst
+    pub fn counts(&self) -> (usize, usize) {
+        self.roots.iter()
+            .map(BookmarkFolder::counts)
+            .fold((0, 0), |(folders, links), (f, l)| (folders + f, links + l))
+    }
+}
+
+impl BookmarkFolder {
+    // (Ordner, Lesezeichen) unterhalb dieses Ordners, beliebig tief
+    pub fn counts(&self) -> (usize, usize) {
+        count_nodes(&self.children)
+    }
+}
+
+impl BookmarkTree {
+    // Lesezeichen, deren Titel oder URL den Suchtext enthält (ohne Groß-/Kleinschreibung),
+    // jeweils mit dem Ordnerpfad wie "Lesezeichenleiste / Rezepte"
+    pub fn find_links(&self, query: &str) -> Vec<(String, &BookmarkLink)> {
+        fn walk<'a>(folder: &'a BookmarkFolder, path: &str, query: &str, matches: &mut Vec<(String, &'a BookmarkLink)>) {
+            let path = if path.is_empty() { folder.title.clone() } else { format!("{} / {}", path, folder.title) };
+            for node in &folder.children {
+                match node {
+                    BookmarkNode::Folder(child) => walk(child, &path, query, matches),
+                    BookmarkNode::Link(link) => {
+                        if link.title.to_lowercase().contains(query) || link.url.to_lowercase().contains(query) {
+                            matches.push((path.clone(), link));
+                        }
+                    }
+                }
+            }
+        }
+
+        let query = query.to_lowercase();
+        let mut matches = Vec::new();
+        for root in &self.roots {
+            walk(root, "", &query, &mut matches);
+        }
+        matches
+    }
+}
+
+// Zeigt, wohin die Wurzelordner beim Schreiben in `format` wandern
+pub fn root_mapping(tree: &BookmarkTree, format: BookmarkFormat) -> Vec<RootMapping> {
+    tree.roots
+        .iter()
+        .map(|root| {
+            let (target, as_subfolder) = format.target_root(root.root);
+            let (folders, links) = count_nodes(&root.children);
+            RootMapping {
+                source: root.title.clone(),
+                target: if as_subfolder { format!("{} / {}", target, root.title) } else { target.to_string() },
+                folders,
+                links,
+            }
+        })
+        .collect()
+}
+
+// Chromium speichert Mikrosekunden seit dem 01.01.1601 als String
+const WINDOWS_TO_UNIX_EPOCH_SECS: i64 = 11_644_473_600;
+
+fn chromium_time(node: &Value, key: &str) -> Option<DateTime<Utc>> {
+    let micros: i64 = node.get(key)?.as_str()?.parse().ok()?;
+    if micros == 0 {
+        return None;
+    }
+    Utc.timestamp_micros(micros - WINDOWS_TO_UNIX_EPOCH_SECS * 1_000_000).single()
+}
+
+fn chromium_timestamp(date: Option<DateTime<Utc>>) -> String {
+    date.map(|d| d.timestamp_micros() + WINDOWS_TO_UNIX_EPOCH_SECS * 1_000_000)
+        .unwrap_or(0)
+        .to_string()
+}
+
+// Firefox speichert Mikrosekunden seit 1970 (PRTime)
+fn firefox_time(micros: Option<i64>) -> Option<DateTime<Utc>> {
+    micros.filter(|m| *m > 0).and_then(|m| Utc.timestamp_micros(m).single())
+}
+
+pub fn from_chromium(bookmarks: &Value) -> Result<BookmarkTree, String> {
+    let roots = bookmarks
+        .get("roots")
+        .and_then(|v| v.as_object())
+        .ok_or("Ungültige Favoriten-Datei: \"roots\" fehlt")?;
+
+    fn convert(node: &Value) -> Option<BookmarkNode> {
+        let id = node.get("guid").or_else(|| node.get("id")).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let title = node.get("name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        match node.get("type").and_then(|v| v.as_str())? {
+            "folder" => Some(BookmarkNode::Folder(BookmarkFolder {
+                id,
+                title,
+                added: chromium_time(node, "date_added"),
+                modified: chromium_time(node, "date_modified"),
+                root: None,
+                children: node
+                    .get("children")
+                    .and_then(|v| v.as_array())
+                    .map(|children| children.iter().filter_map(convert).collect())
+                    .unwrap_or_default(),
+            })),
+            "url" => Some(BookmarkNode::Link(BookmarkLink {
+                id,
+                title,
+                url: node.get("url").and_then(|v| v.as_str())?.to_string(),
+                added: chromium_time(node, "date_added"),
+                sources: Vec::new(),
+            })),
+            _ => None,
+        }
+    }
+
+    let mut tree = BookmarkTree::default();
+    for (key, root) in roots {
+        // Manche Chromium-Versionen legen Nicht-Knoten unter "roots" ab
+        if let Some(BookmarkNode::Folder(mut folder)) = convert(root) {
+            folder.root = Some(match key.as_str() {
+                "bookmark_bar" => RootKind::Toolbar,
+                "synced" => RootKind::Mobile,
+                _ => RootKind::Other,
+            });
+            tree.roots.push(folder);
+        }
+    }
+    Ok(tree)
+}
+
+// Erzeugt eine Bookmarks-Datei im Chrome/Edge-Format. Lesezeichenleiste und
+// Mobile Lesezeichen behalten ihren Platz, alle übrigen Wurzelordner werden
+// unter "Weitere Lesezeichen" zusammengefasst (Firefox' Lesezeichen-Menü
+// bleibt dort als eigener Ordner erhalten).
+pub fn to_chromium(tree: &BookmarkTree) -> Value {
+    fn convert(node: &BookmarkNode, next_id: &mut u64) -> Value {
+        *next_id += 1;
+        match node {
+            BookmarkNode::Folder(folder) => {
+                let id = *next_id;
+                json!({
+                    "children": folder.children.iter().map(|child| convert(child, next_id)).collect::<Vec<_>>(),
+                    "date_added": chromium_timestamp(folder.added),
+                    "date_modified": chromium_timestamp(folder.modified),
+                    "id": id.to_string(),
+                    "name": folder.title,
+                    "type": "folder",
+                })
+            }
+            BookmarkNode::Link(link) => json!({
+                "date_added": chromium_timestamp(link.added),
+                "id": next_id.to_string(),
+                "name": link.title,
+                "type": "url",
+                "url": link.url,
+            }),
+        }
+    }
+
+    let mut bar: Vec<BookmarkNode> = Vec::new();
+    let mut other: Vec<BookmarkNode> = Vec::new();
+    let mut synced: Vec<BookmarkNode> = Vec::new();
+    for root in &tree.roots {
+        match root.root {
+            Some(RootKind::Toolbar) => bar.extend(root.children.iter().cloned()),
+            Some(RootKind::Other) => other.extend(root.children.iter().cloned()),
+            Some(RootKind::Mobile) => synced.extend(root.children.iter().cloned()),
+            Some(RootKind::Menu) | None => {
+                if !root.children.is_empty() {
+                    other.push(BookmarkNode::Folder(BookmarkFolder { root: None, ..root.clone() }));
+                }
+            }
+        }
+    }
+
+    // IDs 1-3 gehören den Wurzelordnern, wie bei Chrome
+    let mut next_id = 3;
+    let mut root_folder = |id: u64, name: &str, children: &[BookmarkNode]| {
+        json!({
+            "children": children.iter().map(|child| convert(child, &mut next_id)).collect::<Vec<_>>(),
+            "date_added": "0",
+            "date_modified": "0",
+            "id": id.to_string(),
+            "name": name,
+            "type": "folder",
+        })
+    };
+    json!({
+        "roots": {
+            "bookmark_bar": root_folder(1, "Lesezeichenleiste", &bar),
+            "other": root_folder(2, "Weitere Lesezeichen", &other),
+            "synced": root_folder(3, "Mobile Lesezeichen", &synced),
+        },
+        "version": 1,
+    })
+}
+
+// Anzeigenamen der Firefox-Wurzelordner; "tags" ist kein echter Ordner
+const FIREFOX_ROOTS: [(&str, &str, RootKind); 4] = [
+    ("toolbar_____", "Lesezeichen-Symbolleiste", RootKind::Toolbar),
+    ("menu________", "Lesezeichen-Menü", RootKind::Menu),
+    ("unfiled_____", "Weitere Lesezeichen", RootKind::Other),
+    ("mobile______", "Mobile Lesezeichen", RootKind::Mobile),
+];
+
+pub fn from_firefox(db_path: &Path) -> Result<BookmarkTree, String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Fehler beim Öffnen der Firefox-Datenbank: {}", e))?;
+
+    struct Row {
+        id: i64,
+        kind: i64,
+        title: String,
+        url: Option<String>,
+        added: Option<i64>,
+        modified: Option<i64>,
+        guid: String,
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT b.id, b.parent, b.type, COALESCE(b.title, ''), p.url, b.dateAdded, b.lastModified, COALESCE(b.guid, '')
+             FROM moz_bookmarks b
+             LEFT JOIN moz_places p ON b.fk = p.id
+             ORDER BY b.parent, b.position",
+        )
+        .map_err(|e| format!("Fehler beim Vorbereiten der SQL-Abfrage: {}", e))?;
+
+    let mut children: HashMap<i64, Vec<Row>> = HashMap::new();
+    let mut roots: HashMap<String, i64> = HashMap::new();
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(1)?,
+                Row {
+                    id: row.get(0)?,
+                    kind: row.get(2)?,
+                    title: row.get(3)?,
+                    url: row.get(4)?,
+                    added: row.get(5)?,
+                    modified: row.get(6)?,
+                    guid: row.get(7)?,
+                },
+            ))
+        })
+        .map_err(|e| format!("Fehler beim Ausführen der SQL-Abfrage: {}", e))?;
+    for row in rows {
+        let (parent, row) = row.map_err(|e| format!("Fehler beim Lesen der Lesezeichen: {}", e))?;
+        roots.insert(row.guid.clone(), row.id);
+        children.entry(parent).or_default().push(row);
+    }
+
+    // Typ 1 = Lesezeichen, 2 = Ordner, 3 = Trennlinie
+    fn build(parent: i64, children: &HashMap<i64, Vec<Row>>) -> Vec<BookmarkNode> {
+        children
+            .get(&parent)
+            .map(|rows| {
+                rows.iter()
+                    .filter_map(|row| match (row.kind, &row.url) {
+                        (1, Some(url)) => Some(BookmarkNode::Link(BookmarkLink {
+                            id: row.guid.clone(),
+                            title: row.title.clone(),
+                            url: url.clone(),
+                            added: firefox_time(row.added),
+                            sources: Vec::new(),
+                        })),
+                        (2, _) => Some(BookmarkNode::Folder(BookmarkFolder {
+                            id: row.guid.clone(),
+                            title: row.title.clone(),
+                            added: firefox_time(row.added),
+                            modified: firefox_time(row.modified),
+                            root: None,
+                            children: build(row.id, children),
+                        })),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    let mut tree = BookmarkTree::default();
+    for (guid, title, kind) in FIREFOX_ROOTS {
+        if let Some(id) = roots.get(guid) {
+            tree.roots.push(BookmarkFolder {
+                id: guid.to_string(),
+                title: title.to_string(),
+                added: None,
+                modified: None,
+                root: Some(kind),
+                children: build(*id, &children),
+            });
+        }
+    }
+    Ok(tree)
+}
+
+fn reversed_host(url: &str) -> String {
+    let host = url
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split(['/', '?', '#']).next())
+        .unwrap_or_default()
+        .to_lowercase();
+    let mut reversed: String = host.chars().rev().collect();
+    reversed.push('.');
+    reversed
+}
+
+// mozilla::HashString aus mfbt/HashFunctions.h, über die Bytes ohne Vorzeichen
+fn hash_string(bytes: &[u8]) -> u32 {
+    const GOLDEN_RATIO: u32 = 0x9E37_79B9;
+    bytes.iter().fold(0, |hash: u32, byte| (hash.rotate_left(5) ^ *byte as u32).wrapping_mul(GOLDEN_RATIO))
+}
+
+// Wie die SQL-Funktion hash() in Firefox: Hash des Schemas in den Bits 32-47,
+// darunter der Hash der ersten 1500 Bytes. Firefox sucht Adressen über
+// url_hash, ohne passenden Wert legt es die Adresse doppelt an.
+fn firefox_url_hash(url: &str) -> i64 {
+    let bytes = url.as_bytes();
+    let hash = hash_string(&bytes[..bytes.len().min(1500)]) as u64;
+    // Das Schema wird nur in den ersten 50 Zeichen gesucht
+    match bytes[..bytes.len().min(50)].iter().position(|b| *b == b':') {
+        Some(scheme_end) => {
+            let prefix = (hash_string(&bytes[..scheme_end]) & 0xFFFF) as u64;
+            ((prefix << 32) + hash) as i64
+        }
+        None => hash as i64,
+    }
+}
+
+// Eintrag in moz_origins zu einer Adresse, z.B. Präfix "https://" und Host
+// "example.org:8080"; Firefox verlangt ihn für jeden Eintrag in moz_places
+fn firefox_origin(tx: &Connection, url: &str) -> rusqlite::Result<Option<i64>> {
+    let parsed = match url::Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return Ok(None),
+    };
+    let prefix = if parsed.has_authority() {
+        format!("{}://", parsed.scheme())
+    } else {
+        format!("{}:", parsed.scheme())
+    };
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{}:{}", host.to_lowercase(), port),
+        (Some(host), None) => host.to_lowercase(),
+        (None, _) => String::new(),
+    };
+    tx.execute(
+        "INSERT OR IGNORE INTO moz_origins (prefix, host, frecency) VALUES (?1, ?2, 0)",
+        rusqlite::params![prefix, host],
+    )?;
+    tx.query_row(
+        "SELECT id FROM moz_origins WHERE prefix = ?1 AND host = ?2",
+        rusqlite::params![prefix, host],
+        |row| row.get(0),
+    )
+    .map(Some)
+}
+
+// Liest Firefox' JSON-Sicherung (bookmarkbackups/*.jsonlz4 nach dem Entpacken
+// oder "Sichern…" in der Bibliothek). Typ 1 = Lesezeichen, 2 = Ordner.
+pub fn from_firefox_json(backup: &Value) -> Result<BookmarkTree, String> {
+    if backup.get("guid").and_then(|v| v.as_str()) != Some("root________") {
+        return Err("Keine Firefox-Lesezeichensicherung".to_string());
+    }
+
+    fn time(node: &Value, key: &str) -> Option<DateTime<Utc>> {
+        firefox_time(node.get(key).and_then(|v| v.as_i64()))
+    }
+
+    fn convert(node: &Value) -> Option<BookmarkNode> {
+        let id = node.get("guid").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let title = node.get("title").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        match node.get("typeCode").and_then(|v| v.as_i64())? {
+            1 => Some(BookmarkNode::Link(BookmarkLink {
+                id,
+                title,
+                url: node.get("uri").and_then(|v| v.as_str())?.to_string(),
+                added: time(node, "dateAdded"),
+                sources: Vec::new(),
+            })),
+            2 => Some(BookmarkNode::Folder(BookmarkFolder {
+                id,
+                title,
+                added: time(node, "dateAdded"),
+                modified: time(node, "lastModified"),
+                root: None,
+                children: children(node),
+            })),
+            _ => None,
+        }
+    }
+
+    fn children(node: &Value) -> Vec<BookmarkNode> {
+        node.get("children")
+            .and_then(|v| v.as_array())
+            .map(|children| children.iter().filter_map(convert).collect())
+            .unwrap_or_default()
+    }
+
+    let roots = backup.get("children").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let mut tree = BookmarkTree::default();
+    for (guid, title, kind) in FIREFOX_ROOTS {
+        if let Some(root) = roots.iter().find(|r| r.get("guid").and_then(|v| v.as_str()) == Some(guid)) {
+            tree.roots.push(BookmarkFolder {
+                id: guid.to_string(),
+                title: title.to_string(),
+                added: None,
+                modified: None,
+                root: Some(kind),
+                children: children(root),
+            });
+        }
+    }
+    Ok(tree)
+}
+
+// Gegenstück zu `from_firefox_json`, lesbar für Firefox' "Wiederherstellen"
+// in der Bibliothek. Wurzelordner werden wie bei `replace_firefox` zugeordnet.
+pub fn to_firefox_json(tree: &BookmarkTree) -> Value {
+    fn micros(date: Option<DateTime<Utc>>) -> i64 {
+        date.map(|d| d.timestamp_micros()).unwrap_or(0)
+    }
+
+    fn convert(node: &BookmarkNode, index: usize, next_id: &mut i64) -> Value {
+        *next_id += 1;
+        match node {
+            BookmarkNode::Folder(folder) => {
+                let id = *next_id;
+                json!({
+                    "guid": format!("bbimp{:07}", id),
+                    "title": folder.title,
+                    "index": index,
+                    "dateAdded": micros(folder.added),
+                    "lastModified": micros(folder.modified.or(folder.added)),
+                    "id": id,
+                    "typeCode": 2,
+                    "type": "text/x-moz-place-container",
+                    "children": folder.children.iter().enumerate()
+                        .map(|(i, child)| convert(child, i, next_id))
+                        .collect::<Vec<_>>(),
+                })
+            }
+            BookmarkNode::Link(link) => json!({
+                "guid": format!("bbimp{:07}", next_id),
+                "title": link.title,
+                "index": index,
+                "dateAdded": micros(link.added),
+                "lastModified": micros(link.added),
+                "id": *next_id,
+                "typeCode": 1,
+                "type": "text/x-moz-place",
+                "uri": link.url,
+            }),
+        }
+    }
+
+    let mut toolbar = Vec::new();
+    let mut menu = Vec::new();
+    let mut unfiled = Vec::new();
+    let mut mobile = Vec::new();
+    for root in &tree.roots {
+        match root.root {
+            Some(RootKind::Toolbar) => toolbar.extend(root.children.iter().cloned()),
+            Some(RootKind::Menu) => menu.extend(root.children.iter().cloned()),
+            Some(RootKind::Other) => unfiled.extend(root.children.iter().cloned()),
+            Some(RootKind::Mobile) => mobile.extend(root.children.iter().cloned()),
+            None => unfiled.push(BookmarkNode::Folder(root.clone())),
+        }
+    }
+
+    // IDs 1-6 gehören den Wurzelordnern, wie in places.sqlite
+    let mut next_id = 6;
+    let mut roots = Vec::new();
+    for (index, (id, guid, name, children)) in [
+        (2, "menu________", "bookmarksMenuFolder", &menu),
+        (3, "toolbar_____", "toolbarFolder", &toolbar),
+        (4, "tags________", "tagsFolder", &Vec::new()),
+        (5, "unfiled_____", "unfiledBookmarksFolder", &unfiled),
+        (6, "mobile______", "mobileFolder", &mobile),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        roots.push(json!({
+            "guid": guid,
+            "title": guid.trim_end_matches('_'),
+            "index": index,
+            "dateAdded": 0,
+            "lastModified": 0,
+            "id": id,
+            "typeCode": 2,
+            "type": "text/x-moz-place-container",
+            "root": name,
+            "children": children.iter().enumerate()
+                .map(|(i, child)| convert(child, i, &mut next_id))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    json!({
+        "guid": "root________",
+        "title": "",
+        "index": 0,
+        "dateAdded": 0,
+        "lastModified": 0,
+        "id": 1,
+        "typeCode": 2,
+        "type": "text/x-moz-place-container",
+        "root": "placesRoot",
+        "children": roots,
+    })
+}
+
+// Für den Abgleich über Browser hinweg: "https://example.org/" == "https://example.org"
+fn url_key(url: &str) -> String {
+    url.trim_end_matches('/').to_lowercase()
+}
+
+// Führt die Bäume mehrerer Browser zusammen. Wurzelordner gleicher Art und
+// gleichnamige Unterordner werden vereinigt; jede URL bleibt nur an ihrer
+// ersten Fundstelle erhalten und merkt sich alle Browser, in denen sie vorkommt.
+pub fn merge(trees: &[(&str, BookmarkTree)]) -> BookmarkTree {
+    fn collect_sources(nodes: &[BookmarkNode], browser: &str, sources: &mut HashMap<String, Vec<String>>) {
+        for node in nodes {
+            match node {
+                BookmarkNode::Folder(folder) => collect_sources(&folder.children, browser, sources),
+                BookmarkNode::Link(link) => {
+                    let entry = sources.entry(url_key(&link.url)).or_default();
+                    if !entry.iter().any(|b| b == browser) {
+                        entry.push(browser.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    fn merge_children(
+        target: &mut Vec<BookmarkNode>,
+        nodes: &[BookmarkNode],
+        sources: &HashMap<String, Vec<String>>,
+        seen: &mut HashSet<String>,
+    ) {
+        for node in nodes {
+            match node {
+                BookmarkNode::Folder(folder) => {
+                    let existing = target.iter_mut().find_map(|n| match n {
+                        BookmarkNode::Folder(f) if f.title == folder.title => Some(f),
+                        _ => None,
+                    });
+                    match existing {
+                        Some(existing) => merge_children(&mut existing.children, &folder.children, sources, seen),
+                        None => {
+                            let mut merged = BookmarkFolder { children: Vec::new(), ..folder.clone() };
+                            merge_children(&mut merged.children, &folder.children, sources, seen);
+                            target.push(BookmarkNode::Folder(merged));
+                        }
+                    }
+                }
+                BookmarkNode::Link(link) => {
+                    let key = url_key(&link.url);
+                    if seen.insert(key.clone()) {
+                        target.push(BookmarkNode::Link(BookmarkLink {
+                            sources: sources.get(&key).cloned().unwrap_or_default(),
+                            ..link.clone()
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut sources = HashMap::new();
+    for (browser, tree) in trees {
+        for root in &tree.roots {
+            collect_sources(&root.children, browser, &mut sources);
+        }
+    }
+
+    let mut merged = BookmarkTree::default();
+    let mut seen = HashSet::new();
+    for (_, tree) in trees {
+        for root in &tree.roots {
+            let index = match merged.roots.iter().position(|r| r.root.is_some() && r.root == root.root) {
+                Some(index) => index,
+                None => {
+                    merged.roots.push(BookmarkFolder { children: Vec::new(), ..root.clone() });
+                    merged.roots.len() - 1
+                }
+            };
+            merge_children(&mut merged.roots[index].children, &root.children, &sources, &mut seen);
+        }
+    }
+    merged
+}
+
+// Ein Ordner für die teilweise Wiederherstellung: Art des Wurzelordners und die
+// Titel der Ordner darunter, z.B. Lesezeichenleiste / "Rezepte" / "Kuchen".
+// Ohne Titel ist der Wurzelordner selbst gemeint; bei gleichnamigen Geschwistern gilt der erste.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FolderPath {
+    pub root: RootKind,
+    pub titles: Vec<String>,
+}
+
+impl FolderPath {
+    fn is_within(&self, other: &FolderPath) -> bool {
+        self.root == other.root && self.titles.len() > other.titles.len() && self.titles.starts_with(&other.titles)
+    }
+}
+
+// Ohne Ordner, die schon mit einem gewählten übergeordneten Ordner kommen, und ohne Doppelte
+fn outermost(folders: &[FolderPath]) -> Vec<&FolderPath> {
+    let mut result: Vec<&FolderPath> = Vec::new();
+    for folder in folders {
+        if !folders.iter().any(|other| folder.is_within(other)) && !result.contains(&folder) {
+            result.push(folder);
+        }
+    }
+    result
+}
+
+impl BookmarkTree {
+    pub fn folder(&self, path: &FolderPath) -> Option<&BookmarkFolder> {
+        let mut folder = self.roots.iter().find(|root| root.root == Some(path.root))?;
+        for title in &path.titles {
+            folder = folder.children.iter().find_map(|node| match node {
+                BookmarkNode::Folder(child) if child.title == *title => Some(child),
+                _ => None,
+            })?;
+        }
+        Some(folder)
+    }
+}
+
+fn chromium_root_key(kind: RootKind) -> &'static str {
+    match kind {
+        RootKind::Toolbar => "bookmark_bar",
+        RootKind::Mobile => "synced",
+        RootKind::Other | RootKind::Menu => "other",
+    }
+}
+
+// Setzt "checksum" so, wie Chrome/Edge ihn beim Laden nachrechnen: MD5 über ID,
+// Titel (UTF-16LE) und "folder" bzw. "url" und URL aller Knoten in Dateireihenfolge,
+// beginnend mit Lesezeichenleiste, weiteren und mobilen Lesezeichen. Passt die
+// Summe nicht, behandelt der Browser die Datei als beschädigt.
+pub fn update_chromium_checksum(bookmarks: &mut Value) {
+    fn walk(node: &Value, md5: &mut Md5) {
+        let text = |key: &str| node.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+        md5.update(text("id").as_bytes());
+        let title: Vec<u8> = text("name").encode_utf16().flat_map(u16::to_le_bytes).collect();
+        md5.update(&title);
+        if text("type") == "url" {
+            md5.update(b"url");
+            md5.update(text("url").as_bytes());
+        } else {
+            md5.update(b"folder");
+            for child in node.get("children").and_then(|v| v.as_array()).into_iter().flatten() {
+                walk(child, md5);
+            }
+        }
+    }
+
+    let mut md5 = Md5::new();
+    for key in ["bookmark_bar", "other", "synced"] {
+        if let Some(root) = bookmarks.get("roots").and_then(|roots| roots.get(key)) {
+            walk(root, &mut md5);
+        }
+    }
+    if let Some(object) = bookmarks.as_object_mut() {
+        object.insert("checksum".to_string(), Value::String(md5.hex()));
+    }
+}
+
+fn is_chromium_folder(node: &Value, title: &str) -> bool {
+    node.get("type").and_then(|v| v.as_str()) == Some("folder")
+        && node.get("name").and_then(|v| v.as_str()) == Some(title)
+}
+
+// Höchste ID und alle GUIDs einer Bookmarks-Datei
+fn chromium_ids(bookmarks: &Value) -> (u64, HashSet<String>) {
+    fn walk(node: &Value, max_id: &mut u64, guids: &mut HashSet<String>) {
+        if let Some(id) = node.get("id").and_then(|v| v.as_str()).and_then(|id| id.parse().ok()) {
+            *max_id = (*max_id).max(id);
+        }
+        if let Some(guid) = node.get("guid").and_then(|v| v.as_str()) {
+            guids.insert(guid.to_string());
+        }
+        for child in node.get("children").and_then(|v| v.as_array()).into_iter().flatten() {
+            walk(child, max_id, guids);
+        }
+    }
+
+    let mut max_id = 0;
+    let mut guids = HashSet::new();
+    for root in bookmarks.get("roots").and_then(|v| v.as_object()).into_iter().flat_map(|roots| roots.values()) {
+        walk(root, &mut max_id, &mut guids);
+    }
+    (max_id, guids)
+}
+
+// Ordner in der aktuellen Datei; fehlende Ordner auf dem Weg dorthin werden angelegt
+fn chromium_folder_mut<'a>(bookmarks: &'a mut Value, path: &FolderPath, next_id: &mut u64) -> Result<&'a mut Value, String> {
+    let mut folder = bookmarks
+        .get_mut("roots")
+        .and_then(|roots| roots.get_mut(chromium_root_key(path.root)))
+        .ok_or("Ungültige Favoriten-Datei: Wurzelordner fehlt")?;
+    for title in &path.titles {
+        let children = folder
+            .get_mut("children")
+            .and_then(|v| v.as_array_mut())
+            .ok_or("Ungültige Favoriten-Datei: Ordner ohne \"children\"")?;
+        let index = match children.iter().position(|child| is_chromium_folder(child, title)) {
+            Some(index) => index,
+            None => {
+                children.push(json!({
+                    "children": [],
+                    "date_added": chromium_timestamp(Some(Utc::now())),
+                    "date_modified": "0",
+                    "id": next_id.to_string(),
+                    "name": title,
+                    "type": "folder",
+                }));
+                *next_id += 1;
+                children.len() - 1
+            }
+        };
+        folder = &mut children[index];
+    }
+    Ok(folder)
+}
+
+// Setzt die gewählten Ordner einer Chrome/Edge-Bookmarks-Datei auf ihren Stand
+// im Backup zurück; alles andere bleibt unverändert. Übernommene Einträge
+// bekommen neue IDs, GUIDs nur, wenn die alte anderswo noch vorkommt.
+// Gibt die Zahl der wiederhergestellten Ordner zurück.
+pub fn restore_chromium_folders(current: &mut Value, backup: &Value, folders: &[FolderPath]) -> Result<usize, String> {
+    fn renumber(node: &mut Value, next_id: &mut u64, guids: &HashSet<String>) {
+        node["id"] = Value::String(next_id.to_string());
+        *next_id += 1;
+        let taken = node.get("guid").and_then(|v| v.as_str()).is_some_and(|guid| guids.contains(guid));
+        if let (true, Some(object)) = (taken, node.as_object_mut()) {
+            object.remove("guid");
+        }
+        if let Some(children) = node.get_mut("children").and_then(|v| v.as_array_mut()) {
+            for child in children {
+                renumber(child, next_id, guids);
+            }
+        }
+    }
+
+    let folders = outermost(folders);
+    let mut next_id = chromium_ids(current).0 + 1;
+    for folder in &folders {
+        let mut source = backup
+            .get("roots")
+            .and_then(|roots| roots.get(chromium_root_key(folder.root)))
+            .ok_or("Ungültiges Backup: Wurzelordner fehlt")?;
+        for title in &folder.titles {
+            source = source
+                .get("children")
+                .and_then(|v| v.as_array())
+                .and_then(|children| children.iter().find(|child| is_chromium_folder(child, title)))
+                .ok_or_else(|| format!("Ordner \"{}\" nicht im Backup gefunden", folder.titles.join(" / ")))?;
+        }
+        let mut children = source.get("children").cloned().unwrap_or_else(|| json!([]));
+
+        // Erst den alten Inhalt entfernen, damit seine GUIDs wieder frei sind
+        chromium_folder_mut(current, folder, &mut next_id)?["children"] = json!([]);
+        let (_, guids) = chromium_ids(current);
+        for child in children.as_array_mut().into_iter().flatten() {
+            renumber(child, &mut next_id, &guids);
+        }
+        chromium_folder_mut(current, folder, &mut next_id)?["children"] = children;
+    }
+
+    // Die Prüfsumme passt nicht mehr zum Inhalt
+    if let Some(object) = current.as_object_mut() {
+        object.remove("checksum");
+    }
+    Ok(folders.len())
+}
+
+// Neuer Eintrag am Ende von `parent`; Firefox-GUIDs sind 12 Zeichen lang
+fn firefox_insert(tx: &Connection, next_id: &mut i64, parent: i64, kind: i64, fk: Option<i64>, title: &str, added: i64) -> rusqlite::Result<i64> {
+    *next_id += 1;
+    let id = *next_id;
+    let position: i64 = tx.query_row(
+        "SELECT COALESCE(MAX(position) + 1, 0) FROM moz_bookmarks WHERE parent = ?1",
+        [parent],
+        |row| row.get(0),
+    )?;
+    tx.execute(
+        "INSERT INTO moz_bookmarks (id, type, fk, parent, position, title, dateAdded, lastModified, guid)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?8)",
+        rusqlite::params![id, kind, fk, parent, position, title, added, format!("bbres{:07}", id)],
+    )?;
+    Ok(id)
+}
+
+fn firefox_place(tx: &Connection, link: &BookmarkLink) -> rusqlite::Result<i64> {
+    let url_hash = firefox_url_hash(&link.url);
+    let existing = tx
+        .query_row(
+            "SELECT id FROM moz_places WHERE url_hash = ?1 AND url = ?2",
+            rusqlite::params![url_hash, link.url],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let id = match existing {
+        Some(id) => id,
+        None => {
+            let origin_id = firefox_origin(tx, &link.url)?;
+            tx.execute(
+                "INSERT INTO moz_places (url, title, rev_host, url_hash, origin_id) VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![link.url, link.title, reversed_host(&link.url), url_hash, origin_id],
+            )?;
+            let id = tx.last_insert_rowid();
+            tx.execute("UPDATE moz_places SET guid = ?1 WHERE id = ?2", rusqlite::params![format!("bbplc{:07}", id), id])?;
+            id
+        }
+    };
+    tx.execute("UPDATE moz_places SET foreign_count = foreign_count + 1 WHERE id = ?1", [id])?;
+    Ok(id)
+}
+
+fn firefox_insert_link(tx: &Connection, next_id: &mut i64, parent: i64, link: &BookmarkLink, now: i64) -> rusqlite::Result<i64> {
+    let fk = firefox_place(tx, link)?;
+    let added = link.added.map(|d| d.timestamp_micros()).unwrap_or(now);
+    firefox_insert(tx, next_id, parent, 1, Some(fk), &link.title, added)
+}
+
+fn firefox_insert_children(tx: &Connection, next_id: &mut i64, parent: i64, nodes: &[BookmarkNode], now: i64) -> rusqlite::Result<()> {
+    for node in nodes {
+        match node {
+            BookmarkNode::Folder(folder) => {
+                let added = folder.added.map(|d| d.timestamp_micros()).unwrap_or(now);
+                let id = firefox_insert(tx, next_id, parent, 2, None, &folder.title, added)?;
+                firefox_insert_children(tx, next_id, id, &folder.children, now)?;
+            }
+            BookmarkNode::Link(link) => {
+                firefox_insert_link(tx, next_id, parent, link, now)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// ID des Ordners in moz_bookmarks; fehlende Ordner auf dem Weg dorthin werden angelegt
+fn firefox_folder(tx: &Connection, next_id: &mut i64, path: &FolderPath, now: i64) -> Result<i64, String> {
+    let sql_error = |e: rusqlite::Error| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e);
+    let root_guid = match path.root {
+        RootKind::Toolbar => "toolbar_____",
+        RootKind::Menu => "menu________",
+        RootKind::Other => "unfiled_____",
+        RootKind::Mobile => "mobile______",
+    };
+    let mut parent: i64 = tx
+        .query_row("SELECT id FROM moz_bookmarks WHERE guid = ?1", [root_guid], |row| row.get(0))
+        .map_err(|_| "Ungültige Firefox-Datenbank: Wurzelordner fehlt".to_string())?;
+    for title in &path.titles {
+        let existing = tx
+            .query_row(
+                "SELECT id FROM moz_bookmarks WHERE parent = ?1 AND type = 2 AND COALESCE(title, '') = ?2 ORDER BY position LIMIT 1",
+                rusqlite::params![parent, title],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sql_error)?;
+        parent = match existing {
+            Some(id) => id,
+            None => firefox_insert(tx, next_id, parent, 2, None, title, now).map_err(sql_error)?,
+        };
+    }
+    Ok(parent)
+}
+
+// Entfernt den Inhalt samt Unterordnern, der Ordner selbst bleibt
+fn firefox_clear_folder(tx: &Connection, parent: i64) -> rusqlite::Result<()> {
+    let removed: Vec<(i64, Option<i64>)> = tx
+        .prepare(
+            "WITH RECURSIVE sub(id) AS (
+                 SELECT id FROM moz_bookmarks WHERE parent = ?1
+                 UNION ALL SELECT b.id FROM moz_bookmarks b JOIN sub ON b.parent = sub.id)
+             SELECT id, fk FROM moz_bookmarks WHERE id IN sub",
+        )?
+        .query_map([parent], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (id, fk) in removed {
+        tx.execute("DELETE FROM moz_bookmarks WHERE id = ?1", [id])?;
+        if let Some(fk) = fk {
+            tx.execute("UPDATE moz_places SET foreign_count = foreign_count - 1 WHERE id = ?1", [fk])?;
+        }
+    }
+    Ok(())
+}
+
+// Öffnet eine places.sqlite, führt `change` in einer Transaktion aus und speichert nur bei Erfolg
+fn change_firefox<F>(db_path: &Path, change: F) -> Result<usize, String>
+where
+    F: FnOnce(&Connection, &mut i64, i64) -> Result<usize, String>,
+{
+    let mut conn = Connection::open(db_path)
+        .map_err(|e| format!("Fehler beim Öffnen der Firefox-Datenbank: {}", e))?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e))?;
+    let sql_error = |e: rusqlite::Error| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e);
+
+    let now = Utc::now().timestamp_micros();
+    let mut next_id: i64 = tx
+        .query_row("SELECT COALESCE(MAX(id), 0) FROM moz_bookmarks", [], |row| row.get(0))
+        .map_err(sql_error)?;
+    let changed = change(&tx, &mut next_id, now)?;
+    tx.commit().map_err(sql_error)?;
+    Ok(changed)
+}
+
+// Wie restore_chromium_folders, aber direkt in einer places.sqlite; Verlauf und
+// alle übrigen Lesezeichen bleiben erhalten. Gedacht für eine Kopie der aktuellen
+// Datenbank, die anschließend wie ein Backup zurückgespielt wird.
+pub fn restore_firefox_folders(db_path: &Path, backup: &BookmarkTree, folders: &[FolderPath]) -> Result<usize, String> {
+    let sql_error = |e: rusqlite::Error| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e);
+    let folders = outermost(folders);
+    change_firefox(db_path, |tx, next_id, now| {
+        for folder in &folders {
+            let source = backup
+                .folder(folder)
+                .ok_or_else(|| format!("Ordner \"{}\" nicht im Backup gefunden", folder.titles.join(" / ")))?;
+            let parent = firefox_folder(tx, next_id, folder, now)?;
+
+            firefox_clear_folder(tx, parent).map_err(sql_error)?;
+            firefox_insert_children(tx, next_id, parent, &source.children, now).map_err(sql_error)?;
+        }
+        Ok(folders.len())
+    })
+}
+
+// Ersetzt alle Lesezeichen einer places.sqlite durch `tree`; Verlauf, Schlagwörter
+// und Einstellungen bleiben erhalten. Wurzelordner ohne Firefox-Gegenstück kommen
+// unter "Weitere Lesezeichen". Gedacht für eine Kopie der aktuellen Datenbank.
+// Gibt die Zahl der Lesezeichen zurück.
+pub fn replace_firefox(db_path: &Path, tree: &BookmarkTree) -> Result<usize, String> {
+    let sql_error = |e: rusqlite::Error| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e);
+    change_firefox(db_path, |tx, next_id, now| {
+        for kind in [RootKind::Menu, RootKind::Toolbar, RootKind::Other, RootKind::Mobile] {
+            let root = firefox_folder(tx, next_id, &FolderPath { root: kind, titles: Vec::new() }, now)?;
+            firefox_clear_folder(tx, root).map_err(sql_error)?;
+        }
+        for root in &tree.roots {
+            let (kind, nodes) = match root.root {
+                Some(kind) => (kind, root.children.clone()),
+                None => (RootKind::Other, vec![BookmarkNode::Folder(root.clone())]),
+            };
+            let parent = firefox_folder(tx, next_id, &FolderPath { root: kind, titles: Vec::new() }, now)?;
+            firefox_insert_children(tx, next_id, parent, &nodes, now).map_err(sql_error)?;
+        }
+        Ok(tree.counts().1)
+    })
+}
+
+// Lesezeichen eines Baums mit dem Pfad ihres Ordners, in Baumreihenfolge
+fn links_with_paths(tree: &BookmarkTree) -> Vec<(FolderPath, &BookmarkLink)> {
+    fn walk<'a>(nodes: &'a [BookmarkNode], path: &FolderPath, links: &mut Vec<(FolderPath, &'a BookmarkLink)>) {
+        for node in nodes {
+            match node {
+                BookmarkNode::Folder(folder) => {
+                    let mut child = path.clone();
+                    child.titles.push(folder.title.clone());
+                    walk(&folder.children, &child, links);
+                }
+                BookmarkNode::Link(link) => links.push((path.clone(), link)),
+            }
+        }
+    }
+
+    let mut links = Vec::new();
+    for root in &tree.roots {
+        // Wurzelordner ohne Gegenstück landen wie bei replace_firefox unter "Weitere Lesezeichen"
+        let path = match root.root {
+            Some(kind) => FolderPath { root: kind, titles: Vec::new() },
+            None => FolderPath { root: RootKind::Other, titles: vec![root.title.clone()] },
+        };
+        walk(&root.children, &path, &mut links);
+    }
+    links
+}
+
+// (hinzukommende, wegfallende) URLs, wenn `backup` an die Stelle von `current` tritt
+pub fn url_changes(current: &BookmarkTree, backup: &BookmarkTree) -> (usize, usize) {
+    let urls = |tree| -> HashSet<String> {
+        links_with_paths(tree).into_iter().map(|(_, link)| url_key(&link.url)).collect()
+    };
+    let (current, backup) = (urls(current), urls(backup));
+    (backup.difference(&current).count(), current.difference(&backup).count())
+}
+
+// Baum mit nur dem ersten Lesezeichen mit dieser URL, in denselben Ordnern wie im
+// Original; zum Einfügen eines einzelnen Lesezeichens mit merge_chromium bzw. merge_firefox
+pub fn single_link(tree: &BookmarkTree, url: &str) -> Option<BookmarkTree> {
+    let (path, link) = links_with_paths(tree).into_iter().find(|(_, link)| link.url == url)?;
+    let mut node = BookmarkNode::Link(link.clone());
+    for title in path.titles.iter().rev() {
+        node = BookmarkNode::Folder(BookmarkFolder {
+            id: String::new(),
+            title: title.clone(),
+            added: None,
+            modified: None,
+            root: None,
+            children: vec![node],
+        });
+    }
+    let root = tree.roots.iter().find(|root| root.root == Some(path.root));
+    Some(BookmarkTree {
+        roots: vec![BookmarkFolder {
+            id: String::new(),
+            title: root.map(|root| root.title.clone()).unwrap_or_default(),
+            added: None,
+            modified: None,
+            root: Some(path.root),
+            children: vec![node],
+        }],
+    })
+}
+
+// Ergänzt eine Chrome/Edge-Bookmarks-Datei um alle Lesezeichen des Backups, deren
+// URL dort noch nicht vorkommt, jeweils im Ordner mit demselben Pfad (fehlende
+// Ordner werden angelegt). Vorhandenes bleibt unverändert. Gibt die Zahl der
+// hinzugefügten Lesezeichen zurück.
+pub fn merge_chromium(current: &mut Value, backup: &Value) -> Result<usize, String> {
+    let backup = from_chromium(backup)?;
+    let mut known: HashSet<String> = links_with_paths(&from_chromium(current)?)
+        .into_iter()
+        .map(|(_, link)| url_key(&link.url))
+        .collect();
+
+    let mut next_id = chromium_ids(current).0 + 1;
+    let mut added = 0;
+    for (path, link) in links_with_paths(&backup) {
+        if !known.insert(url_key(&link.url)) {
+            continue;
+        }
+        let folder = chromium_folder_mut(current, &path, &mut next_id)?;
+        let children = folder
+            .get_mut("children")
+            .and_then(|v| v.as_array_mut())
+            .ok_or("Ungültige Favoriten-Datei: Ordner ohne \"children\"")?;
+        children.push(json!({
+            "date_added": chromium_timestamp(link.added.or_else(|| Some(Utc::now()))),
+            "id": next_id.to_string(),
+            "name": link.title,
+            "type": "url",
+            "url": link.url,
+        }));
+        next_id += 1;
+        added += 1;
+    }
+
+    if added > 0 {
+        if let Some(object) = current.as_object_mut() {
+            object.remove("checksum");
+        }
+    }
+    Ok(added)
+}
+
+// Wie merge_chromium, aber direkt in einer places.sqlite
+pub fn merge_firefox(db_path: &Path, backup: &BookmarkTree) -> Result<usize, String> {
+    let sql_error = |e: rusqlite::Error| format!("Fehler beim Schreiben der Firefox-Datenbank: {}", e);
+    let mut known: HashSet<String> = links_with_paths(&from_firefox(db_path)?)
+        .into_iter()
+        .map(|(_, link)| url_key(&link.url))
+        .collect();
+
+    change_firefox(db_path, |tx, next_id, now| {
+        let mut added = 0;
+        for (path, link) in links_with_paths(backup) {
+            if !known.insert(url_key(&link.url)) {
+                continue;
+            }
+            let parent = firefox_folder(tx, next_id, &path, now)?;
+            firefox_insert_link(tx, next_id, parent, link, now).map_err(sql_error)?;
+            added += 1;
+        }
+        Ok(added)
+    })
+}
+
+// Filter für Exporte; ohne gesetzte Felder bleibt der Baum unverändert
+#[derive(Clone, Debug, Default)]
+pub struct BookmarkFilter {
+    // Ordnertitel (ohne Groß-/Kleinschreibung), deren Inhalt ausgelassen wird
+    pub excluded_folders: Vec<String>,
+    // Wenn gesetzt, nur Lesezeichen aus diesen Ordnern (samt Unterordnern)
+    pub included_folders: Vec<String>,
+    pub added_after: Option<DateTime<Utc>>,
+    pub added_before: Option<DateTime<Utc>>,
+}
+
+impl BookmarkFilter {
+    pub fn is_empty(&self) -> bool {
+        self.excluded_folders.is_empty()
+            && self.included_folders.is_empty()
+            && self.added_after.is_none()
+            && self.added_before.is_none()
+    }
+
+    fn matches_folder(list: &[String], title: &str) -> bool {
+        let title = title.to_lowercase();
+        list.iter().any(|f| f.trim().to_lowercase() == title)
+    }
+
+    // Lesezeichen ohne Datum fallen bei einem Datumsfilter heraus
+    fn matches_link(&self, link: &BookmarkLink) -> bool {
+        if self.added_after.is_none() && self.added_before.is_none() {
+            return true;
+        }
+        match link.added {
+            Some(added) => {
+                self.added_after.is_none_or(|after| added >= after)
+                    && self.added_before.is_none_or(|before| added < before)
+            }
+            None => false,
+        }
+    }
+
+    fn filter_folder(&self, folder: &BookmarkFolder, included: bool) -> Option<BookmarkFolder> {
+        if Self::matches_folder(&self.excluded_folders, &folder.title) {
+            return None;
+        }
+        let included = included || Self::matches_folder(&self.included_folders, &folder.title);
+
+        let children: Vec<BookmarkNode> = folder
+            .children
+            .iter()
+            .filter_map(|node| match node {
+                BookmarkNode::Folder(child) => self.filter_folder(child, included).map(BookmarkNode::Folder),
+                BookmarkNode::Link(link) if included && self.matches_link(link) => Some(BookmarkNode::Link(link.clone())),
+                BookmarkNode::Link(_) => None,
+            })
+            .collect();
+
+        // Durch den Filter geleerte Ordner weglassen
+        if children.is_empty() && !folder.children.is_empty() {
+            return None;
+        }
+        Some(BookmarkFolder { children, ..folder.clone() })
+    }
+
+    pub fn apply(&self, tree: &BookmarkTree) -> BookmarkTree {
+        if self.is_empty() {
+            return tree.clone();
+        }
+        let included = self.included_folders.is_empty();
+        BookmarkTree {
+            // Nicht ausgeschlossene Wurzelordner bleiben auch leer erhalten, damit der Import sie zuordnen kann
+            roots: tree
+                .roots
+                .iter()
+                .filter(|root| !Self::matches_folder(&self.excluded_folders, &root.title))
+                .map(|root| {
+                    let filtered = self.filter_folder(root, included);
+                    filtered.unwrap_or_else(|| BookmarkFolder { children: Vec::new(), ..root.clone() })
+                })
+                .collect(),
+        }
+    }
+}
+
+// Anordnung für Exporte, z.B. für Linklisten zum Weitergeben
+#[derive(Clone, Debug, Default)]
+pub struct BookmarkLayout {
+    pub sort_alphabetically: bool,
+    pub folders_first: bool,
+    // Alle Lesezeichen in einer einzigen Liste ohne Ordner
+    pub flatten: bool,
+}
+
+impl BookmarkLayout {
+    fn arrange(&self, nodes: &mut [BookmarkNode]) {
+        fn title(node: &BookmarkNode) -> String {
+            match node {
+                BookmarkNode::Folder(folder) => folder.title.to_lowercase(),
+                BookmarkNode::Link(link) => link.title.to_lowercase(),
+            }
+        }
+
+        // Stabil sortieren, damit gleichrangige Einträge ihre Reihenfolge behalten
+        if self.sort_alphabetically {
+            nodes.sort_by_key(title);
+        }
+        if self.folders_first {
+            nodes.sort_by_key(|node| matches!(node, BookmarkNode::Link(_)));
+        }
+        for node in nodes {
+            if let BookmarkNode::Folder(folder) = node {
+                self.arrange(&mut folder.children);
+            }
+        }
+    }
+
+    pub fn apply(&self, tree: &BookmarkTree) -> BookmarkTree {
+        fn collect_links(nodes: &[BookmarkNode], out: &mut Vec<BookmarkNode>) {
+            for node in nodes {
+                match node {
+                    BookmarkNode::Folder(folder) => collect_links(&folder.children, out),
+                    BookmarkNode::Link(_) => out.push(node.clone()),
+                }
+            }
+        }
+
+        let mut tree = if self.flatten {
+            let mut links = Vec::new();
+            for root in &tree.roots {
+                collect_links(&root.children, &mut links);
+            }
+            BookmarkTree {
+                roots: vec![BookmarkFolder {
+                    id: String::new(),
+                    title: "Lesezeichen".to_string(),
+                    added: None,
+                    modified: None,
+                    root: Some(RootKind::Other),
+                    children: links,
+                }],
+            }
+        } else {
+            tree.clone()
+        };
+
+        for root in &mut tree.roots {
+            self.arrange(&mut root.children);
+        }
+        tree
+    }
+}
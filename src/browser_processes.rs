@@ -0,0 +1,111 @@
+// browser_processes.rs - Ermittelt, welche Browser gerade laufen
+use std::collections::HashSet;
+
+const BROWSER_EXECUTABLES: [(&str, &str); 3] = [
+    ("Chrome", "chrome.exe"),
+    ("Edge", "msedge.exe"),
+    ("Firefox", "firefox.exe"),
+];
+
+// Namen der Browser ("Chrome", "Edge", "Firefox"), von denen mindestens ein Prozess läuft
+pub fn running_browsers() -> HashSet<&'static str> {
+    let processes = process_names();
+    BROWSER_EXECUTABLES
+        .iter()
+        .filter(|(_, exe)| processes.contains(*exe))
+        .map(|(browser, _)| *browser)
+        .collect()
+}
+
+pub fn is_running(browser: &str) -> bool {
+    running_browsers().contains(browser)
+}
+
+fn process_names() -> HashSet<String> {
+    processes().into_iter().map(|(name, _)| name).collect()
+}
+
+// Bittet den Browser, sich zu beenden, wie ein Klick auf das X jedes seiner Fenster.
+// Wartet nicht; ob er wirklich beendet ist, zeigt is_running(). Gibt die Zahl der
+// angeschriebenen Fenster zurück.
+#[cfg(target_os = "windows")]
+pub fn request_close(browser: &str) -> Result<usize, String> {
+    use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+    use winapi::shared::windef::HWND;
+    use winapi::um::winuser::{EnumWindows, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, WM_CLOSE};
+
+    struct Search {
+        pids: HashSet<u32>,
+        closed: usize,
+    }
+
+    unsafe extern "system" fn close_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        let mut pid = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        if search.pids.contains(&pid) && IsWindowVisible(hwnd) != 0 {
+            PostMessageW(hwnd, WM_CLOSE, 0, 0);
+            search.closed += 1;
+        }
+        TRUE
+    }
+
+    let exe = BROWSER_EXECUTABLES
+        .iter()
+        .find(|(name, _)| *name == browser)
+        .map(|(_, exe)| *exe)
+        .ok_or_else(|| format!("Unbekannter Browser: {}", browser))?;
+    let pids = processes()
+        .into_iter()
+        .filter(|(name, _)| name == exe)
+        .map(|(_, pid)| pid)
+        .collect();
+
+    let mut search = Search { pids, closed: 0 };
+    unsafe { EnumWindows(Some(close_window), &mut search as *mut Search as LPARAM) };
+    Ok(search.closed)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn request_close(_browser: &str) -> Result<usize, String> {
+    // Not implemented for non-Windows platforms
+    Ok(0)
+}
+
+// (Name der .exe in Kleinbuchstaben, Prozess-ID) aller laufenden Prozesse
+#[cfg(target_os = "windows")]
+fn processes() -> Vec<(String, u32)> {
+    use std::mem;
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS};
+
+    let mut processes = Vec::new();
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return processes;
+        }
+
+        let mut entry: PROCESSENTRY32W = mem::zeroed();
+        entry.dwSize = mem::size_of::<PROCESSENTRY32W>() as u32;
+
+        let mut ok = Process32FirstW(snapshot, &mut entry);
+        while ok != 0 {
+            let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+            let name = String::from_utf16_lossy(&entry.szExeFile[..len]).to_lowercase();
+            processes.push((name, entry.th32ProcessID));
+            ok = Process32NextW(snapshot, &mut entry);
+        }
+
+        CloseHandle(snapshot);
+    }
+
+    processes
+}
+
+#[cfg(not(target_os = "windows"))]
+fn processes() -> Vec<(String, u32)> {
+    // Not implemented for non-Windows platforms
+    Vec::new()
+}
@@ -0,0 +1,26 @@
+// cancel.rs - Abbrechen langer Vorgänge wie Backup oder Archiv-Export
+// Der Vorgang fragt das Token an geeigneten Stellen ab und räumt selbst auf.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    // Für Schleifen mit `?`
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("Abgebrochen".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// catalog.rs - Verzeichnis aller Backups im Backup-Ordner (catalog.json)
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CATALOG_FILE: &str = "catalog.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatalogEntry {
+    pub browser: String,
+    pub file_name: String,
+    pub created: DateTime<Local>,
+    pub size: u64,
+    // Favoriten-Datei, aus der das Backup stammt, mit Benutzer- und Profilordner
+    // des sichernden Rechners; fehlt bei älteren Einträgen und Importen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    // Beschriftung für Backups, die nicht aus einem normalen Lauf stammen,
+    // z.B. "Vor Wiederherstellung 16.10.2026 14:30"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Catalog {
+    pub entries: Vec<CatalogEntry>,
+    // Zeitpunkt des letzten erfolgreichen Backups pro Browser (bzw. pro
+    // Browser und Rechner, siehe success_key)
+    pub last_success: BTreeMap<String, DateTime<Local>>,
+    // Beim Laden eingearbeitete Konfliktkopien, werden beim Speichern entfernt
+    #[serde(skip)]
+    merged_copies: Vec<PathBuf>,
+}
+
+impl Catalog {
+    // Lädt catalog.json und führt Konfliktkopien von Sync-Programmen ein,
+    // wenn mehrere Rechner denselben Backup-Ordner beschreiben
+    pub fn load(backup_dir: &Path) -> Self {
+        let mut catalog = Self::read(&backup_dir.join(CATALOG_FILE)).unwrap_or_default();
+        for copy in conflict_copies(backup_dir) {
+            if let Some(other) = Self::read(&copy) {
+                log::info!("Konfliktkopie des Katalogs zusammengeführt: {}", copy.display());
+                catalog.merge(other);
+                catalog.merged_copies.push(copy);
+            }
+        }
+        catalog
+    }
+
+    fn read(path: &Path) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    }
+
+    pub fn save(&self, backup_dir: &Path) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(backup_dir.join(CATALOG_FILE), content)?;
+        for copy in &self.merged_copies {
+            fs::remove_file(copy).ok();
+        }
+        Ok(())
+    }
+
+    // Einträge vereinigen, pro Schlüssel gilt das jüngste erfolgreiche Backup
+    pub fn merge(&mut self, other: Catalog) {
+        for entry in other.entries {
+            if !self.contains(&entry.browser, &entry.file_name) {
+                self.entries.push(entry);
+            }
+        }
+        for (key, created) in other.last_success {
+            self.mark_success(key, created);
+        }
+    }
+
+    pub fn contains(&self, browser: &str, file_name: &str) -> bool {
+        self.entries.iter().any(|e| e.browser == browser && e.file_name == file_name)
+    }
+
+    // Anzahl und Gesamtgröße der Backups eines Browsers
+    pub fn usage(&self, browser: &str) -> (usize, u64) {
+        self.entries.iter()
+            .filter(|e| e.browser == browser)
+            .fold((0, 0), |(count, size), e| (count + 1, size + e.size))
+    }
+
+    pub fn record_backup(&mut self, browser: &str, backup_path: &Path, created: DateTime<Local>) {
+        self.add_entry(browser, backup_path, created);
+        let machine = backup_path.file_name()
+            .and_then(|name| machine_from_file_name(&name.to_string_lossy()).map(str::to_string));
+        self.mark_success(success_key(browser, machine.as_deref()), created);
+    }
+
+    fn mark_success(&mut self, key: String, created: DateTime<Local>) {
+        let latest = self.last_success.get(&key).map_or(created, |last| created.max(*last));
+        self.last_success.insert(key, latest);
+    }
+
+    // Eintrag ohne "letztes erfolgreiches Backup" zu verändern, z.B. für Importe
+    pub fn add_entry(&mut self, browser: &str, backup_path: &Path, created: DateTime<Local>) {
+        let file_name = match backup_path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => return,
+        };
+        let size = fs::metadata(backup_path).map(|m| m.len()).unwrap_or(0);
+        // Beim Neuaufbau des Katalogs lassen sich Herkunft und Beschriftung nicht mehr ermitteln
+        let (source, label) = self.entry(browser, &file_name)
+            .map(|e| (e.source.clone(), e.label.clone()))
+            .unwrap_or_default();
+
+        self.remove(browser, &file_name);
+        self.entries.push(CatalogEntry {
+            browser: browser.to_string(),
+            file_name,
+            created,
+            size,
+            source,
+            label,
+        });
+    }
+
+    pub fn set_source(&mut self, browser: &str, file_name: &str, source: &Path) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.browser == browser && e.file_name == file_name) {
+            entry.source = Some(source.to_path_buf());
+        }
+    }
+
+    pub fn set_label(&mut self, browser: &str, file_name: &str, label: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.browser == browser && e.file_name == file_name) {
+            entry.label = Some(label.to_string());
+        }
+    }
+
+    pub fn entry(&self, browser: &str, file_name: &str) -> Option<&CatalogEntry> {
+        self.entries.iter().find(|e| e.browser == browser && e.file_name == file_name)
+    }
+
+    pub fn remove(&mut self, browser: &str, file_name: &str) {
+        self.entries.retain(|e| !(e.browser == browser && e.file_name == file_name));
+    }
+}
+
+// Backups heißen bookmarks_<JJJJMMTT_HHMMSS>.<ext>; daraus lässt sich der
+// Erstellungszeitpunkt unabhängig vom Änderungsdatum der Datei ablesen
+pub fn timestamp_from_file_name(file_name: &str) -> Option<DateTime<Local>> {
+    let timestamp = file_name.strip_prefix("bookmarks_")?.get(..15)?;
+    let naive = NaiveDateTime::parse_from_str(timestamp, "%Y%m%d_%H%M%S").ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+// Backups mit Rechnername heißen bookmarks_<JJJJMMTT_HHMMSS>_<Rechner>.<ext>
+pub fn machine_from_file_name(file_name: &str) -> Option<&str> {
+    let rest = file_name.strip_prefix("bookmarks_")?.get(15..)?.strip_prefix('_')?;
+    let machine = &rest[..rest.find('.')?];
+    Some(machine).filter(|m| !m.is_empty())
+}
+
+// Schlüssel für last_success: "Chrome", bei Backups mit Rechnername "Chrome@LAPTOP"
+pub fn success_key(browser: &str, machine: Option<&str>) -> String {
+    match machine {
+        Some(machine) => format!("{}@{}", browser, machine),
+        None => browser.to_string(),
+    }
+}
+
+// OneDrive legt z.B. "catalog-LAPTOP.json" an, Dropbox "catalog (... conflicted copy).json",
+// Syncthing "catalog.sync-conflict-....json"
+fn conflict_copies(backup_dir: &Path) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(backup_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            name != CATALOG_FILE && name.starts_with("catalog") && name.ends_with(".json")
+        })
+        .collect()
+}
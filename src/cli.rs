@@ -0,0 +1,379 @@
+// cli.rs - Kommandozeile ohne GUI und Tray, z.B. für Skripte oder RDP/SSH
+use chrono::Local;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backup_manager::BackupManager;
+use crate::bookmarks::{BookmarkFilter, BookmarkLayout};
+use crate::cancel::CancellationToken;
+use crate::catalog;
+use crate::export::ExportFormat;
+use crate::history::RunTrigger;
+use crate::ipc::{self, InstanceStatus, Request};
+
+#[derive(Parser)]
+#[command(name = "bbb", version, about = "Browser Favoriten Backup", arg_required_else_help = true)]
+pub struct Cli {
+    /// Einmaliges Backup wie `bbb backup`, z.B. für die Aufgabenplanung
+    #[arg(long)]
+    backup_now: bool,
+    /// Nur anzeigen, was backup, cleanup und restore tun würden
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Ausgabeformat von `backup` und `list`
+    #[arg(long, value_enum, global = true, default_value = "text")]
+    output: Output,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Backup aller aktivierten Browser erstellen, bei laufender Instanz durch diese
+    Backup,
+    /// Zustand der laufenden Instanz anzeigen
+    Status,
+    /// Fenster der laufenden Instanz anzeigen
+    Show {
+        /// Gleich die Wiederherstellung öffnen
+        #[arg(long)]
+        restore: bool,
+    },
+    /// Backup-Ordner im Explorer öffnen
+    Folder,
+    /// Vorhandene Backups auflisten
+    List {
+        /// Nur Backups dieses Browsers
+        #[arg(long, value_enum, ignore_case = true)]
+        browser: Option<Browser>,
+    },
+    /// Backups löschen, die älter als die Aufbewahrungsdauer sind
+    Cleanup {
+        /// Ohne Angabe gilt die Aufbewahrungsdauer aus den Einstellungen
+        #[arg(long)]
+        days: Option<i64>,
+    },
+    /// Backup in den Browser zurückspielen
+    Restore {
+        #[arg(long, value_enum, ignore_case = true)]
+        browser: Browser,
+        /// Dateiname aus `bbb list` oder Pfad zu einer Backup-Datei
+        #[arg(long)]
+        file: PathBuf,
+        /// Nur fehlende Lesezeichen ergänzen statt die Favoriten zu ersetzen
+        #[arg(long)]
+        merge: bool,
+        /// Zielprofil als Ordnername (z.B. "Profile 2"), Standard ist das gesicherte Profil
+        #[arg(long, conflicts_with = "merge")]
+        profile: Option<String>,
+        /// Backup als eigene Datei hierhin schreiben, die Favoriten im Browser bleiben unverändert
+        #[arg(long, conflicts_with_all = ["merge", "profile"])]
+        save_as: Option<PathBuf>,
+    },
+    /// Letzte Wiederherstellung rückgängig machen
+    UndoRestore {
+        #[arg(long, value_enum, ignore_case = true)]
+        browser: Browser,
+    },
+    /// Favoriten aus dem neuesten Backup exportieren
+    Export {
+        #[arg(long, value_enum, default_value = "html")]
+        format: Format,
+        /// Ohne Angabe werden alle Browser zusammengeführt
+        #[arg(long, value_enum, ignore_case = true)]
+        browser: Option<Browser>,
+        /// Zieldatei, Standard ist z.B. chrome_bookmarks.html im aktuellen Ordner
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Output {
+    Text,
+    Json,
+}
+
+// Eine Zeile der Ausgabe, bei `--output json` als Objekt im Array
+#[derive(Serialize)]
+struct Record {
+    browser: String,
+    path: Option<PathBuf>,
+    timestamp: Option<chrono::DateTime<Local>>,
+    size: Option<u64>,
+    success: bool,
+    message: String,
+}
+
+impl Record {
+    fn print_text(&self) {
+        if self.success && self.message.is_empty() {
+            println!(
+                "{:<8} {}  {:>10}  {}",
+                self.browser,
+                self.timestamp.map(|t| t.format("%d.%m.%Y %H:%M:%S").to_string()).unwrap_or_default(),
+                self.size.unwrap_or(0),
+                self.path.as_ref().and_then(|p| p.file_name()).map(|n| n.to_string_lossy()).unwrap_or_default()
+            );
+        } else {
+            let status = if self.success { "OK" } else { "FEHLER" };
+            println!("{:<8} {:<7} {}", self.browser, status, self.message);
+        }
+    }
+}
+
+fn print_records(records: &[Record], output: Output) {
+    match output {
+        Output::Text => records.iter().for_each(Record::print_text),
+        Output::Json => match serde_json::to_string_pretty(records) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Fehler beim Erzeugen der JSON-Ausgabe: {}", e),
+        },
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+#[value(rename_all = "PascalCase")]
+enum Browser {
+    Chrome,
+    Edge,
+    Firefox,
+}
+
+impl Browser {
+    const ALL: [&'static str; 3] = ["Chrome", "Edge", "Firefox"];
+
+    fn name(self) -> &'static str {
+        Self::ALL[self as usize]
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Html,
+    NetscapeHtml,
+    Xbel,
+    Opml,
+    Json,
+    Jsonlz4,
+    Csv,
+    Markdown,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Html => ExportFormat::Html,
+            Format::NetscapeHtml => ExportFormat::NetscapeHtml,
+            Format::Xbel => ExportFormat::Xbel,
+            Format::Opml => ExportFormat::Opml,
+            Format::Json => ExportFormat::Canonical,
+            Format::Jsonlz4 => ExportFormat::FirefoxJsonlz4,
+            Format::Csv => ExportFormat::Csv,
+            Format::Markdown => ExportFormat::Markdown,
+        }
+    }
+}
+
+// Führt den Befehl aus und liefert den Exit-Code des Prozesses
+pub fn run(cli: Cli) -> i32 {
+    let command = match (cli.backup_now, cli.command) {
+        (true, Some(_)) => Cli::command()
+            .error(clap::error::ErrorKind::ArgumentConflict, "--backup-now kann nicht mit einem Befehl kombiniert werden")
+            .exit(),
+        (true, None) => Command::Backup,
+        (false, Some(command)) => command,
+        // Nur Optionen wie --dry-run, aber kein Befehl
+        (false, None) => {
+            Cli::command().print_help().ok();
+            return 2;
+        }
+    };
+
+    let manager = BackupManager::new();
+    let dry_run = cli.dry_run;
+    let result = match command {
+        Command::Backup => backup(&manager, dry_run, cli.backup_now, cli.output),
+        Command::Status => status(),
+        Command::Show { restore: false } => ipc::send(Request::ShowWindow).map(|_| ()),
+        Command::Show { restore: true } => ipc::send(Request::ShowRestore).map(|_| ()),
+        Command::Folder => open_folder(&manager),
+        Command::List { browser } => {
+            list(&manager, browser, cli.output);
+            Ok(())
+        }
+        Command::Cleanup { days } => cleanup(&manager, days, dry_run),
+        Command::Restore { browser, file, merge, profile, save_as } => {
+            restore(&manager, browser, &file, merge, profile.as_deref(), save_as.as_deref(), dry_run)
+        }
+        Command::UndoRestore { browser } => manager.undo_restore(browser.name(), dry_run).map(|message| println!("{}", message)),
+        Command::Export { format, browser, file } => export(&manager, format.into(), browser, file),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            // Auch ins Protokoll, z.B. bei --backup-now aus der Aufgabenplanung
+            log::error!("Fehler: {}", e);
+            1
+        }
+    }
+}
+
+// Ergebnis je Browser ausgeben; ein einziger Fehlschlag ergibt Exit-Code 1
+// `scheduled`: Aufruf mit --backup-now aus der Aufgabenplanung, dann gibt es auch den Healthcheck-Ping
+fn backup(manager: &BackupManager, dry_run: bool, scheduled: bool, output: Output) -> Result<(), String> {
+    // Läuft die Tray-App, sichert sie selbst, damit nicht zwei Backups gleichzeitig laufen.
+    // Ein Probelauf bleibt lokal, die Instanz würde sonst wirklich sichern.
+    let delegated = if dry_run { None } else { ipc::send(Request::BackupNow).ok() };
+    let results = match delegated {
+        Some(response) => serde_json::from_value(response)
+            .map_err(|e| format!("Ungültige Antwort der laufenden Instanz: {}", e))?,
+        None => {
+            let trigger = if scheduled { RunTrigger::Scheduled } else { RunTrigger::External };
+            manager.backup_browsers_with_progress(
+                &manager.enabled_browsers(), trigger, dry_run, &CancellationToken::default(), &|_| {},
+            )
+        }
+    };
+    if scheduled && !dry_run {
+        manager.report_healthcheck(&results);
+    }
+    let records: Vec<Record> = results.iter()
+        .map(|result| {
+            let metadata = result.path.as_ref().and_then(|path| fs::metadata(path).ok());
+            // Das Änderungsdatum ist bei kopierten Favoriten das der Quelldatei
+            let timestamp = result.path.as_ref()
+                .and_then(|path| path.file_name())
+                .and_then(|name| catalog::timestamp_from_file_name(&name.to_string_lossy()));
+            Record {
+                browser: result.browser.clone(),
+                path: result.path.clone(),
+                timestamp,
+                size: metadata.map(|m| m.len()),
+                success: result.success,
+                message: result.message.clone(),
+            }
+        })
+        .collect();
+    print_records(&records, output);
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    if failed > 0 {
+        return Err(format!("{} von {} Backups fehlgeschlagen", failed, results.len()));
+    }
+    Ok(())
+}
+
+fn status() -> Result<(), String> {
+    let status: InstanceStatus = serde_json::from_value(ipc::send(Request::Status)?)
+        .map_err(|e| format!("Ungültige Antwort der laufenden Instanz: {}", e))?;
+    let format = |time: Option<chrono::DateTime<Local>>| {
+        time.map_or("-".to_string(), |t| t.format("%d.%m.%Y %H:%M").to_string())
+    };
+    println!("Automatische Backups: {}", if status.paused { "pausiert" } else { "aktiv" });
+    println!("Letztes Backup:       {}", format(status.last_backup));
+    println!("Nächstes Backup:      {}", format(status.next_backup));
+    Ok(())
+}
+
+fn open_folder(manager: &BackupManager) -> Result<(), String> {
+    let backup_dir = manager.get_backup_directory();
+    println!("{}", backup_dir.display());
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(backup_dir)
+            .spawn()
+            .map_err(|e| format!("Explorer konnte nicht gestartet werden: {}", e))?;
+    }
+    Ok(())
+}
+
+fn list(manager: &BackupManager, browser: Option<Browser>, output: Output) {
+    let browsers = match browser {
+        Some(browser) => vec![browser.name()],
+        None => Browser::ALL.to_vec(),
+    };
+    let records: Vec<Record> = browsers.into_iter()
+        .flat_map(|browser| manager.get_backup_list(browser).into_iter().map(move |backup| Record {
+            browser: browser.to_string(),
+            path: Some(backup.path),
+            timestamp: catalog::timestamp_from_file_name(&backup.name).or(Some(backup.date)),
+            size: Some(backup.size),
+            success: true,
+            message: String::new(),
+        }))
+        .collect();
+    print_records(&records, output);
+}
+
+fn cleanup(manager: &BackupManager, days: Option<i64>, dry_run: bool) -> Result<(), String> {
+    let days = days.unwrap_or(manager.get_config().retention_days);
+    let count = manager.cleanup_old_backups(days, dry_run)?;
+    if dry_run {
+        println!("{} Backups älter als {} Tage würden gelöscht", count, days);
+    } else {
+        println!("{} Backups älter als {} Tage gelöscht", count, days);
+    }
+    Ok(())
+}
+
+fn restore(
+    manager: &BackupManager,
+    browser: Browser,
+    file: &Path,
+    merge: bool,
+    profile: Option<&str>,
+    save_as: Option<&Path>,
+    dry_run: bool,
+) -> Result<(), String> {
+    // Reiner Dateiname wie in `bbb list` bezieht sich auf den Backup-Ordner
+    let path = if file.exists() {
+        file.to_path_buf()
+    } else {
+        manager.get_backup_list(browser.name())
+            .into_iter()
+            .find(|backup| Path::new(&backup.name) == file)
+            .map(|backup| backup.path)
+            .ok_or_else(|| format!("Backup {} nicht gefunden", file.display()))?
+    };
+
+    let message = if let Some(save_as) = save_as {
+        manager.save_backup_copy(browser.name(), &path, save_as, dry_run)?
+    } else if merge {
+        manager.restore_merged(browser.name(), &path, dry_run)?
+    } else if let Some(profile) = profile {
+        let profiles = BackupManager::browser_profiles(browser.name());
+        let target = profiles
+            .iter()
+            .find(|p| [&p.dir, &p.name].iter().any(|name| name.to_lowercase() == profile.to_lowercase()))
+            .ok_or_else(|| {
+                let known: Vec<&str> = profiles.iter().map(|p| p.dir.as_str()).collect();
+                format!("Profil \"{}\" nicht gefunden, vorhanden: {}", profile, known.join(", "))
+            })?;
+        manager.restore_backup_to(browser.name(), &path, &target.bookmarks_path, dry_run)?
+    } else {
+        manager.restore_backup(browser.name(), &path, dry_run)?
+    };
+    println!("{}", message);
+    Ok(())
+}
+
+fn export(manager: &BackupManager, format: ExportFormat, browser: Option<Browser>, file: Option<PathBuf>) -> Result<(), String> {
+    let name = browser.map_or("alle".to_string(), |b| b.name().to_lowercase());
+    let output = file.unwrap_or_else(|| {
+        PathBuf::from(format!("{}_{}.{}", name, format.file_suffix(), format.extension()))
+    });
+
+    let filter = BookmarkFilter::default();
+    let layout = BookmarkLayout::default();
+    match browser {
+        Some(browser) => manager.export_bookmarks(browser.name(), format, &filter, &layout, &output)?,
+        None => manager.export_merged(format, &filter, &layout, &output)?,
+    }
+    println!("Favoriten nach {} exportiert", output.display());
+    Ok(())
+}
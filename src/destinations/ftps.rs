@@ -0,0 +1,250 @@
+// ftps.rs - Upload per FTP mit explizitem TLS (FTPS, RFC 4217)
+//
+// Bewusst schlank: Anmeldung, Verzeichnisse anlegen und STOR im passiven
+// Modus. Server, die für die Datenverbindung die Wiederaufnahme der
+// TLS-Sitzung erzwingen (vsftpd: require_ssl_reuse=YES), werden nicht
+// unterstützt.
+use super::throttle::Throttled;
+use super::{part_name, remote_path, Destination, UploadOptions};
+use crate::archive::ArchiveEntry;
+use crate::proxy::{self, Proxy};
+use native_tls::{TlsConnector, TlsStream};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct FtpsConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    pub remote_dir: String,
+    // Nur für Server mit selbst signiertem Zertifikat im eigenen Netz
+    pub accept_invalid_certs: bool,
+}
+
+impl Default for FtpsConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: 21,
+            username: String::new(),
+            password: String::new(),
+            remote_dir: "browser-backups".to_string(),
+            accept_invalid_certs: false,
+        }
+    }
+}
+
+// Antwort des Servers: dreistelliger Code und Text
+fn read_reply<R: BufRead>(reader: &mut R) -> Result<(u32, String), String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| format!("FTP Lesefehler: {}", e))?;
+    if line.len() < 4 {
+        return Err("Verbindung vom FTP-Server geschlossen".to_string());
+    }
+    let code: u32 = line[..3].parse().map_err(|_| format!("Ungültige FTP-Antwort: {}", line.trim()))?;
+
+    // Mehrzeilige Antworten ("230-...") enden mit "230 ..."
+    if line.as_bytes()[3] == b'-' {
+        let end = format!("{} ", &line[..3]);
+        loop {
+            let mut next = String::new();
+            if reader.read_line(&mut next).map_err(|e| format!("FTP Lesefehler: {}", e))? == 0 {
+                break;
+            }
+            line.push_str(&next);
+            if next.starts_with(&end) {
+                break;
+            }
+        }
+    }
+    Ok((code, line.trim().to_string()))
+}
+
+struct Session {
+    control: BufReader<TlsStream<TcpStream>>,
+    connector: TlsConnector,
+    host: String,
+    proxy: Option<Proxy>,
+}
+
+impl Session {
+    fn command(&mut self, command: &str) -> Result<(u32, String), String> {
+        self.control
+            .get_mut()
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .map_err(|e| format!("FTP Schreibfehler: {}", e))?;
+        read_reply(&mut self.control)
+    }
+
+    fn expect(&mut self, command: &str, expected: &[u32]) -> Result<String, String> {
+        let (code, text) = self.command(command)?;
+        if expected.contains(&code) {
+            Ok(text)
+        } else {
+            // Passwort nicht ins Log schreiben
+            let shown = if command.starts_with("PASS") { "PASS ***" } else { command };
+            Err(format!("{} abgelehnt: {}", shown, text))
+        }
+    }
+
+    // Passive Datenverbindung; die Adresse aus der Antwort wird ignoriert,
+    // da sie hinter NAT oft nicht stimmt
+    fn data_connection(&mut self) -> Result<TcpStream, String> {
+        let text = self.expect("PASV", &[227])?;
+        let numbers: Vec<u16> = text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .skip(1)
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        if numbers.len() < 6 {
+            return Err(format!("Ungültige PASV-Antwort: {}", text));
+        }
+        let port = numbers[4] * 256 + numbers[5];
+        let stream = proxy::connect(self.proxy.as_ref(), &self.host, port)
+            .map_err(|e| format!("Datenverbindung fehlgeschlagen: {}", e))?;
+        stream.set_read_timeout(Some(TIMEOUT)).ok();
+        stream.set_write_timeout(Some(TIMEOUT)).ok();
+        Ok(stream)
+    }
+
+    fn create_dirs(&mut self, dir: &str) -> Result<(), String> {
+        let mut current = String::new();
+        for part in dir.split('/').filter(|p| !p.is_empty()) {
+            if dir.starts_with('/') || !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(part);
+            // 550/521: existiert bereits
+            self.command(&format!("MKD {}", current))?;
+        }
+        Ok(())
+    }
+
+    // Größe einer Datei auf dem Server (RFC 3659), None wenn sie fehlt
+    fn size(&mut self, remote: &str) -> Option<u64> {
+        match self.command(&format!("SIZE {}", remote)) {
+            Ok((213, text)) => text.split_whitespace().nth(1).and_then(|s| s.parse().ok()),
+            _ => None,
+        }
+    }
+
+    // Mit offset > 0 wird per REST an eine vorhandene Datei angehängt
+    fn store(&mut self, remote: &str, source: &mut impl Read, offset: u64) -> Result<(), String> {
+        let data = self.data_connection()?;
+        if offset > 0 {
+            self.expect(&format!("REST {}", offset), &[350])?;
+        }
+        self.expect(&format!("STOR {}", remote), &[125, 150])?;
+
+        let mut data = self.connector
+            .connect(&self.host, data)
+            .map_err(|e| format!("TLS für die Datenverbindung fehlgeschlagen: {}", e))?;
+        io::copy(source, &mut data).map_err(|e| format!("Fehler beim Hochladen: {}", e))?;
+        data.shutdown().ok();
+        drop(data);
+
+        let (code, text) = read_reply(&mut self.control)?;
+        if code != 226 && code != 250 {
+            return Err(format!("Upload von {} fehlgeschlagen: {}", remote, text));
+        }
+        Ok(())
+    }
+}
+
+impl FtpsConfig {
+    fn connect(&self, proxy: Option<&Proxy>) -> Result<Session, String> {
+        if self.host.is_empty() {
+            return Err("Kein Server angegeben".to_string());
+        }
+        let tcp = proxy::connect(proxy, &self.host, self.port)
+            .map_err(|e| format!("{}:{} nicht erreichbar: {}", self.host, self.port, e))?;
+        tcp.set_read_timeout(Some(TIMEOUT)).ok();
+        tcp.set_write_timeout(Some(TIMEOUT)).ok();
+
+        let mut plain = BufReader::new(tcp);
+        let (code, text) = read_reply(&mut plain)?;
+        if code != 220 {
+            return Err(format!("Unerwartete Begrüßung: {}", text));
+        }
+        plain.get_mut()
+            .write_all(b"AUTH TLS\r\n")
+            .map_err(|e| format!("FTP Schreibfehler: {}", e))?;
+        let (code, text) = read_reply(&mut plain)?;
+        if code != 234 {
+            return Err(format!("Server unterstützt kein TLS: {}", text));
+        }
+
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .build()
+            .map_err(|e| format!("TLS Fehler: {}", e))?;
+        let control = connector
+            .connect(&self.host, plain.into_inner())
+            .map_err(|e| format!("TLS-Handshake fehlgeschlagen: {}", e))?;
+
+        let mut session = Session {
+            control: BufReader::new(control),
+            connector,
+            host: self.host.clone(),
+            proxy: proxy.cloned(),
+        };
+        session.expect(&format!("USER {}", self.username), &[230, 331])
+            .and_then(|_| session.expect(&format!("PASS {}", self.password), &[230, 202]))
+            .map_err(|e| format!("Anmeldung fehlgeschlagen: {}", e))?;
+        session.expect("PBSZ 0", &[200])?;
+        session.expect("PROT P", &[200])?;
+        session.expect("TYPE I", &[200])?;
+        Ok(session)
+    }
+}
+
+impl Destination for FtpsConfig {
+    fn label(&self) -> String {
+        format!("FTPS {}@{}:{}/{}", self.username, self.host, self.port, self.remote_dir.trim_start_matches('/'))
+    }
+
+    fn test_connection(&mut self, proxy: Option<&Proxy>) -> Result<String, String> {
+        let mut session = self.connect(proxy)?;
+        session.create_dirs(&self.remote_dir)?;
+        session.expect(&format!("CWD {}", self.remote_dir), &[250])
+            .map_err(|e| format!("Verzeichnis {} nicht verfügbar: {}", self.remote_dir, e))?;
+        session.command("QUIT").ok();
+        Ok("Verbindung erfolgreich.".to_string())
+    }
+
+    fn upload(&self, files: &[ArchiveEntry], options: &UploadOptions) -> Result<usize, String> {
+        let mut session = self.connect(options.proxy.as_ref())?;
+        for entry in files {
+            let target = remote_path(&self.remote_dir, &entry.name);
+            if let Some((dir, _)) = target.rsplit_once('/') {
+                session.create_dirs(dir)?;
+            }
+            if session.size(&target) == Some(entry.size) {
+                continue;
+            }
+
+            // Abgebrochenen Upload fortsetzen, sofern der Rest noch passt
+            let part = part_name(&target);
+            let offset = session.size(&part).filter(|size| *size <= entry.size).unwrap_or(0);
+            let mut source = fs::File::open(&entry.path)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+            source.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+            session.store(&part, &mut Throttled::new(source, options.bytes_per_second), offset)?;
+
+            session.command(&format!("DELE {}", target)).ok();
+            session.expect(&format!("RNFR {}", part), &[350])?;
+            session.expect(&format!("RNTO {}", target), &[250])?;
+        }
+        session.command("QUIT").ok();
+        Ok(files.len())
+    }
+}
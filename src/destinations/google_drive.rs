@@ -0,0 +1,478 @@
+// google_drive.rs - Upload in einen Google-Drive-Ordner (OAuth 2.0)
+//
+// Anmeldung über den Ablauf für Desktop-Apps: Der Browser leitet nach der
+// Zustimmung auf einen lokalen Port um, dort wird der Code abgeholt. Mit dem
+// Bereich drive.file sieht die App nur Dateien, die sie selbst angelegt hat -
+// das reicht, um Backups auf einem neuen Rechner wieder herunterzuladen.
+// PKCE (RFC 7636) sorgt dafür, dass nur diese Anmeldung den Code einlösen
+// kann, auch wenn ein anderes Programm die Rückleitung abfängt.
+use super::throttle::Throttled;
+use super::{Destination, UploadOptions};
+use crate::archive::ArchiveEntry;
+use crate::proxy::{self, Proxy};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const FILES_URL: &str = "https://www.googleapis.com/drive/v3/files";
+const UPLOAD_URL: &str = "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart";
+const RESUMABLE_URL: &str = "https://www.googleapis.com/upload/drive/v3/files?uploadType=resumable";
+const SCOPE: &str = "https://www.googleapis.com/auth/drive.file";
+const FOLDER_MIME: &str = "application/vnd.google-apps.folder";
+
+// Größere Dateien werden in Blöcken dieser Größe fortsetzbar hochgeladen
+// (Vielfaches von 256 KiB, wie von der API verlangt)
+const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+// So lange wartet die Anmeldung auf die Rückleitung aus dem Browser
+const AUTH_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct GoogleDriveConfig {
+    // OAuth-Client vom Typ "Desktop-App" aus der Google Cloud Console
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: Option<String>,
+    pub folder_name: String,
+    // Wird beim ersten Verbindungstest ermittelt bzw. angelegt
+    pub folder_id: Option<String>,
+}
+
+impl Default for GoogleDriveConfig {
+    fn default() -> Self {
+        Self {
+            client_id: String::new(),
+            client_secret: String::new(),
+            refresh_token: None,
+            folder_name: "Browser-Backups".to_string(),
+            folder_id: None,
+        }
+    }
+}
+
+// Ein Backup in Drive, Name wie im Archiv ("Chrome/bookmarks_...json")
+#[derive(Clone, Debug)]
+pub struct RemoteFile {
+    pub id: String,
+    pub name: String,
+}
+
+fn api_error(e: ureq::Error) -> String {
+    match e {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!("Google Drive antwortet mit {}: {}", code, body.trim())
+        }
+        ureq::Error::Transport(e) => format!("Google Drive nicht erreichbar: {}", e),
+    }
+}
+
+// Zeichenketten in Drive-Suchanfragen stehen in einfachen Anführungszeichen
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn open_in_browser(url: &str) {
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(url)
+            .spawn()
+            .ok();
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        // Not implemented for non-Windows platforms
+        log::info!("Zur Anmeldung im Browser öffnen: {}", url);
+    }
+}
+
+// Zufällige Zeichenkette für state und code_verifier (43 Zeichen, URL-sicher)
+fn random_string() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Zufallszahlen nicht verfügbar: {}", e))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+// Wartet auf "GET /?code=...&state=..." vom Browser und antwortet mit einer kurzen Seite
+fn receive_code(listener: &TcpListener, state: &str) -> Result<String, String> {
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let deadline = Instant::now() + AUTH_TIMEOUT;
+    loop {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                if Instant::now() > deadline {
+                    return Err("Zeitüberschreitung bei der Anmeldung".to_string());
+                }
+                thread::sleep(Duration::from_millis(200));
+                continue;
+            }
+            Err(e) => return Err(format!("Fehler bei der Anmeldung: {}", e)),
+        };
+        stream.set_nonblocking(false).ok();
+
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line).ok();
+        let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+        // Favicon-Anfragen u.ä. ignorieren
+        let url = match url::Url::parse(&format!("http://127.0.0.1{}", target)) {
+            Ok(url) if url.query().is_some() => url,
+            _ => continue,
+        };
+
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        let value = |key: &str| query.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        let result = if value("state").as_deref() != Some(state) {
+            Err("Ungültige Antwort bei der Anmeldung".to_string())
+        } else if let Some(code) = value("code") {
+            Ok(code)
+        } else {
+            Err(format!("Anmeldung abgelehnt: {}", value("error").unwrap_or_default()))
+        };
+
+        let text = match &result {
+            Ok(_) => "Anmeldung erfolgreich. Dieses Fenster kann geschlossen werden.",
+            Err(_) => "Anmeldung fehlgeschlagen. Details stehen in der Anwendung.",
+        };
+        let page = format!("<!DOCTYPE html><html><head><meta charset=\"UTF-8\"></head><body><p>{}</p></body></html>", text);
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            page.len(),
+            page
+        ).ok();
+        return result;
+    }
+}
+
+impl GoogleDriveConfig {
+    pub fn is_authorized(&self) -> bool {
+        self.refresh_token.is_some()
+    }
+
+    // Öffnet die Google-Anmeldung im Browser und speichert das Refresh-Token
+    pub fn authorize(&mut self, proxy: Option<&Proxy>) -> Result<(), String> {
+        if self.client_id.is_empty() || self.client_secret.is_empty() {
+            return Err("Client-ID und Client-Secret fehlen".to_string());
+        }
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| format!("Lokaler Port nicht verfügbar: {}", e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        let redirect_uri = format!("http://127.0.0.1:{}", port);
+        let state = random_string()?;
+        let verifier = random_string()?;
+        let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+        let mut url = url::Url::parse(AUTH_URL).map_err(|e| e.to_string())?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("response_type", "code")
+            .append_pair("scope", SCOPE)
+            .append_pair("access_type", "offline")
+            .append_pair("prompt", "consent")
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &challenge)
+            .append_pair("code_challenge_method", "S256");
+        open_in_browser(url.as_str());
+
+        let code = receive_code(&listener, &state)?;
+        let response: Value = proxy::agent(proxy, TOKEN_URL)
+            .post(TOKEN_URL)
+            .send_form(&[
+                ("code", code.as_str()),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("code_verifier", verifier.as_str()),
+                ("grant_type", "authorization_code"),
+            ])
+            .map_err(api_error)?
+            .into_json()
+            .map_err(|e| format!("Ungültige Antwort von Google: {}", e))?;
+
+        let token = response["refresh_token"].as_str()
+            .ok_or("Google hat kein Refresh-Token geliefert")?;
+        self.refresh_token = Some(token.to_string());
+        Ok(())
+    }
+
+    // Holt ein frisches Zugriffstoken
+    fn connect(&self, proxy: Option<&Proxy>) -> Result<Api, String> {
+        let refresh_token = self.refresh_token.as_deref()
+            .ok_or("Nicht bei Google Drive angemeldet")?;
+        let response: Value = proxy::agent(proxy, TOKEN_URL)
+            .post(TOKEN_URL)
+            .send_form(&[
+                ("refresh_token", refresh_token),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("grant_type", "refresh_token"),
+            ])
+            .map_err(api_error)?
+            .into_json()
+            .map_err(|e| format!("Ungültige Antwort von Google: {}", e))?;
+        let token = response["access_token"].as_str()
+            .ok_or("Google hat kein Zugriffstoken geliefert")?;
+        // Alle weiteren Aufrufe gehen an www.googleapis.com
+        Ok(Api { agent: proxy::agent(proxy, FILES_URL), token: token.to_string() })
+    }
+
+    fn backup_folder(&self, api: &Api) -> Result<String, String> {
+        match &self.folder_id {
+            Some(id) => Ok(id.clone()),
+            None => api.find_or_create_folder(&self.folder_name, "root"),
+        }
+    }
+
+    // Alle Backups im Drive-Ordner, gegliedert nach Browser-Unterordnern
+    pub fn list_backups(&self, proxy: Option<&Proxy>) -> Result<Vec<RemoteFile>, String> {
+        let api = self.connect(proxy)?;
+        let folder = self.backup_folder(&api)?;
+
+        let mut backups = Vec::new();
+        for browser in &["Chrome", "Edge", "Firefox"] {
+            let browser_folder = match api.find_folder(browser, &folder)? {
+                Some(id) => id,
+                None => continue,
+            };
+            let query = format!("{} in parents and trashed = false", quote(&browser_folder));
+            for file in api.find_files(&query)? {
+                if file["mimeType"] == FOLDER_MIME {
+                    continue;
+                }
+                if let (Some(id), Some(name)) = (file["id"].as_str(), file["name"].as_str()) {
+                    backups.push(RemoteFile {
+                        id: id.to_string(),
+                        name: format!("{}/{}", browser, name),
+                    });
+                }
+            }
+        }
+        Ok(backups)
+    }
+
+    pub fn download(&self, file: &RemoteFile, target: &Path, proxy: Option<&Proxy>) -> Result<(), String> {
+        let api = self.connect(proxy)?;
+        let response = api.get(&format!("{}/{}", FILES_URL, file.id))
+            .query("alt", "media")
+            .call()
+            .map_err(api_error)?;
+
+        let mut output = fs::File::create(target)
+            .map_err(|e| format!("Fehler beim Erstellen von {}: {}", target.display(), e))?;
+        io::copy(&mut response.into_reader(), &mut output)
+            .map_err(|e| format!("Fehler beim Herunterladen von {}: {}", file.name, e))?;
+        Ok(())
+    }
+}
+
+// Angemeldeter Zugang zur Drive-API
+struct Api {
+    agent: ureq::Agent,
+    token: String,
+}
+
+impl Api {
+    fn get(&self, url: &str) -> ureq::Request {
+        self.agent.get(url).set("Authorization", &format!("Bearer {}", self.token))
+    }
+
+    fn post(&self, url: &str) -> ureq::Request {
+        self.agent.post(url).set("Authorization", &format!("Bearer {}", self.token))
+    }
+
+    fn put(&self, url: &str) -> ureq::Request {
+        self.agent.put(url).set("Authorization", &format!("Bearer {}", self.token))
+    }
+
+    fn find_files(&self, query: &str) -> Result<Vec<Value>, String> {
+        let mut files = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = self.get(FILES_URL)
+                .query("q", query)
+                .query("fields", "nextPageToken,files(id,name,size,mimeType)")
+                .query("pageSize", "1000");
+            if let Some(page) = &page_token {
+                request = request.query("pageToken", page);
+            }
+            let response: Value = request.call()
+                .map_err(api_error)?
+                .into_json()
+                .map_err(|e| format!("Ungültige Antwort von Google Drive: {}", e))?;
+
+            files.extend(response["files"].as_array().cloned().unwrap_or_default());
+            match response["nextPageToken"].as_str() {
+                Some(next) => page_token = Some(next.to_string()),
+                None => return Ok(files),
+            }
+        }
+    }
+
+    fn find_folder(&self, name: &str, parent: &str) -> Result<Option<String>, String> {
+        let query = format!(
+            "name = {} and mimeType = '{}' and {} in parents and trashed = false",
+            quote(name), FOLDER_MIME, quote(parent)
+        );
+        Ok(self.find_files(&query)?
+            .first()
+            .and_then(|f| f["id"].as_str())
+            .map(str::to_string))
+    }
+
+    fn find_or_create_folder(&self, name: &str, parent: &str) -> Result<String, String> {
+        if let Some(id) = self.find_folder(name, parent)? {
+            return Ok(id);
+        }
+        let response: Value = self.post(FILES_URL)
+            .send_json(json!({ "name": name, "mimeType": FOLDER_MIME, "parents": [parent] }))
+            .map_err(api_error)?
+            .into_json()
+            .map_err(|e| format!("Ungültige Antwort von Google Drive: {}", e))?;
+        response["id"].as_str()
+            .map(str::to_string)
+            .ok_or_else(|| format!("Ordner {} konnte nicht angelegt werden", name))
+    }
+
+    // Größe einer gleichnamigen Datei im Ordner; Drive liefert sie als Zeichenkette
+    fn existing_size(&self, name: &str, parent: &str) -> Result<Option<u64>, String> {
+        let query = format!("name = {} and {} in parents and trashed = false", quote(name), quote(parent));
+        Ok(self.find_files(&query)?
+            .first()
+            .and_then(|f| f["size"].as_str())
+            .and_then(|s| s.parse().ok()))
+    }
+
+    fn upload_file(&self, name: &str, parent: &str, entry: &ArchiveEntry, options: &UploadOptions) -> Result<(), String> {
+        if entry.size > CHUNK_SIZE {
+            return self.upload_resumable(name, parent, entry, options);
+        }
+
+        let content = fs::read(&entry.path)
+            .map_err(|e| format!("Fehler beim Lesen von {}: {}", entry.path.display(), e))?;
+        let boundary = "browser_backup_boundary";
+        let metadata = json!({ "name": name, "parents": [parent] });
+
+        let mut body = format!(
+            "--{b}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{m}\r\n--{b}\r\nContent-Type: application/octet-stream\r\n\r\n",
+            b = boundary,
+            m = metadata
+        ).into_bytes();
+        body.extend_from_slice(&content);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        self.post(UPLOAD_URL)
+            .set("Content-Type", &format!("multipart/related; boundary={}", boundary))
+            .set("Content-Length", &body.len().to_string())
+            .send(Throttled::new(Cursor::new(body), options.bytes_per_second))
+            .map_err(api_error)?;
+        Ok(())
+    }
+
+    // Fortsetzbarer Upload: nach einem Abbruch fragt Drive, wie viel schon
+    // angekommen ist, und es geht ab dort weiter
+    fn upload_resumable(&self, name: &str, parent: &str, entry: &ArchiveEntry, options: &UploadOptions) -> Result<(), String> {
+        let size = entry.size;
+        let response = self.post(RESUMABLE_URL)
+            .set("X-Upload-Content-Type", "application/octet-stream")
+            .set("X-Upload-Content-Length", &size.to_string())
+            .send_json(json!({ "name": name, "parents": [parent] }))
+            .map_err(api_error)?;
+        let session = response.header("Location")
+            .ok_or("Google Drive hat keine Upload-Sitzung geliefert")?
+            .to_string();
+
+        let mut source = fs::File::open(&entry.path)
+            .map_err(|e| format!("Fehler beim Lesen von {}: {}", entry.path.display(), e))?;
+        let mut offset = 0;
+        let mut failures = 0;
+        loop {
+            let length = CHUNK_SIZE.min(size - offset);
+            source.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+            let chunk = (&mut source).take(length);
+            let result = self.put(&session)
+                .set("Content-Length", &length.to_string())
+                .set("Content-Range", &format!("bytes {}-{}/{}", offset, offset + length - 1, size))
+                .send(Throttled::new(chunk, options.bytes_per_second));
+
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    failures += 1;
+                    if failures > options.retries {
+                        return Err(api_error(e));
+                    }
+                    log::warn!("Upload von {} unterbrochen, wird fortgesetzt: {}", name, api_error(e));
+                    // Stand bei Drive erfragen
+                    self.put(&session)
+                        .set("Content-Range", &format!("bytes */{}", size))
+                        .send_bytes(&[])
+                        .map_err(api_error)?
+                }
+            };
+
+            match response.status() {
+                200 | 201 => return Ok(()),
+                // "Resume Incomplete": Range enthält die bereits gespeicherten Bytes
+                308 => {
+                    offset = response.header("Range")
+                        .and_then(|range| range.rsplit('-').next())
+                        .and_then(|end| end.parse::<u64>().ok())
+                        .map_or(0, |end| end + 1);
+                }
+                status => return Err(format!("Unerwartete Antwort von Google Drive: {}", status)),
+            }
+        }
+    }
+}
+
+impl Destination for GoogleDriveConfig {
+    fn label(&self) -> String {
+        if self.is_authorized() {
+            format!("Google Drive /{}", self.folder_name)
+        } else {
+            format!("Google Drive /{} (nicht angemeldet)", self.folder_name)
+        }
+    }
+
+    // Meldet sich bei Bedarf an und legt den Backup-Ordner an
+    fn test_connection(&mut self, proxy: Option<&Proxy>) -> Result<String, String> {
+        if !self.is_authorized() {
+            self.authorize(proxy)?;
+        }
+        let api = self.connect(proxy)?;
+        let folder = api.find_or_create_folder(&self.folder_name, "root")?;
+        self.folder_id = Some(folder);
+        Ok(format!("Angemeldet, Ordner \"{}\" ist bereit.", self.folder_name))
+    }
+
+    fn upload(&self, files: &[ArchiveEntry], options: &UploadOptions) -> Result<usize, String> {
+        let api = self.connect(options.proxy.as_ref())?;
+        let folder = self.backup_folder(&api)?;
+
+        for entry in files {
+            let (parent, name) = match entry.name.split_once('/') {
+                Some((browser, name)) => (api.find_or_create_folder(browser, &folder)?, name),
+                None => (folder.clone(), entry.name.as_str()),
+            };
+            if api.existing_size(name, &parent)? == Some(entry.size) {
+                continue;
+            }
+            api.upload_file(name, &parent, entry, options)?;
+        }
+        Ok(files.len())
+    }
+}
@@ -0,0 +1,140 @@
+// destinations - Entfernte Ablageorte, auf die neue Backups hochgeladen werden
+pub mod sftp;
+pub mod ftps;
+pub mod google_drive;
+pub mod network_share;
+pub mod throttle;
+
+use crate::archive::ArchiveEntry;
+use crate::proxy::Proxy;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+pub trait Destination {
+    // Kurzbeschreibung für Einstellungen und Log, z.B. "SFTP user@host:22/backups"
+    fn label(&self) -> String;
+    // Verbindet sich, meldet sich an und legt das Zielverzeichnis an. Darf die
+    // Konfiguration ergänzen (z.B. den Host-Schlüssel beim ersten Test merken).
+    fn test_connection(&mut self, proxy: Option<&Proxy>) -> Result<String, String>;
+    // Lädt die Dateien unter ihrem Archivnamen ("Chrome/bookmarks_...json") hoch.
+    // Vollständig vorhandene Dateien werden übersprungen, abgebrochene
+    // Uploads (<Name>.part) fortgesetzt - so kann ein Fehlversuch einfach
+    // wiederholt werden.
+    fn upload(&self, files: &[ArchiveEntry], options: &UploadOptions) -> Result<usize, String>;
+}
+
+#[derive(Clone, Debug)]
+pub struct UploadOptions {
+    // Weitere Versuche nach einem Fehler
+    pub retries: u32,
+    // 0 = unbegrenzt
+    pub bytes_per_second: u64,
+    pub proxy: Option<Proxy>,
+}
+
+// Wiederholt den Upload mit wachsender Wartezeit (2, 4, 8... Sekunden)
+pub fn upload_with_retries(
+    destination: &dyn Destination,
+    files: &[ArchiveEntry],
+    options: &UploadOptions,
+) -> Result<usize, String> {
+    let mut attempt = 0;
+    loop {
+        match destination.upload(files, options) {
+            Ok(count) => return Ok(count),
+            Err(e) if attempt < options.retries => {
+                attempt += 1;
+                let wait = Duration::from_secs(1 << attempt.min(6));
+                log::warn!(
+                    "Upload nach {} fehlgeschlagen: {} - Versuch {} von {} in {} s",
+                    destination.label(), e, attempt + 1, options.retries + 1, wait.as_secs()
+                );
+                thread::sleep(wait);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type")]
+pub enum DestinationConfig {
+    Sftp(sftp::SftpConfig),
+    Ftps(ftps::FtpsConfig),
+    GoogleDrive(google_drive::GoogleDriveConfig),
+    NetworkShare(network_share::NetworkShareConfig),
+}
+
+impl DestinationConfig {
+    pub fn destination(&self) -> &dyn Destination {
+        match self {
+            DestinationConfig::Sftp(config) => config,
+            DestinationConfig::Ftps(config) => config,
+            DestinationConfig::GoogleDrive(config) => config,
+            DestinationConfig::NetworkShare(config) => config,
+        }
+    }
+
+    pub fn destination_mut(&mut self) -> &mut dyn Destination {
+        match self {
+            DestinationConfig::Sftp(config) => config,
+            DestinationConfig::Ftps(config) => config,
+            DestinationConfig::GoogleDrive(config) => config,
+            DestinationConfig::NetworkShare(config) => config,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RemoteDestination {
+    pub enabled: bool,
+    pub config: DestinationConfig,
+}
+
+// "backups/" + "Chrome/x.json" -> "backups/Chrome/x.json"
+pub fn remote_path(remote_dir: &str, name: &str) -> String {
+    let dir = remote_dir.trim_end_matches('/');
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+const STATUS_FILE: &str = "destinations_status.json";
+
+// Zustand eines Ziels für die Übersicht in den Einstellungen
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DestinationStatus {
+    pub last_upload: Option<DateTime<Local>>,
+    pub last_check: Option<DateTime<Local>>,
+    // Fehler des letzten Uploads oder Verbindungstests, None wenn er geklappt hat
+    pub last_error: Option<String>,
+}
+
+// Nach Label des Ziels, damit geänderte Zugangsdaten neu beginnen
+pub type StatusMap = BTreeMap<String, DestinationStatus>;
+
+pub fn load_status(backup_dir: &Path) -> StatusMap {
+    fs::read_to_string(backup_dir.join(STATUS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_status(backup_dir: &Path, status: &StatusMap) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(status)?;
+    fs::write(backup_dir.join(STATUS_FILE), content)
+}
+
+// Zwischenname während des Uploads, wird erst am Ende umbenannt
+pub fn part_name(path: &str) -> String {
+    format!("{}.part", path)
+}
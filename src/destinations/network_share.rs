@@ -0,0 +1,195 @@
+// network_share.rs - Kopie auf eine Netzwerkfreigabe (\\server\freigabe\ordner)
+//
+// Braucht die Freigabe andere Anmeldedaten als das Windows-Konto, wird vor
+// dem Kopieren mit WNetAddConnection2 eine Verbindung aufgebaut. Ohne
+// Benutzername nimmt Windows die gespeicherten Anmeldedaten
+// (Anmeldeinformationsverwaltung) bzw. das aktuelle Konto.
+use super::throttle::Throttled;
+use super::{part_name, Destination, UploadOptions};
+use crate::archive::ArchiveEntry;
+use crate::proxy::Proxy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+#[serde(default)]
+pub struct NetworkShareConfig {
+    pub path: String,
+    pub username: String,
+    pub password: String,
+}
+
+// "\\server\freigabe\backups\x" -> "\\server\freigabe"
+fn share_root(path: &str) -> Option<String> {
+    let trimmed = path.replace('/', "\\");
+    let rest = trimmed.strip_prefix("\\\\")?;
+    let mut parts = rest.split('\\').filter(|p| !p.is_empty());
+    let server = parts.next()?;
+    let share = parts.next()?;
+    Some(format!("\\\\{}\\{}", server, share))
+}
+
+// Hält die Verbindung, solange kopiert wird, und trennt sie danach wieder
+struct Connection {
+    remote: String,
+    established: bool,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        if self.established {
+            disconnect(&self.remote);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn wide(value: &str) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+    std::ffi::OsStr::new(value).encode_wide().chain(Some(0)).collect()
+}
+
+#[cfg(target_os = "windows")]
+fn connect(remote: &str, username: &str, password: &str) -> Result<bool, String> {
+    use std::ptr;
+    use winapi::shared::winerror::{
+        ERROR_ACCESS_DENIED, ERROR_BAD_NETPATH, ERROR_BAD_NET_NAME, ERROR_LOGON_FAILURE,
+        ERROR_SESSION_CREDENTIAL_CONFLICT, NO_ERROR,
+    };
+    use winapi::um::winnetwk::{WNetAddConnection2W, NETRESOURCEW, RESOURCETYPE_DISK};
+
+    let mut remote_name = wide(remote);
+    let username_w = wide(username);
+    let password_w = wide(password);
+
+    let mut resource = NETRESOURCEW {
+        dwScope: 0,
+        dwType: RESOURCETYPE_DISK,
+        dwDisplayType: 0,
+        dwUsage: 0,
+        lpLocalName: ptr::null_mut(),
+        lpRemoteName: remote_name.as_mut_ptr(),
+        lpComment: ptr::null_mut(),
+        lpProvider: ptr::null_mut(),
+    };
+
+    // NULL statt leerer Zeichenketten, damit Windows gespeicherte Anmeldedaten verwendet
+    let (username_ptr, password_ptr) = if username.is_empty() {
+        (ptr::null(), ptr::null())
+    } else {
+        (username_w.as_ptr(), password_w.as_ptr())
+    };
+
+    let result = unsafe { WNetAddConnection2W(&mut resource, password_ptr, username_ptr, 0) };
+    match result {
+        NO_ERROR => Ok(true),
+        // Bereits mit anderen Anmeldedaten verbunden: diese Verbindung weiterverwenden
+        ERROR_SESSION_CREDENTIAL_CONFLICT => Ok(false),
+        ERROR_BAD_NETPATH | ERROR_BAD_NET_NAME => Err(format!("Freigabe {} nicht erreichbar", remote)),
+        ERROR_LOGON_FAILURE => Err(format!("Anmeldung an {} fehlgeschlagen: Benutzername oder Passwort falsch", remote)),
+        ERROR_ACCESS_DENIED => Err(format!("Zugriff auf {} verweigert", remote)),
+        code => Err(format!(
+            "Verbindung zu {} fehlgeschlagen: {}",
+            remote,
+            std::io::Error::from_raw_os_error(code as i32)
+        )),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn connect(_remote: &str, _username: &str, _password: &str) -> Result<bool, String> {
+    // Not implemented for non-Windows platforms
+    Ok(false)
+}
+
+#[cfg(target_os = "windows")]
+fn disconnect(remote: &str) {
+    use winapi::um::winnetwk::WNetCancelConnection2W;
+
+    let remote_name = wide(remote);
+    unsafe {
+        WNetCancelConnection2W(remote_name.as_ptr(), 0, 0);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn disconnect(_remote: &str) {
+    // Not implemented for non-Windows platforms
+}
+
+impl NetworkShareConfig {
+    fn connect(&self) -> Result<Connection, String> {
+        let remote = share_root(&self.path)
+            .ok_or_else(|| format!("Kein gültiger Freigabepfad (\\\\server\\freigabe): {}", self.path))?;
+        let established = connect(&remote, &self.username, &self.password)?;
+        let connection = Connection { remote, established };
+
+        if !Path::new(&connection.remote).exists() {
+            return Err(format!("Freigabe {} nicht erreichbar", connection.remote));
+        }
+        fs::create_dir_all(&self.path)
+            .map_err(|e| format!("Zielordner {} kann nicht angelegt werden: {}", self.path, e))?;
+        Ok(connection)
+    }
+}
+
+impl Destination for NetworkShareConfig {
+    fn label(&self) -> String {
+        if self.username.is_empty() {
+            format!("Freigabe {}", self.path)
+        } else {
+            format!("Freigabe {} ({})", self.path, self.username)
+        }
+    }
+
+    // Netzwerkfreigaben im LAN laufen nie über den Proxy
+    fn test_connection(&mut self, _proxy: Option<&Proxy>) -> Result<String, String> {
+        let _connection = self.connect()?;
+        // Schreibrecht prüfen
+        let probe = PathBuf::from(&self.path).join(".verbindungstest");
+        fs::write(&probe, b"ok")
+            .map_err(|e| format!("Keine Schreibrechte in {}: {}", self.path, e))?;
+        fs::remove_file(&probe).ok();
+        Ok("Verbindung erfolgreich.".to_string())
+    }
+
+    fn upload(&self, files: &[ArchiveEntry], options: &UploadOptions) -> Result<usize, String> {
+        let _connection = self.connect()?;
+        for entry in files {
+            let target = entry.name.split('/').fold(PathBuf::from(&self.path), |path, part| path.join(part));
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+            }
+            if fs::metadata(&target).map(|m| m.len()).ok() == Some(entry.size) {
+                continue;
+            }
+
+            // Abgebrochene Kopie fortsetzen, sofern der Rest noch passt
+            let part = PathBuf::from(part_name(&target.to_string_lossy()));
+            let offset = fs::metadata(&part).map(|m| m.len()).ok()
+                .filter(|size| *size <= entry.size)
+                .unwrap_or(0);
+            let mut output = fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(offset == 0)
+                .open(&part)
+                .map_err(|e| format!("{} konnte nicht angelegt werden: {}", part.display(), e))?;
+            output.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+
+            let mut source = fs::File::open(&entry.path)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+            source.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+            io::copy(&mut Throttled::new(source, options.bytes_per_second), &mut output)
+                .map_err(|e| format!("Fehler beim Kopieren nach {}: {}", target.display(), e))?;
+            drop(output);
+
+            fs::rename(&part, &target)
+                .map_err(|e| format!("Fehler beim Umbenennen von {}: {}", part.display(), e))?;
+        }
+        Ok(files.len())
+    }
+}
@@ -0,0 +1,156 @@
+// sftp.rs - Upload per SFTP (SSH)
+use super::throttle::Throttled;
+use super::{part_name, remote_path, Destination, UploadOptions};
+use crate::archive::ArchiveEntry;
+use crate::proxy::{self, Proxy};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use ssh2::{HashType, OpenFlags, OpenType, Session, Sftp};
+use std::fs;
+use std::io::{self, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct SftpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    // Passwort bzw. Passphrase des Schlüssels, wenn `key_path` gesetzt ist
+    pub password: String,
+    pub key_path: Option<PathBuf>,
+    pub remote_dir: String,
+    // Wird beim ersten erfolgreichen Verbindungstest gemerkt ("SHA256:..."),
+    // ohne ihn lädt `upload` nichts hoch
+    pub host_key_fingerprint: Option<String>,
+}
+
+impl Default for SftpConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: 22,
+            username: String::new(),
+            password: String::new(),
+            key_path: None,
+            remote_dir: "browser-backups".to_string(),
+            host_key_fingerprint: None,
+        }
+    }
+}
+
+impl SftpConfig {
+    fn connect(&self, proxy: Option<&Proxy>) -> Result<(Session, String), String> {
+        if self.host.is_empty() {
+            return Err("Kein Server angegeben".to_string());
+        }
+        let tcp = proxy::connect(proxy, &self.host, self.port)
+            .map_err(|e| format!("{}:{} nicht erreichbar: {}", self.host, self.port, e))?;
+        tcp.set_read_timeout(Some(Duration::from_secs(30))).ok();
+
+        let mut session = Session::new().map_err(|e| format!("SSH Fehler: {}", e))?;
+        session.set_tcp_stream(tcp);
+        session.handshake().map_err(|e| format!("SSH-Handshake fehlgeschlagen: {}", e))?;
+
+        let fingerprint = session
+            .host_key_hash(HashType::Sha256)
+            .map(|hash| format!("SHA256:{}", base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash)))
+            .ok_or("Host-Schlüssel nicht verfügbar")?;
+        if let Some(expected) = &self.host_key_fingerprint {
+            if *expected != fingerprint {
+                return Err(format!(
+                    "Host-Schlüssel von {} hat sich geändert (erwartet {}, erhalten {})",
+                    self.host, expected, fingerprint
+                ));
+            }
+        }
+
+        let result = match &self.key_path {
+            Some(key) => {
+                let passphrase = Some(self.password.as_str()).filter(|p| !p.is_empty());
+                session.userauth_pubkey_file(&self.username, None, key, passphrase)
+            }
+            None => session.userauth_password(&self.username, &self.password),
+        };
+        result.map_err(|e| format!("Anmeldung fehlgeschlagen: {}", e))?;
+        Ok((session, fingerprint))
+    }
+
+    // Legt alle Verzeichnisse auf dem Weg an; vorhandene sind kein Fehler
+    fn create_dirs(sftp: &Sftp, dir: &str) {
+        let mut current = String::new();
+        for part in dir.split('/').filter(|p| !p.is_empty()) {
+            if dir.starts_with('/') || !current.is_empty() {
+                current.push('/');
+            }
+            current.push_str(part);
+            sftp.mkdir(Path::new(&current), 0o755).ok();
+        }
+    }
+}
+
+impl Destination for SftpConfig {
+    fn label(&self) -> String {
+        format!("SFTP {}@{}:{}/{}", self.username, self.host, self.port, self.remote_dir.trim_start_matches('/'))
+    }
+
+    fn test_connection(&mut self, proxy: Option<&Proxy>) -> Result<String, String> {
+        let (session, fingerprint) = self.connect(proxy)?;
+        let sftp = session.sftp().map_err(|e| format!("SFTP nicht verfügbar: {}", e))?;
+        Self::create_dirs(&sftp, &self.remote_dir);
+        sftp.stat(Path::new(&self.remote_dir))
+            .map_err(|e| format!("Verzeichnis {} nicht verfügbar: {}", self.remote_dir, e))?;
+
+        if self.host_key_fingerprint.is_none() {
+            self.host_key_fingerprint = Some(fingerprint.clone());
+            return Ok(format!("Verbindung erfolgreich.\nHost-Schlüssel gespeichert: {}", fingerprint));
+        }
+        Ok("Verbindung erfolgreich.".to_string())
+    }
+
+    fn upload(&self, files: &[ArchiveEntry], options: &UploadOptions) -> Result<usize, String> {
+        // Ohne gemerkten Schlüssel ließe sich ein fremder Server nicht erkennen
+        if self.host_key_fingerprint.is_none() {
+            return Err("Host-Schlüssel unbekannt - bitte zuerst die Verbindung testen".to_string());
+        }
+        let (session, _) = self.connect(options.proxy.as_ref())?;
+        let sftp = session.sftp().map_err(|e| format!("SFTP nicht verfügbar: {}", e))?;
+
+        for entry in files {
+            let target = remote_path(&self.remote_dir, &entry.name);
+            if let Some((dir, _)) = target.rsplit_once('/') {
+                Self::create_dirs(&sftp, dir);
+            }
+            let remote_size = |path: &str| sftp.stat(Path::new(path)).ok().and_then(|stat| stat.size);
+            if remote_size(&target) == Some(entry.size) {
+                continue;
+            }
+
+            // Abgebrochenen Upload fortsetzen, sofern der Rest noch passt
+            let part = part_name(&target);
+            let offset = remote_size(&part).filter(|size| *size <= entry.size).unwrap_or(0);
+            let mut remote = if offset > 0 {
+                let mut file = sftp.open_mode(Path::new(&part), OpenFlags::WRITE, 0o644, OpenType::File)
+                    .map_err(|e| format!("{} konnte nicht geöffnet werden: {}", part, e))?;
+                file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+                file
+            } else {
+                sftp.create(Path::new(&part))
+                    .map_err(|e| format!("{} konnte nicht angelegt werden: {}", part, e))?
+            };
+
+            let mut source = fs::File::open(&entry.path)
+                .map_err(|e| format!("Fehler beim Lesen: {}", e))?;
+            source.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+            io::copy(&mut Throttled::new(source, options.bytes_per_second), &mut remote)
+                .map_err(|e| format!("Fehler beim Hochladen von {}: {}", entry.name, e))?;
+            drop(remote);
+
+            sftp.unlink(Path::new(&target)).ok();
+            sftp.rename(Path::new(&part), Path::new(&target), None)
+                .map_err(|e| format!("{} konnte nicht umbenannt werden: {}", part, e))?;
+        }
+        Ok(files.len())
+    }
+}
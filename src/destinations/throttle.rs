@@ -0,0 +1,38 @@
+// throttle.rs - Begrenzt die Upload-Bandbreite, indem das Lesen der Quelldatei gebremst wird
+use std::io::{self, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub struct Throttled<R> {
+    inner: R,
+    // 0 = unbegrenzt
+    bytes_per_second: u64,
+    started: Instant,
+    transferred: u64,
+}
+
+impl<R: Read> Throttled<R> {
+    pub fn new(inner: R, bytes_per_second: u64) -> Self {
+        Self { inner, bytes_per_second, started: Instant::now(), transferred: 0 }
+    }
+}
+
+impl<R: Read> Read for Throttled<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.bytes_per_second == 0 {
+            return self.inner.read(buf);
+        }
+
+        // Kleine Blöcke (1/10 Sekunde), damit die Leitung gleichmäßig ausgelastet wird
+        let chunk = ((self.bytes_per_second / 10).max(1024) as usize).min(buf.len());
+        let read = self.inner.read(&mut buf[..chunk])?;
+        self.transferred += read as u64;
+
+        let expected = Duration::from_secs_f64(self.transferred as f64 / self.bytes_per_second as f64);
+        let elapsed = self.started.elapsed();
+        if expected > elapsed {
+            thread::sleep(expected - elapsed);
+        }
+        Ok(read)
+    }
+}
@@ -0,0 +1,188 @@
+// differential.rs - Differenzielle Backups für Chrome/Edge
+//
+// Ein Chromium-Bookmarks-File wird in eine flache Liste von Knoten (id, parent,
+// index) zerlegt. Eine Differenz speichert nur hinzugefügte, entfernte,
+// verschobene und geänderte Knoten gegenüber einem vollständigen Snapshot.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+pub const DIFF_SUFFIX: &str = ".diff.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlatNode {
+    pub id: String,
+    pub parent: String,
+    pub index: usize,
+    // Knoten ohne "children"
+    pub node: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BookmarkDiff {
+    // Dateiname des vollständigen Snapshots, auf dem die Differenz basiert
+    pub base: String,
+    // Alles außerhalb der Knoten (version, checksum, ...); "roots" enthält nur die IDs
+    pub envelope: Value,
+    pub added: Vec<FlatNode>,
+    pub removed: Vec<String>,
+    pub moved: Vec<FlatNode>,
+    pub changed: Vec<FlatNode>,
+}
+
+impl BookmarkDiff {
+    pub fn summary(&self) -> String {
+        format!(
+            "+{} −{}, {} verschoben, {} geändert",
+            self.added.len(),
+            self.removed.len(),
+            self.moved.len(),
+            self.changed.len()
+        )
+    }
+}
+
+pub fn is_diff_file(name: &str) -> bool {
+    name.ends_with(DIFF_SUFFIX)
+}
+
+const ROOT_PARENT_PREFIX: &str = "root:";
+
+fn flatten(bookmarks: &Value) -> Result<(Value, HashMap<String, FlatNode>), String> {
+    let roots = bookmarks
+        .get("roots")
+        .and_then(|v| v.as_object())
+        .ok_or("Ungültige Favoriten-Datei: \"roots\" fehlt")?;
+
+    let mut nodes = HashMap::new();
+    let mut root_ids = serde_json::Map::new();
+
+    fn visit(node: &Value, parent: &str, index: usize, nodes: &mut HashMap<String, FlatNode>) -> Result<String, String> {
+        let id = node
+            .get("id")
+            .and_then(|v| v.as_str())
+            .ok_or("Ungültige Favoriten-Datei: Knoten ohne \"id\"")?
+            .to_string();
+
+        let mut content = node.clone();
+        if let Some(object) = content.as_object_mut() {
+            object.remove("children");
+        }
+
+        if let Some(children) = node.get("children").and_then(|v| v.as_array()) {
+            for (child_index, child) in children.iter().enumerate() {
+                visit(child, &id, child_index, nodes)?;
+            }
+        }
+
+        nodes.insert(id.clone(), FlatNode {
+            id: id.clone(),
+            parent: parent.to_string(),
+            index,
+            node: content,
+        });
+        Ok(id)
+    }
+
+    for (key, root) in roots {
+        // Manche Chromium-Versionen legen Nicht-Knoten unter "roots" ab
+        if root.get("id").is_none() {
+            root_ids.insert(key.clone(), root.clone());
+            continue;
+        }
+        let id = visit(root, &format!("{}{}", ROOT_PARENT_PREFIX, key), 0, &mut nodes)?;
+        root_ids.insert(key.clone(), Value::String(id));
+    }
+
+    let mut envelope = bookmarks.clone();
+    if let Some(object) = envelope.as_object_mut() {
+        object.insert("roots".to_string(), Value::Object(root_ids));
+    }
+
+    Ok((envelope, nodes))
+}
+
+pub fn create_diff(base_name: &str, base: &Value, current: &Value) -> Result<BookmarkDiff, String> {
+    let (_, base_nodes) = flatten(base)?;
+    let (envelope, current_nodes) = flatten(current)?;
+
+    let mut diff = BookmarkDiff {
+        base: base_name.to_string(),
+        envelope,
+        added: Vec::new(),
+        removed: Vec::new(),
+        moved: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for (id, node) in &current_nodes {
+        match base_nodes.get(id) {
+            None => diff.added.push(node.clone()),
+            Some(old) if old.parent != node.parent || old.index != node.index => diff.moved.push(node.clone()),
+            Some(old) if old.node != node.node => diff.changed.push(node.clone()),
+            Some(_) => {}
+        }
+    }
+
+    for id in base_nodes.keys() {
+        if !current_nodes.contains_key(id) {
+            diff.removed.push(id.clone());
+        }
+    }
+
+    Ok(diff)
+}
+
+pub fn apply_diff(base: &Value, diff: &BookmarkDiff) -> Result<Value, String> {
+    let (_, mut nodes) = flatten(base)?;
+
+    for id in &diff.removed {
+        nodes.remove(id);
+    }
+    for node in diff.added.iter().chain(&diff.moved).chain(&diff.changed) {
+        nodes.insert(node.id.clone(), node.clone());
+    }
+
+    let mut children_map: HashMap<&str, BTreeMap<usize, &FlatNode>> = HashMap::new();
+    for node in nodes.values() {
+        children_map.entry(node.parent.as_str()).or_default().insert(node.index, node);
+    }
+
+    fn build(node: &FlatNode, children_map: &HashMap<&str, BTreeMap<usize, &FlatNode>>) -> Value {
+        let mut value = node.node.clone();
+        let is_folder = value.get("type").and_then(|v| v.as_str()) == Some("folder");
+        if is_folder {
+            let children: Vec<Value> = children_map
+                .get(node.id.as_str())
+                .map(|children| children.values().map(|child| build(child, children_map)).collect())
+                .unwrap_or_default();
+            if let Some(object) = value.as_object_mut() {
+                object.insert("children".to_string(), Value::Array(children));
+            }
+        }
+        value
+    }
+
+    let mut result = diff.envelope.clone();
+    let root_ids = diff
+        .envelope
+        .get("roots")
+        .and_then(|v| v.as_object())
+        .ok_or("Ungültige Differenz: \"roots\" fehlt")?;
+
+    let mut roots = serde_json::Map::new();
+    for (key, root_id) in root_ids {
+        let root = root_id
+            .as_str()
+            .and_then(|id| nodes.get(id))
+            .map(|node| build(node, &children_map))
+            .unwrap_or_else(|| root_id.clone());
+        roots.insert(key.clone(), root);
+    }
+
+    if let Some(object) = result.as_object_mut() {
+        object.insert("roots".to_string(), Value::Object(roots));
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,220 @@
+// canonical.rs - Browserunabhängiges JSON-Format für Lesezeichen
+//
+// Dient als Austauschformat für browserübergreifende Wiederherstellung,
+// Zusammenführen und Vergleichen. Jeder Eintrag ist ein Ordner (ohne url)
+// oder ein Lesezeichen; die Reihenfolge der Liste ist die Reihenfolge im Baum.
+use crate::bookmarks::{BookmarkFolder, BookmarkLink, BookmarkNode, BookmarkTree, RootKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const FORMAT_NAME: &str = "browser-bookmark-backup";
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CanonicalRoot {
+    pub title: String,
+    pub kind: Option<RootKind>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CanonicalBookmark {
+    pub id: String,
+    pub title: String,
+    // None bei Ordnern
+    pub url: Option<String>,
+    // Titel der übergeordneten Ordner, beginnend beim Wurzelordner
+    pub folder: Vec<String>,
+    pub added: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub source_browser: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CanonicalBookmarks {
+    pub format: String,
+    pub version: u32,
+    pub exported: DateTime<Utc>,
+    pub roots: Vec<CanonicalRoot>,
+    pub bookmarks: Vec<CanonicalBookmark>,
+}
+
+impl CanonicalBookmarks {
+    pub fn from_tree(tree: &BookmarkTree, source_browser: &str) -> Self {
+        fn visit(nodes: &[BookmarkNode], path: &mut Vec<String>, browser: &str, out: &mut Vec<CanonicalBookmark>) {
+            for node in nodes {
+                match node {
+                    BookmarkNode::Folder(folder) => {
+                        out.push(CanonicalBookmark {
+                            id: folder.id.clone(),
+                            title: folder.title.clone(),
+                            url: None,
+                            folder: path.clone(),
+                            added: folder.added,
+                            modified: folder.modified,
+                            source_browser: browser.to_string(),
+                        });
+                        path.push(folder.title.clone());
+                        visit(&folder.children, path, browser, out);
+                        path.pop();
+                    }
+                    BookmarkNode::Link(link) => out.push(CanonicalBookmark {
+                        id: link.id.clone(),
+                        title: link.title.clone(),
+                        url: Some(link.url.clone()),
+                        folder: path.clone(),
+                        added: link.added,
+                        modified: None,
+                        source_browser: if link.sources.is_empty() { browser.to_string() } else { link.sources.join(", ") },
+                    }),
+                }
+            }
+        }
+
+        let mut bookmarks = Vec::new();
+        for root in &tree.roots {
+            let mut path = vec![root.title.clone()];
+            visit(&root.children, &mut path, source_browser, &mut bookmarks);
+        }
+
+        Self {
+            format: FORMAT_NAME.to_string(),
+            version: FORMAT_VERSION,
+            exported: Utc::now(),
+            roots: tree
+                .roots
+                .iter()
+                .map(|root| CanonicalRoot { title: root.title.clone(), kind: root.root })
+                .collect(),
+            bookmarks,
+        }
+    }
+
+    // Baut den Baum über die Ordnerpfade wieder auf. Die Liste ist in
+    // Baumreihenfolge, ein Ordner steht also vor seinem Inhalt: Ein Eintrag
+    // gehört in den zuletzt angelegten Ordner mit seinem Pfad. So bleiben
+    // gleichnamige Geschwisterordner getrennt. Fehlen Ordnereinträge (z.B. von
+    // anderen Programmen erzeugt), werden sie über den Titel gesucht bzw. angelegt.
+    pub fn to_tree(&self) -> BookmarkTree {
+        // Ort eines Ordners: Index der Wurzel, dann die Indizes der Kinder
+        fn folder_at<'a>(tree: &'a mut BookmarkTree, location: &[usize]) -> &'a mut BookmarkFolder {
+            let mut folder = &mut tree.roots[location[0]];
+            for &index in &location[1..] {
+                folder = match &mut folder.children[index] {
+                    BookmarkNode::Folder(child) => child,
+                    BookmarkNode::Link(_) => unreachable!(),
+                };
+            }
+            folder
+        }
+
+        fn new_folder(title: &str, root: Option<RootKind>) -> BookmarkFolder {
+            BookmarkFolder {
+                id: String::new(),
+                title: title.to_string(),
+                added: None,
+                modified: None,
+                root,
+                children: Vec::new(),
+            }
+        }
+
+        let mut tree = BookmarkTree {
+            roots: self.roots.iter().map(|root| new_folder(&root.title, root.kind)).collect(),
+        };
+        let mut locations: HashMap<Vec<String>, Vec<usize>> = tree.roots
+            .iter()
+            .enumerate()
+            .map(|(index, root)| (vec![root.title.clone()], vec![index]))
+            .collect();
+        // Ohne eigenen Eintrag angelegt, weil ein Lesezeichen darin vor dem Ordner kam
+        let mut implicit: HashSet<Vec<usize>> = HashSet::new();
+
+        for bookmark in &self.bookmarks {
+            let root_title = match bookmark.folder.first() {
+                Some(title) => title,
+                None => continue,
+            };
+            let mut parent = match locations.get(&bookmark.folder[..1]) {
+                Some(location) => location.clone(),
+                None => {
+                    tree.roots.push(new_folder(root_title, None));
+                    let location = vec![tree.roots.len() - 1];
+                    locations.insert(vec![root_title.clone()], location.clone());
+                    location
+                }
+            };
+            for depth in 2..=bookmark.folder.len() {
+                let path = &bookmark.folder[..depth];
+                parent = match locations.get(path) {
+                    Some(location) => location.clone(),
+                    None => {
+                        let children = &mut folder_at(&mut tree, &parent).children;
+                        children.push(BookmarkNode::Folder(new_folder(&path[depth - 1], None)));
+                        let mut location = parent.clone();
+                        location.push(children.len() - 1);
+                        locations.insert(path.to_vec(), location.clone());
+                        implicit.insert(location.clone());
+                        location
+                    }
+                };
+            }
+
+            let mut path = bookmark.folder.clone();
+            path.push(bookmark.title.clone());
+            let folder = folder_at(&mut tree, &parent);
+            match &bookmark.url {
+                Some(url) => folder.children.push(BookmarkNode::Link(BookmarkLink {
+                    id: bookmark.id.clone(),
+                    title: bookmark.title.clone(),
+                    url: url.clone(),
+                    added: bookmark.added,
+                    sources: Vec::new(),
+                })),
+                None => {
+                    // Ein schon über den Pfad eines Eintrags angelegter Ordner
+                    // bekommt nachträglich seine Angaben
+                    let existing = locations.get(&path)
+                        .filter(|location| location[..parent.len()] == parent[..] && implicit.remove(*location));
+                    let index = match existing {
+                        Some(location) => location[parent.len()],
+                        None => {
+                            folder.children.push(BookmarkNode::Folder(new_folder(&bookmark.title, None)));
+                            folder.children.len() - 1
+                        }
+                    };
+                    if let BookmarkNode::Folder(child) = &mut folder.children[index] {
+                        child.id = bookmark.id.clone();
+                        child.added = bookmark.added;
+                        child.modified = bookmark.modified;
+                    }
+                    let mut location = parent;
+                    location.push(index);
+                    locations.insert(path, location);
+                }
+            }
+        }
+
+        tree
+    }
+}
+
+pub fn to_json(tree: &BookmarkTree, source_browser: &str) -> Result<String, String> {
+    serde_json::to_string_pretty(&CanonicalBookmarks::from_tree(tree, source_browser))
+        .map_err(|e| format!("Fehler beim Erstellen des JSON: {}", e))
+}
+
+pub fn from_json(content: &str) -> Result<CanonicalBookmarks, String> {
+    let bookmarks: CanonicalBookmarks = serde_json::from_str(content)
+        .map_err(|e| format!("Ungültige Lesezeichen-Datei: {}", e))?;
+    if bookmarks.format != FORMAT_NAME {
+        return Err(format!("Unbekanntes Format \"{}\"", bookmarks.format));
+    }
+    if bookmarks.version > FORMAT_VERSION {
+        return Err(format!(
+            "Die Datei wurde mit einer neueren Version erstellt (Formatversion {})",
+            bookmarks.version
+        ));
+    }
+    Ok(bookmarks)
+}
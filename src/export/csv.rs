@@ -0,0 +1,42 @@
+// csv.rs - Export als CSV-Tabelle (eine Zeile pro Lesezeichen)
+//
+// Semikolon als Trennzeichen und BOM, damit Excel mit deutschen
+// Ländereinstellungen die Datei ohne Importassistent richtig öffnet.
+use crate::bookmarks::{BookmarkNode, BookmarkTree};
+
+fn field(value: &str) -> String {
+    if value.contains([';', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_nodes(out: &mut String, nodes: &[BookmarkNode], path: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            BookmarkNode::Folder(folder) => {
+                path.push(folder.title.clone());
+                write_nodes(out, &folder.children, path);
+                path.pop();
+            }
+            BookmarkNode::Link(link) => out.push_str(&format!(
+                "{};{};{};{}\r\n",
+                field(&path.join(" / ")),
+                field(&link.title),
+                field(&link.url),
+                link.added
+                    .map(|d| d.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M").to_string())
+                    .unwrap_or_default()
+            )),
+        }
+    }
+}
+
+pub fn to_csv(tree: &BookmarkTree) -> String {
+    let mut out = String::from("\u{feff}Ordner;Titel;URL;Hinzugefügt\r\n");
+    for root in &tree.roots {
+        write_nodes(&mut out, &root.children, &mut vec![root.title.clone()]);
+    }
+    out
+}
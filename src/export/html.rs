@@ -0,0 +1,153 @@
+// html.rs - Seitenvorlage für den HTML-Export
+//
+// Liegt im Backup-Ordner eine export_template.html, wird sie statt der
+// eingebauten Vorlage verwendet. Platzhalter: {{title}}, {{date}}, {{tree}}.
+// Die eingebaute Vorlage bringt Suche und Auf-/Zuklappen per Inline-JS mit,
+// damit die Datei ohne weitere Dateien funktioniert.
+use crate::bookmarks::{BookmarkFolder, BookmarkNode, BookmarkTree};
+use crate::favicons::FaviconMap;
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use std::fs;
+use std::path::Path;
+
+pub const TEMPLATE_FILE: &str = "export_template.html";
+
+pub const DEFAULT_TEMPLATE: &str = "<!DOCTYPE html>
+<html>
+<head>
+    <meta charset=\"UTF-8\">
+    <title>{{title}}</title>
+    <style>
+        body { font-family: Arial, sans-serif; margin: 20px; }
+        ul { list-style-type: none; padding-left: 20px; }
+        li { margin: 5px 0; }
+        a { text-decoration: none; color: #0066cc; }
+        a:hover { text-decoration: underline; }
+        .folder { font-weight: bold; margin: 10px 0; }
+        .root { margin-left: 0; padding-left: 0; }
+        .favicon { vertical-align: middle; }
+        .date, .sources { color: #666; }
+        summary { cursor: pointer; }
+        .toolbar { margin: 10px 0; }
+        .toolbar input { width: 300px; padding: 4px; }
+    </style>
+</head>
+<body>
+    <h1>{{title}}</h1>
+    <p class=\"date\">Exportiert am {{date}}</p>
+    <div class=\"toolbar\">
+        <input type=\"search\" id=\"search\" placeholder=\"Lesezeichen durchsuchen...\" autofocus>
+        <button id=\"expand\">Alle aufklappen</button>
+        <button id=\"collapse\">Alle zuklappen</button>
+        <span id=\"count\"></span>
+    </div>
+{{tree}}
+    <script>
+    (function () {
+        var search = document.getElementById('search');
+        var count = document.getElementById('count');
+        function setOpen(open) {
+            document.querySelectorAll('details').forEach(function (d) { d.open = open; });
+        }
+        document.getElementById('expand').onclick = function () { setOpen(true); };
+        document.getElementById('collapse').onclick = function () { setOpen(false); };
+        search.addEventListener('input', function () {
+            var query = search.value.toLowerCase();
+            var matches = 0;
+            document.querySelectorAll('li.bookmark').forEach(function (item) {
+                var link = item.querySelector('a');
+                var text = (link.textContent + ' ' + link.href).toLowerCase();
+                var visible = !query || text.indexOf(query) !== -1;
+                item.hidden = !visible;
+                if (visible) { matches++; }
+            });
+            // Ordner ohne Treffer ausblenden, Ordner mit Treffern aufklappen
+            document.querySelectorAll('li.folder').forEach(function (folder) {
+                var visible = !query || folder.querySelector('li.bookmark:not([hidden])') !== null;
+                folder.hidden = !visible;
+                if (query && visible) { folder.querySelector('details').open = true; }
+            });
+            count.textContent = query ? matches + ' Treffer' : '';
+        });
+    })();
+    </script>
+</body>
+</html>
+";
+
+// Eigene Vorlage aus dem Backup-Ordner oder die eingebaute
+pub fn load_template(backup_dir: &Path) -> Result<String, String> {
+    let path = backup_dir.join(TEMPLATE_FILE);
+    if !path.exists() {
+        return Ok(DEFAULT_TEMPLATE.to_string());
+    }
+
+    let template = fs::read_to_string(&path)
+        .map_err(|e| format!("Fehler beim Lesen der Vorlage {}: {}", path.display(), e))?;
+    if !template.contains("{{tree}}") {
+        return Err(format!("Die Vorlage {} enthält keinen Platzhalter {{{{tree}}}}", path.display()));
+    }
+    Ok(template)
+}
+
+pub fn render(template: &str, title: &str, date: &str, tree: &str) -> String {
+    // {{tree}} zuletzt ersetzen, damit Lesezeichentitel nicht als Platzhalter gelten
+    template
+        .replace("{{title}}", &encode_text(title))
+        .replace("{{date}}", &encode_text(date))
+        .replace("{{tree}}", tree)
+}
+
+fn favicon_img(favicons: &FaviconMap, url: &str) -> String {
+    favicons
+        .get(url)
+        .map(|uri| format!("<img class=\"favicon\" src=\"{}\" width=\"16\" height=\"16\" alt=\"\"> ", uri))
+        .unwrap_or_default()
+}
+
+fn write_nodes(out: &mut String, nodes: &[BookmarkNode], favicons: &FaviconMap, depth: usize) {
+    let indent = "    ".repeat(depth);
+    for node in nodes {
+        match node {
+            BookmarkNode::Folder(folder) => write_folder(out, folder, favicons, depth),
+            BookmarkNode::Link(link) => {
+                let sources = if link.sources.is_empty() {
+                    String::new()
+                } else {
+                    format!(" <span class=\"sources\">({})</span>", encode_text(&link.sources.join(", ")))
+                };
+                out.push_str(&format!(
+                    "{}<li class=\"bookmark\">{}<a href=\"{}\">{}</a>{}</li>\n",
+                    indent,
+                    favicon_img(favicons, &link.url),
+                    encode_double_quoted_attribute(&link.url),
+                    encode_text(&link.title),
+                    sources
+                ));
+            }
+        }
+    }
+}
+
+// Ordner lassen sich auf- und zuklappen
+fn write_folder(out: &mut String, folder: &BookmarkFolder, favicons: &FaviconMap, depth: usize) {
+    let indent = "    ".repeat(depth);
+    out.push_str(&format!(
+        "{}<li class=\"folder\"><details open><summary>{}</summary>\n{}<ul>\n",
+        indent,
+        encode_text(&folder.title),
+        indent
+    ));
+    write_nodes(out, &folder.children, favicons, depth + 1);
+    out.push_str(&format!("{}</ul>\n{}</details></li>\n", indent, indent));
+}
+
+// Lesezeichenbaum als verschachtelte Liste für den Platzhalter {{tree}}
+pub fn tree_to_html(tree: &BookmarkTree, favicons: &FaviconMap) -> String {
+    let mut out = String::from("<ul class=\"root\">\n");
+    for root in &tree.roots {
+        write_folder(&mut out, root, favicons, 1);
+    }
+    out.push_str("</ul>\n");
+    out
+}
@@ -0,0 +1,59 @@
+// markdown.rs - Export als Markdown-Linkliste
+//
+// Die obersten Ordner (Lesezeichenleiste usw.) werden zu Überschriften,
+// darunter steht eine verschachtelte Liste: Unterordner als fett gesetzter
+// Eintrag mit eingerückten Kindern. So bleibt die Reihenfolge erhalten, wie
+// sie BookmarkLayout vorgibt (sortiert, Ordner zuerst oder wie im Browser).
+use crate::bookmarks::{BookmarkFolder, BookmarkNode, BookmarkTree};
+
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// URLs mit Leerzeichen oder Klammern würden den Link sonst abschneiden
+fn link_target(url: &str) -> String {
+    if url.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn write_items(out: &mut String, nodes: &[BookmarkNode], depth: usize) {
+    let indent = "  ".repeat(depth);
+    for node in nodes {
+        match node {
+            BookmarkNode::Link(link) => {
+                let title = if link.title.is_empty() { &link.url } else { &link.title };
+                out.push_str(&format!("{}- [{}]({})\n", indent, escape_text(title), link_target(&link.url)));
+            }
+            BookmarkNode::Folder(folder) => {
+                out.push_str(&format!("{}- **{}**\n", indent, escape_text(&folder.title)));
+                write_items(out, &folder.children, depth + 1);
+            }
+        }
+    }
+}
+
+fn write_root(out: &mut String, root: &BookmarkFolder) {
+    out.push_str(&format!("## {}\n\n", escape_text(&root.title)));
+    if !root.children.is_empty() {
+        write_items(out, &root.children, 0);
+        out.push('\n');
+    }
+}
+
+pub fn to_markdown(tree: &BookmarkTree, title: &str) -> String {
+    let mut out = format!("# {}\n\n", escape_text(title));
+    for root in &tree.roots {
+        write_root(&mut out, root);
+    }
+    out
+}
@@ -0,0 +1,76 @@
+// export - Exportformate für Lesezeichen
+pub mod netscape;
+pub mod xbel;
+pub mod opml;
+pub mod canonical;
+pub mod html;
+pub mod csv;
+pub mod markdown;
+
+use crate::i18n::tr;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExportFormat {
+    // Übersichtsseite zum Ansehen im Browser
+    Html,
+    // bookmarks.html zum Import in Chrome, Edge oder Firefox
+    NetscapeHtml,
+    Xbel,
+    Opml,
+    // Browserunabhängiges JSON, siehe canonical.rs
+    Canonical,
+    // Firefox-Sicherung für "Wiederherstellen" in der Bibliothek
+    FirefoxJsonlz4,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 8] = [
+        ExportFormat::Html,
+        ExportFormat::NetscapeHtml,
+        ExportFormat::Xbel,
+        ExportFormat::Opml,
+        ExportFormat::Canonical,
+        ExportFormat::FirefoxJsonlz4,
+        ExportFormat::Csv,
+        ExportFormat::Markdown,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Html => tr("HTML-Übersicht"),
+            ExportFormat::NetscapeHtml => tr("HTML für Browser-Import"),
+            ExportFormat::Xbel => "XBEL",
+            ExportFormat::Opml => "OPML",
+            ExportFormat::Canonical => tr("JSON (browserunabhängig)"),
+            ExportFormat::FirefoxJsonlz4 => tr("Firefox-Sicherung (jsonlz4)"),
+            ExportFormat::Csv => tr("CSV-Tabelle"),
+            ExportFormat::Markdown => tr("Markdown-Linkliste"),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Html | ExportFormat::NetscapeHtml => "html",
+            ExportFormat::Xbel => "xbel",
+            ExportFormat::Opml => "opml",
+            ExportFormat::Canonical => "json",
+            ExportFormat::FirefoxJsonlz4 => "jsonlz4",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            ExportFormat::Html
+            | ExportFormat::Xbel
+            | ExportFormat::Opml
+            | ExportFormat::Canonical
+            | ExportFormat::Csv
+            | ExportFormat::Markdown => "bookmarks",
+            ExportFormat::NetscapeHtml | ExportFormat::FirefoxJsonlz4 => "bookmarks_import",
+        }
+    }
+}
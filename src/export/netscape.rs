@@ -0,0 +1,231 @@
+// netscape.rs - Export im Netscape-Bookmark-Format (bookmarks.html)
+//
+// Dieses Format können Chrome, Edge und Firefox direkt importieren.
+use crate::bookmarks::{BookmarkFolder, BookmarkLink, BookmarkNode, BookmarkTree, RootKind};
+use chrono::{DateTime, TimeZone, Utc};
+use html_escape::{decode_html_entities, encode_double_quoted_attribute, encode_text};
+
+fn date_attribute(name: &str, date: Option<DateTime<Utc>>) -> String {
+    date.map(|d| format!(" {}=\"{}\"", name, d.timestamp())).unwrap_or_default()
+}
+
+fn write_folder(out: &mut String, folder: &BookmarkFolder, depth: usize) {
+    let indent = "    ".repeat(depth);
+    out.push_str(&format!(
+        "{}<DT><H3{}{}{}>{}</H3>\n",
+        indent,
+        date_attribute("ADD_DATE", folder.added),
+        date_attribute("LAST_MODIFIED", folder.modified),
+        match folder.root {
+            Some(RootKind::Toolbar) => " PERSONAL_TOOLBAR_FOLDER=\"true\"",
+            Some(RootKind::Other) => " UNFILED_FOLDER=\"true\"",
+            _ => "",
+        },
+        encode_text(&folder.title)
+    ));
+    write_children(out, &folder.children, depth);
+}
+
+fn write_children(out: &mut String, children: &[BookmarkNode], depth: usize) {
+    let indent = "    ".repeat(depth);
+    out.push_str(&format!("{}<DL><p>\n", indent));
+    for child in children {
+        match child {
+            BookmarkNode::Folder(folder) => write_folder(out, folder, depth + 1),
+            BookmarkNode::Link(link) => out.push_str(&format!(
+                "{}    <DT><A HREF=\"{}\"{}>{}</A>\n",
+                indent,
+                encode_double_quoted_attribute(&link.url),
+                date_attribute("ADD_DATE", link.added),
+                encode_text(&link.title)
+            )),
+        }
+    }
+    out.push_str(&format!("{}</DL><p>\n", indent));
+}
+
+pub fn to_netscape_html(tree: &BookmarkTree) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+         <!-- This is an automatically generated file.\n     It will be read and overwritten.\n     DO NOT EDIT! -->\n\
+         <META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n\
+         <TITLE>Bookmarks</TITLE>\n\
+         <H1>Bookmarks</H1>\n",
+    );
+
+    out.push_str("<DL><p>\n");
+    for root in &tree.roots {
+        write_folder(&mut out, root, 1);
+    }
+    out.push_str("</DL><p>\n");
+    out
+}
+
+// Liest NAME="wert"-Paare aus dem Inneren eines Tags; Namen in Großbuchstaben
+fn parse_attributes(tag: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = tag;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].split_whitespace().last().unwrap_or_default().to_ascii_uppercase();
+        let after = rest[eq + 1..].trim_start();
+        let (value, remaining) = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => match after[1..].find(quote) {
+                Some(end) => (&after[1..end + 1], &after[end + 2..]),
+                None => (&after[1..], ""),
+            },
+            _ => {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+        attributes.push((name, decode_html_entities(value).to_string()));
+        rest = remaining;
+    }
+    attributes
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+}
+
+fn attribute_date(attributes: &[(String, String)], name: &str) -> Option<DateTime<Utc>> {
+    let seconds: i64 = attribute(attributes, name)?.trim().parse().ok()?;
+    if seconds <= 0 {
+        return None;
+    }
+    Utc.timestamp_opt(seconds, 0).single()
+}
+
+// Liest eine bookmarks.html, wie sie Chrome, Edge, Firefox und dieses Tool
+// exportieren. Die Lesezeichenleiste (PERSONAL_TOOLBAR_FOLDER) wird zum
+// Wurzelordner "Leiste", alles andere landet unter "Weitere Lesezeichen".
+pub fn from_netscape_html(content: &str) -> Result<BookmarkTree, String> {
+    // Gleiche Byte-Positionen wie `content`, da nur ASCII umgewandelt wird
+    let lower = content.to_ascii_lowercase();
+
+    // Ordner, deren <DL> gerade offen ist; der erste sammelt die oberste Ebene
+    let mut stack = vec![BookmarkFolder {
+        id: String::new(),
+        title: String::new(),
+        added: None,
+        modified: None,
+        root: None,
+        children: Vec::new(),
+    }];
+    // Pro offenem <DL>: ob es zu einem Ordner auf `stack` gehört
+    let mut open_lists: Vec<bool> = Vec::new();
+    // Zuletzt gelesene <H3>-Überschrift, deren <DL> noch aussteht
+    let mut pending: Option<BookmarkFolder> = None;
+    let mut links = 0;
+
+    fn flush(pending: &mut Option<BookmarkFolder>, stack: &mut [BookmarkFolder]) {
+        if let (Some(folder), Some(parent)) = (pending.take(), stack.last_mut()) {
+            parent.children.push(BookmarkNode::Folder(folder));
+        }
+    }
+
+    let mut pos = 0;
+    while let Some(start) = lower[pos..].find('<').map(|i| i + pos) {
+        let end = match lower[start..].find('>') {
+            Some(i) => start + i,
+            None => break,
+        };
+        let tag = &content[start + 1..end];
+        let name = tag.split_whitespace().next().unwrap_or_default().to_ascii_uppercase();
+        pos = end + 1;
+
+        match name.as_str() {
+            "DL" => {
+                let opens_folder = pending.is_some();
+                if let Some(folder) = pending.take() {
+                    stack.push(folder);
+                }
+                open_lists.push(opens_folder);
+            }
+            "/DL" => {
+                flush(&mut pending, &mut stack);
+                if open_lists.pop() == Some(true) && stack.len() > 1 {
+                    let folder = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(BookmarkNode::Folder(folder));
+                }
+            }
+            "H3" => {
+                flush(&mut pending, &mut stack);
+                let close = lower[pos..].find("</h3").map(|i| i + pos).unwrap_or(pos);
+                let attributes = parse_attributes(&tag[2..]);
+                let root = if attribute(&attributes, "PERSONAL_TOOLBAR_FOLDER").is_some() {
+                    Some(RootKind::Toolbar)
+                } else if attribute(&attributes, "UNFILED_FOLDER").is_some() {
+                    Some(RootKind::Other)
+                } else {
+                    None
+                };
+                pending = Some(BookmarkFolder {
+                    id: String::new(),
+                    title: decode_html_entities(content[pos..close].trim()).to_string(),
+                    added: attribute_date(&attributes, "ADD_DATE"),
+                    modified: attribute_date(&attributes, "LAST_MODIFIED"),
+                    root,
+                    children: Vec::new(),
+                });
+                pos = close;
+            }
+            "A" => {
+                flush(&mut pending, &mut stack);
+                let close = lower[pos..].find("</a").map(|i| i + pos).unwrap_or(pos);
+                let attributes = parse_attributes(&tag[1..]);
+                if let (Some(url), Some(parent)) = (attribute(&attributes, "HREF"), stack.last_mut()) {
+                    parent.children.push(BookmarkNode::Link(BookmarkLink {
+                        id: String::new(),
+                        title: decode_html_entities(content[pos..close].trim()).to_string(),
+                        url: url.to_string(),
+                        added: attribute_date(&attributes, "ADD_DATE"),
+                        sources: Vec::new(),
+                    }));
+                    links += 1;
+                }
+                pos = close;
+            }
+            _ => {}
+        }
+    }
+
+    // Nicht geschlossene Listen (fehlerhafte Dateien) trotzdem übernehmen
+    flush(&mut pending, &mut stack);
+    while stack.len() > 1 {
+        let folder = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(BookmarkNode::Folder(folder));
+    }
+
+    if links == 0 {
+        return Err("Keine Lesezeichen in der Datei gefunden".to_string());
+    }
+
+    let mut toolbar: Option<BookmarkFolder> = None;
+    let mut other = BookmarkFolder {
+        id: String::new(),
+        title: "Weitere Lesezeichen".to_string(),
+        added: None,
+        modified: None,
+        root: Some(RootKind::Other),
+        children: Vec::new(),
+    };
+    for node in stack.pop().unwrap().children {
+        match node {
+            BookmarkNode::Folder(folder) if folder.root == Some(RootKind::Toolbar) && toolbar.is_none() => {
+                toolbar = Some(folder);
+            }
+            BookmarkNode::Folder(folder) if folder.root == Some(RootKind::Other) => {
+                other.title = folder.title;
+                other.children.extend(folder.children);
+            }
+            BookmarkNode::Folder(mut folder) => {
+                folder.root = None;
+                other.children.push(BookmarkNode::Folder(folder));
+            }
+            link => other.children.push(link),
+        }
+    }
+
+    Ok(BookmarkTree { roots: toolbar.into_iter().chain(std::iter::once(other)).collect() })
+}
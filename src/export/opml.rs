@@ -0,0 +1,67 @@
+// opml.rs - Export als OPML 2.0 für Feed-Reader und Read-later-Dienste
+use crate::bookmarks::{BookmarkFolder, BookmarkNode, BookmarkTree};
+use chrono::Utc;
+use html_escape::{encode_double_quoted_attribute, encode_text};
+
+// Nur am Aufbau der URL erkennbar; Lesezeichen werden nicht abgerufen
+fn is_feed(url: &str) -> bool {
+    let url = url.to_lowercase();
+    if url.starts_with("feed:") {
+        return true;
+    }
+    let path = url.split(['?', '#']).next().unwrap_or("").trim_end_matches('/');
+    [".rss", ".atom", ".rdf", "/feed", "/rss", "/atom", "/feed.xml", "/rss.xml", "/atom.xml"]
+        .iter()
+        .any(|ending| path.ends_with(ending))
+}
+
+fn write_folder(out: &mut String, folder: &BookmarkFolder, depth: usize) {
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!(
+        "{}<outline text=\"{}\">\n",
+        indent,
+        encode_double_quoted_attribute(&folder.title)
+    ));
+
+    for child in &folder.children {
+        match child {
+            BookmarkNode::Folder(folder) => write_folder(out, folder, depth + 1),
+            BookmarkNode::Link(link) => {
+                // Feed-Reader abonnieren alles mit xmlUrl, daher nur bei Feeds;
+                // Read-later-Dienste lesen htmlUrl bzw. url
+                let url = encode_double_quoted_attribute(&link.url);
+                let attributes = if is_feed(&link.url) {
+                    format!("type=\"rss\" xmlUrl=\"{}\"", url)
+                } else {
+                    format!("type=\"link\" url=\"{}\" htmlUrl=\"{}\"", url, url)
+                };
+                out.push_str(&format!(
+                    "{}  <outline {} text=\"{}\"{}/>\n",
+                    indent,
+                    attributes,
+                    encode_double_quoted_attribute(&link.title),
+                    link.added
+                        .map(|d| format!(" created=\"{}\"", d.to_rfc2822()))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+    }
+
+    out.push_str(&format!("{}</outline>\n", indent));
+}
+
+pub fn to_opml(tree: &BookmarkTree, title: &str) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n");
+    out.push_str("  <head>\n");
+    out.push_str(&format!("    <title>{}</title>\n", encode_text(title)));
+    out.push_str(&format!("    <dateCreated>{}</dateCreated>\n", Utc::now().to_rfc2822()));
+    out.push_str("  </head>\n  <body>\n");
+
+    for root in &tree.roots {
+        write_folder(&mut out, root, 2);
+    }
+
+    out.push_str("  </body>\n</opml>\n");
+    out
+}
@@ -0,0 +1,57 @@
+// xbel.rs - Export im XML Bookmark Exchange Language-Format
+//
+// Wird u.a. von Konqueror, buku und diversen Lesezeichen-Verwaltungen gelesen.
+// Version 1.2, weil erst sie `modified` an Ordnern erlaubt; DOCTYPE und
+// Versionsattribut müssen dazu passen, sonst lehnen validierende Leser ab.
+use crate::bookmarks::{BookmarkFolder, BookmarkNode, BookmarkTree};
+use chrono::{DateTime, SecondsFormat, Utc};
+use html_escape::{encode_double_quoted_attribute, encode_text};
+
+fn date_attribute(name: &str, date: Option<DateTime<Utc>>) -> String {
+    date.map(|d| format!(" {}=\"{}\"", name, d.to_rfc3339_opts(SecondsFormat::Secs, true)))
+        .unwrap_or_default()
+}
+
+fn write_folder(out: &mut String, folder: &BookmarkFolder, depth: usize) {
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!(
+        "{}<folder{}{}>\n",
+        indent,
+        date_attribute("added", folder.added),
+        date_attribute("modified", folder.modified)
+    ));
+    out.push_str(&format!("{}  <title>{}</title>\n", indent, encode_text(&folder.title)));
+
+    for child in &folder.children {
+        match child {
+            BookmarkNode::Folder(folder) => write_folder(out, folder, depth + 1),
+            BookmarkNode::Link(link) => {
+                out.push_str(&format!(
+                    "{}  <bookmark href=\"{}\"{}>\n",
+                    indent,
+                    encode_double_quoted_attribute(&link.url),
+                    date_attribute("added", link.added)
+                ));
+                out.push_str(&format!("{}    <title>{}</title>\n", indent, encode_text(&link.title)));
+                out.push_str(&format!("{}  </bookmark>\n", indent));
+            }
+        }
+    }
+
+    out.push_str(&format!("{}</folder>\n", indent));
+}
+
+pub fn to_xbel(tree: &BookmarkTree) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE xbel PUBLIC \"+//IDN python.org//DTD XML Bookmark Exchange Language 1.2//EN//XML\" \"http://pyxml.sourceforge.net/topics/dtds/xbel-1.2.dtd\">\n\
+         <xbel version=\"1.2\">\n",
+    );
+
+    for root in &tree.roots {
+        write_folder(&mut out, root, 1);
+    }
+
+    out.push_str("</xbel>\n");
+    out
+}
@@ -0,0 +1,88 @@
+// favicons.rs - Favicons aus den Browser-Datenbanken als data:-URIs
+//
+// Chrome/Edge: "Favicons" im Profilordner, Firefox: favicons.sqlite.
+// Die Datenbanken sind gesperrt, solange der Browser läuft, daher wird
+// eine temporäre Kopie gelesen.
+use base64::Engine;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Seiten-URL -> data:-URI
+pub type FaviconMap = HashMap<String, String>;
+
+fn mime_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG") {
+        Some("image/png")
+    } else if data.starts_with(b"\xFF\xD8") {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if data.starts_with(b"\x00\x00\x01\x00") {
+        Some("image/x-icon")
+    } else if data.starts_with(b"<svg") || data.starts_with(b"<?xml") {
+        Some("image/svg+xml")
+    } else {
+        None
+    }
+}
+
+fn to_data_uri(data: &[u8]) -> Option<String> {
+    let mime = mime_type(data)?;
+    Some(format!("data:{};base64,{}", mime, base64::engine::general_purpose::STANDARD.encode(data)))
+}
+
+fn query_icons(db_path: &Path, query: &str) -> Result<FaviconMap, String> {
+    let temp_path = std::env::temp_dir().join(format!("bookmark_backup_favicons_{}.sqlite", std::process::id()));
+    fs::copy(db_path, &temp_path)
+        .map_err(|e| format!("Favicon-Datenbank konnte nicht kopiert werden: {}", e))?;
+
+    let result = (|| {
+        let conn = Connection::open_with_flags(&temp_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Fehler beim Öffnen der Favicon-Datenbank: {}", e))?;
+        let mut stmt = conn.prepare(query)
+            .map_err(|e| format!("Fehler beim Vorbereiten der SQL-Abfrage: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?)))
+            .map_err(|e| format!("Fehler beim Ausführen der SQL-Abfrage: {}", e))?;
+
+        // Die Abfragen sind nach Größe sortiert; das kleinste Icon gewinnt
+        let mut icons = FaviconMap::new();
+        for (page_url, data) in rows.flatten() {
+            if icons.contains_key(&page_url) {
+                continue;
+            }
+            if let Some(uri) = to_data_uri(&data) {
+                icons.insert(page_url, uri);
+            }
+        }
+        Ok(icons)
+    })();
+
+    fs::remove_file(&temp_path).ok();
+    result
+}
+
+pub fn load_chromium(profile_dir: &Path) -> Result<FaviconMap, String> {
+    query_icons(
+        &profile_dir.join("Favicons"),
+        "SELECT m.page_url, b.image_data
+         FROM icon_mapping m
+         JOIN favicon_bitmaps b ON b.icon_id = m.icon_id
+         WHERE b.image_data IS NOT NULL
+         ORDER BY b.width",
+    )
+}
+
+pub fn load_firefox(profile_dir: &Path) -> Result<FaviconMap, String> {
+    query_icons(
+        &profile_dir.join("favicons.sqlite"),
+        "SELECT p.page_url, i.data
+         FROM moz_pages_w_icons p
+         JOIN moz_icons_to_pages ip ON ip.page_id = p.id
+         JOIN moz_icons i ON i.id = ip.icon_id
+         WHERE i.data IS NOT NULL
+         ORDER BY i.width",
+    )
+}
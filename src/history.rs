@@ -0,0 +1,68 @@
+// history.rs - Verlauf aller Backup-Läufe im Backup-Ordner
+// Eine JSON-Zeile pro Lauf, neue Läufe werden nur angehängt.
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::backup_manager::BackupResult;
+use crate::i18n::tr;
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+// Wer den Lauf ausgelöst hat
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RunTrigger {
+    // Knopf im Fenster oder Tastenkürzel
+    Manual,
+    Tray,
+    Scheduled,
+    // Programmstart, Sperren, Herunterfahren, Browser beendet, Favoriten geändert
+    Event,
+    // Kommandozeile, REST-API oder andere Programme
+    External,
+}
+
+impl RunTrigger {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RunTrigger::Manual => tr("Manuell"),
+            RunTrigger::Tray => tr("Tray"),
+            RunTrigger::Scheduled => tr("Zeitplan"),
+            RunTrigger::Event => tr("Ereignis"),
+            RunTrigger::External => tr("Extern"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunRecord {
+    pub finished: DateTime<Local>,
+    pub trigger: RunTrigger,
+    pub results: Vec<BackupResult>,
+}
+
+pub fn append(backup_dir: &Path, record: &RunRecord) -> Result<(), String> {
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Verlauf konnte nicht geschrieben werden: {}", e))?;
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(backup_dir.join(HISTORY_FILE))
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(|e| format!("Verlauf konnte nicht geschrieben werden: {}", e))
+}
+
+// Älteste Läufe zuerst; unlesbare Zeilen (z.B. abgebrochenes Schreiben) werden übersprungen
+pub fn load(backup_dir: &Path) -> Result<Vec<RunRecord>, String> {
+    let path = backup_dir.join(HISTORY_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Verlauf {} nicht lesbar: {}", path.display(), e))?;
+    Ok(content.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
@@ -0,0 +1,92 @@
+// hooks.rs - Eigene Befehle vor und nach jedem Backup-Lauf
+// (z.B. verschlüsselten Container einhängen und danach wegsynchronisieren)
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// So lange wird nach dem Ende der Shell noch auf die restliche Ausgabe gewartet
+const OUTPUT_GRACE: Duration = Duration::from_secs(2);
+
+// Führt den Befehl über die Shell aus; die Variablen kommen zur Umgebung hinzu.
+// Ein Exit-Code ungleich 0 gilt als Fehler. Nach `timeout` wird die Shell
+// beendet, damit ein hängender Befehl nicht alle weiteren Backups aufhält.
+pub fn run(label: &str, command_line: &str, env: &[(&str, String)], timeout: Duration) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        use std::os::windows::process::CommandExt;
+        let mut command = Command::new("cmd");
+        // raw_arg, damit cmd die Anführungszeichen selbst auswertet
+        command.arg("/C").raw_arg(command_line);
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        command
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    };
+    command.envs(env.iter().map(|(name, value)| (*name, value)));
+    command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    log::info!("{}: {}", label, command_line);
+    let mut child = command.spawn()
+        .map_err(|e| format!("{} konnte nicht gestartet werden: {}", label, e))?;
+
+    // Ausgaben nebenher lesen, sonst blockiert der Befehl bei vollem Puffer
+    let read_all = |pipe: Option<Box<dyn Read + Send>>| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut content = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut content).ok();
+            }
+            sender.send(String::from_utf8_lossy(&content).to_string()).ok();
+        });
+        receiver
+    };
+    let stdout = read_all(child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+    let stderr = read_all(child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                child.kill().ok();
+                child.wait().ok();
+                // Nicht auf die Ausgabe warten: von der Shell gestartete Programme
+                // können die Pipes noch offen halten
+                return Err(format!("{} nach {} Sekunden abgebrochen", label, timeout.as_secs()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("{} nicht auswertbar: {}", label, e)),
+        }
+    };
+
+    // Auch nach normalem Ende können Programme wie bei "start /b ..." die Pipes
+    // offen halten, daher nur kurz auf die Ausgabe warten
+    let deadline = Instant::now() + OUTPUT_GRACE;
+    let collect = |output: mpsc::Receiver<String>| output
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .unwrap_or_default();
+    let stdout = collect(stdout);
+    let stderr = collect(stderr);
+    for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+        log::info!("{}: {}", label, line);
+    }
+    for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+        log::warn!("{}: {}", label, line);
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+            Some(line) => Err(format!("{} beendet mit {}: {}", label, status, line.trim())),
+            None => Err(format!("{} beendet mit {}", label, status)),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// hotkey.rs - Systemweites Tastenkürzel für ein sofortiges Backup
+// Ohne Windows gibt es kein Tastenkürzel, die Auswertung bleibt ungenutzt
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+// Werte wie MOD_* und VK_* aus winuser.h, damit das Parsen auch ohne Windows übersetzt
+const MOD_ALT: u32 = 0x0001;
+const MOD_CONTROL: u32 = 0x0002;
+const MOD_SHIFT: u32 = 0x0004;
+const MOD_WIN: u32 = 0x0008;
+const MOD_NOREPEAT: u32 = 0x4000;
+const VK_F1: u32 = 0x70;
+
+// "Strg+Alt+B" bzw. "Ctrl+Alt+B" -> (Modifikatoren, virtueller Tastencode).
+// Erlaubt sind Buchstaben, Ziffern und F1 bis F24 mit mindestens einem Modifikator.
+pub fn parse(text: &str) -> Result<(u32, u32), String> {
+    let mut modifiers = 0;
+    let mut key = None;
+    for part in text.split('+').map(str::trim) {
+        match part.to_ascii_lowercase().as_str() {
+            "strg" | "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "umschalt" | "shift" => modifiers |= MOD_SHIFT,
+            "win" => modifiers |= MOD_WIN,
+            other => {
+                if key.is_some() {
+                    return Err(format!("Mehr als eine Taste in \"{}\"", text));
+                }
+                key = Some(virtual_key(other).ok_or_else(|| format!("Unbekannte Taste \"{}\"", part))?);
+            }
+        }
+    }
+
+    let key = key.ok_or_else(|| format!("Keine Taste in \"{}\"", text))?;
+    if modifiers == 0 {
+        return Err("Mindestens Strg, Alt, Umschalt oder Win ist nötig".to_string());
+    }
+    Ok((modifiers, key))
+}
+
+fn virtual_key(name: &str) -> Option<u32> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c.to_ascii_uppercase() as u32),
+        (Some('f'), Some(_)) => match name[1..].parse::<u32>() {
+            Ok(n @ 1..=24) => Some(VK_F1 + n - 1),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Registriert das Tastenkürzel in einem eigenen Thread; `on_press` läuft dort
+// und sollte längere Arbeit selbst auslagern. Änderungen gelten nach einem Neustart.
+#[cfg(target_os = "windows")]
+pub fn start<F>(text: &str, on_press: F) -> Result<(), String>
+where
+    F: Fn() + Send + 'static,
+{
+    use std::sync::mpsc;
+    use winapi::um::winuser::{GetMessageW, RegisterHotKey, MSG, WM_HOTKEY};
+
+    let (modifiers, key) = parse(text)?;
+    let (sender, receiver) = mpsc::channel();
+    let text = text.to_string();
+    std::thread::spawn(move || unsafe {
+        // Ohne Fenster landet WM_HOTKEY in der Nachrichtenschlange dieses Threads
+        if RegisterHotKey(std::ptr::null_mut(), 1, (modifiers | MOD_NOREPEAT) as _, key) == 0 {
+            sender.send(Err(format!("Tastenkürzel {} ist bereits belegt", text))).ok();
+            return;
+        }
+        sender.send(Ok(())).ok();
+
+        let mut msg: MSG = std::mem::zeroed();
+        while GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) > 0 {
+            if msg.message == WM_HOTKEY {
+                on_press();
+            }
+        }
+    });
+    receiver.recv().unwrap_or_else(|_| Err("Tastenkürzel-Thread beendet".to_string()))
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start<F>(text: &str, _on_press: F) -> Result<(), String>
+where
+    F: Fn() + Send + 'static,
+{
+    // Not implemented for non-Windows platforms
+    parse(text).map(|_| ())
+}
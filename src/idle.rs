@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+// Zeit seit der letzten Tastatur- oder Mauseingabe
+#[cfg(target_os = "windows")]
+pub fn idle_time() -> Option<Duration> {
+    use winapi::um::sysinfoapi::GetTickCount;
+    use winapi::um::winuser::{GetLastInputInfo, LASTINPUTINFO};
+    
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    
+    unsafe {
+        if GetLastInputInfo(&mut info) == 0 {
+            return None;
+        }
+        // Beide Werte laufen nach ~49 Tagen über, daher wrapping_sub
+        let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+        Some(Duration::from_millis(idle_ms as u64))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn idle_time() -> Option<Duration> {
+    // Not implemented for non-Windows platforms
+    None
+}
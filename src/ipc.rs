@@ -0,0 +1,175 @@
+// ipc.rs - Steuerung der laufenden Instanz über eine Named Pipe
+// Protokoll: eine Zeile mit dem Befehl, Antwort ist eine Zeile JSON.
+// Ohne Windows gibt es keinen Server, die Typen bleiben teilweise ungenutzt
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Pro Anmeldesitzung, wie der Mutex in single_instance.rs. Sonst erreichen
+// Befehle anderer angemeldeter Benutzer diese Instanz bzw. deren Instanz.
+#[cfg(target_os = "windows")]
+fn pipe_name() -> String {
+    use winapi::um::processthreadsapi::{GetCurrentProcessId, ProcessIdToSessionId};
+
+    let mut session = 0;
+    unsafe {
+        ProcessIdToSessionId(GetCurrentProcessId(), &mut session);
+    }
+    format!(r"\\.\pipe\BrowserFavoritenBackup-{}", session)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Request {
+    BackupNow,
+    ShowWindow,
+    // Fenster mit der Wiederherstellung anzeigen
+    ShowRestore,
+    Status,
+}
+
+impl Request {
+    pub fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "backup-now" => Some(Request::BackupNow),
+            "show-window" => Some(Request::ShowWindow),
+            "show-restore" => Some(Request::ShowRestore),
+            "status" => Some(Request::Status),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Request::BackupNow => "backup-now",
+            Request::ShowWindow => "show-window",
+            Request::ShowRestore => "show-restore",
+            Request::Status => "status",
+        }
+    }
+}
+
+// Antwort auf "status"
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InstanceStatus {
+    pub paused: bool,
+    pub next_backup: Option<chrono::DateTime<Local>>,
+    pub last_backup: Option<chrono::DateTime<Local>>,
+}
+
+// Startet einen Thread, der nacheinander Verbindungen annimmt. Der Handler
+// läuft in diesem Thread; ein Backup blockiert also weitere Befehle.
+#[cfg(target_os = "windows")]
+pub fn start_server<F>(handler: F)
+where
+    F: Fn(Request) -> Value + Send + 'static,
+{
+    std::thread::spawn(move || {
+        use std::os::windows::io::FromRawHandle;
+        use winapi::shared::winerror::ERROR_PIPE_CONNECTED;
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::fileapi::FlushFileBuffers;
+        use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+        use winapi::um::namedpipeapi::{ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe};
+        use winapi::um::winbase::{
+            FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX, PIPE_READMODE_BYTE,
+            PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_WAIT,
+        };
+
+        let pipe_name = pipe_name();
+        let name: Vec<u16> = format!("{}\0", pipe_name).encode_utf16().collect();
+        loop {
+            // Immer nur eine Instanz, die vorige ist am Ende der Schleife geschlossen.
+            // Gehört der Name schon einem anderen Prozess, schlägt das fehl,
+            // statt dass sich beide die Verbindungen teilen.
+            let handle = unsafe {
+                CreateNamedPipeW(
+                    name.as_ptr(),
+                    PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE,
+                    PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                    1,
+                    4096,
+                    4096,
+                    0,
+                    std::ptr::null_mut(),
+                )
+            };
+            if handle == INVALID_HANDLE_VALUE {
+                log::error!("Named Pipe {} konnte nicht erstellt werden", pipe_name);
+                return;
+            }
+
+            // Hat sich der Client schon vor ConnectNamedPipe verbunden, ist das kein Fehler
+            let connected = unsafe {
+                ConnectNamedPipe(handle, std::ptr::null_mut()) != 0 || GetLastError() == ERROR_PIPE_CONNECTED
+            };
+            let pipe = unsafe { std::fs::File::from_raw_handle(handle as _) };
+            if connected {
+                if let Err(e) = serve(&pipe, &handler) {
+                    log::error!("Fehler bei Pipe-Befehl: {}", e);
+                }
+                unsafe {
+                    // Warten, bis der Client die Antwort gelesen hat
+                    FlushFileBuffers(handle);
+                    DisconnectNamedPipe(handle);
+                }
+            }
+            // Das Schließen übernimmt `pipe`
+        }
+    });
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start_server<F>(_handler: F)
+where
+    F: Fn(Request) -> Value + Send + 'static,
+{
+    // Not implemented for non-Windows platforms
+}
+
+#[cfg(target_os = "windows")]
+fn serve<F>(mut pipe: &std::fs::File, handler: &F) -> std::io::Result<()>
+where
+    F: Fn(Request) -> Value,
+{
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut line = String::new();
+    BufReader::new(pipe).read_line(&mut line)?;
+    let response = match Request::parse(&line) {
+        Some(request) => handler(request),
+        None => serde_json::json!({ "error": format!("Unbekannter Befehl: {}", line.trim()) }),
+    };
+    writeln!(pipe, "{}", response)
+}
+
+// Schickt einen Befehl an die laufende Instanz. Ein Fehler bedeutet meist,
+// dass keine Instanz läuft.
+#[cfg(target_os = "windows")]
+pub fn send(request: Request) -> Result<Value, String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let mut pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(pipe_name())
+        .map_err(|e| format!("Keine laufende Instanz erreichbar: {}", e))?;
+    writeln!(pipe, "{}", request.as_str())
+        .map_err(|e| format!("Fehler beim Senden an die laufende Instanz: {}", e))?;
+
+    let mut line = String::new();
+    BufReader::new(&pipe).read_line(&mut line)
+        .map_err(|e| format!("Fehler beim Lesen der Antwort: {}", e))?;
+    let response: Value = serde_json::from_str(&line)
+        .map_err(|e| format!("Ungültige Antwort der laufenden Instanz: {}", e))?;
+    match response.get("error").and_then(Value::as_str) {
+        Some(error) => Err(error.to_string()),
+        None => Ok(response),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn send(_request: Request) -> Result<Value, String> {
+    // Not implemented for non-Windows platforms
+    Err("Keine laufende Instanz erreichbar".to_string())
+}
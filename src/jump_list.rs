@@ -0,0 +1,196 @@
+// jump_list.rs - Aufgaben im Kontextmenü des Taskleisten-Symbols
+// Jede Aufgabe startet `bbb` mit einem Befehl, den die laufende Instanz ausführt.
+// Ohne Windows gibt es keine Sprungliste, die Aufgaben bleiben ungenutzt
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+// Titel (wird übersetzt) und Argumente für `bbb`
+const TASKS: [(&str, &str); 3] = [
+    ("Backup jetzt", "backup"),
+    ("Backup-Ordner öffnen", "folder"),
+    ("Wiederherstellen", "show --restore"),
+];
+
+// Sprunglisten-Schnittstellen aus objectarray.h und shobjidl_core.h, die winapi 0.3 fehlen
+#[cfg(target_os = "windows")]
+#[allow(non_snake_case)]
+mod com {
+    use winapi::ctypes::c_void;
+    use winapi::shared::guiddef::REFIID;
+    use winapi::shared::minwindef::UINT;
+    use winapi::shared::winerror::HRESULT;
+    use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+    use winapi::um::winnt::LPCWSTR;
+    use winapi::RIDL;
+
+    RIDL!{#[uuid(0x92ca9dcd, 0x5622, 0x4bba, 0xa8, 0x05, 0x5e, 0x9f, 0x54, 0x1b, 0xd8, 0xc9)]
+    interface IObjectArray(IObjectArrayVtbl): IUnknown(IUnknownVtbl) {
+        fn GetCount(
+            pcObjects: *mut UINT,
+        ) -> HRESULT,
+        fn GetAt(
+            uiIndex: UINT,
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+    }}
+    RIDL!{#[uuid(0x5632b1a4, 0xe38a, 0x400a, 0x92, 0x8a, 0xd4, 0xcd, 0x63, 0x23, 0x02, 0x95)]
+    interface IObjectCollection(IObjectCollectionVtbl): IObjectArray(IObjectArrayVtbl) {
+        fn AddObject(
+            punk: *mut IUnknown,
+        ) -> HRESULT,
+        fn AddFromArray(
+            poaSource: *mut IObjectArray,
+        ) -> HRESULT,
+        fn RemoveObjectAt(
+            uiIndex: UINT,
+        ) -> HRESULT,
+        fn Clear() -> HRESULT,
+    }}
+    RIDL!{#[uuid(0x6332debf, 0x87b5, 0x4670, 0x90, 0xc0, 0x5e, 0x57, 0xb4, 0x08, 0xa4, 0x9e)]
+    interface ICustomDestinationList(ICustomDestinationListVtbl): IUnknown(IUnknownVtbl) {
+        fn SetAppID(
+            pszAppID: LPCWSTR,
+        ) -> HRESULT,
+        fn BeginList(
+            pcMinSlots: *mut UINT,
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+        fn AppendCategory(
+            pszCategory: LPCWSTR,
+            poa: *mut IObjectArray,
+        ) -> HRESULT,
+        fn AppendKnownCategory(
+            category: i32,
+        ) -> HRESULT,
+        fn AddUserTasks(
+            poa: *mut IObjectArray,
+        ) -> HRESULT,
+        fn CommitList() -> HRESULT,
+        fn GetRemovedDestinations(
+            riid: REFIID,
+            ppv: *mut *mut c_void,
+        ) -> HRESULT,
+        fn DeleteList(
+            pszAppID: LPCWSTR,
+        ) -> HRESULT,
+        fn AbortList() -> HRESULT,
+    }}
+    RIDL!{#[uuid(0x77f10cf0, 0x3db5, 0x4966, 0xb5, 0x20, 0xb7, 0xc5, 0x4f, 0xd3, 0x5e, 0xd6)]
+    class DestinationList;}
+    RIDL!{#[uuid(0x2d3468c1, 0x36a7, 0x43b6, 0xac, 0x24, 0xd3, 0xf0, 0x2f, 0xd9, 0x60, 0x7a)]
+    class EnumerableObjectCollection;}
+}
+
+// Einmal beim Start; die Titel erscheinen in der Sprache zu diesem Zeitpunkt
+#[cfg(target_os = "windows")]
+pub fn register() -> Result<(), String> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr;
+    use winapi::shared::guiddef::GUID;
+    use winapi::shared::winerror::{FAILED, HRESULT};
+    use winapi::shared::wtypes::{PROPERTYKEY, VT_LPWSTR};
+    use winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER;
+    use winapi::um::combaseapi::{CoCreateInstance, CoInitializeEx};
+    use winapi::um::objbase::COINIT_APARTMENTTHREADED;
+    use winapi::um::propidl::PROPVARIANT;
+    use winapi::um::propsys::IPropertyStore;
+    use winapi::um::shobjidl_core::{IShellLinkW, ShellLink};
+    use winapi::um::unknwnbase::IUnknown;
+    use winapi::{Class, Interface};
+    use crate::i18n::tr;
+    use com::{DestinationList, EnumerableObjectCollection, ICustomDestinationList, IObjectArray, IObjectCollection};
+
+    // PKEY_Title aus propkey.h
+    const PKEY_TITLE: PROPERTYKEY = PROPERTYKEY {
+        fmtid: GUID {
+            Data1: 0xF29F85E0,
+            Data2: 0x4FF9,
+            Data3: 0x1068,
+            Data4: [0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9],
+        },
+        pid: 2,
+    };
+
+    // Gibt die Schnittstelle beim Verlassen wieder frei, auch bei Fehlern
+    struct Com<T: Interface>(*mut T);
+
+    impl<T: Interface> Drop for Com<T> {
+        fn drop(&mut self) {
+            if !self.0.is_null() {
+                unsafe { (*(self.0 as *mut IUnknown)).Release() };
+            }
+        }
+    }
+
+    impl<T: Interface> std::ops::Deref for Com<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { &*self.0 }
+        }
+    }
+
+    fn check(hr: HRESULT, what: &str) -> Result<(), String> {
+        if FAILED(hr) {
+            Err(format!("{} fehlgeschlagen: 0x{:08X}", what, hr))
+        } else {
+            Ok(())
+        }
+    }
+
+    unsafe fn create<C: Class, T: Interface>(what: &str) -> Result<Com<T>, String> {
+        let mut object: *mut T = ptr::null_mut();
+        let hr = CoCreateInstance(&C::uuidof(), ptr::null_mut(), CLSCTX_INPROC_SERVER, &T::uuidof(), &mut object as *mut _ as *mut _);
+        check(hr, what)?;
+        Ok(Com(object))
+    }
+
+    fn wide(text: &OsStr) -> Vec<u16> {
+        text.encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    let exe = std::env::current_exe().map_err(|e| format!("Programmpfad unbekannt: {}", e))?;
+    let exe = wide(exe.as_os_str());
+    unsafe {
+        // Schon initialisiert (S_FALSE) ist hier auch in Ordnung
+        CoInitializeEx(ptr::null_mut(), COINIT_APARTMENTTHREADED);
+
+        let list: Com<ICustomDestinationList> = create::<DestinationList, _>("Sprungliste anlegen")?;
+        let mut max_slots = 0;
+        let mut removed: *mut IObjectArray = ptr::null_mut();
+        check(list.BeginList(&mut max_slots, &IObjectArray::uuidof(), &mut removed as *mut _ as *mut _), "Sprungliste beginnen")?;
+        drop(Com(removed));
+
+        let tasks: Com<IObjectCollection> = create::<EnumerableObjectCollection, _>("Aufgabenliste anlegen")?;
+        for (title, arguments) in TASKS {
+            let link: Com<IShellLinkW> = create::<ShellLink, _>("Verknüpfung anlegen")?;
+            check(link.SetPath(exe.as_ptr()), "Verknüpfung anlegen")?;
+            check(link.SetArguments(wide(OsStr::new(arguments)).as_ptr()), "Verknüpfung anlegen")?;
+            check(link.SetIconLocation(exe.as_ptr(), 0), "Verknüpfung anlegen")?;
+
+            // Der Titel ist eine Eigenschaft der Verknüpfung, nicht ihr Name
+            let mut store: *mut IPropertyStore = ptr::null_mut();
+            check(link.QueryInterface(&IPropertyStore::uuidof(), &mut store as *mut _ as *mut _), "Titel setzen")?;
+            let store = Com(store);
+            let mut title = wide(OsStr::new(tr(title)));
+            let mut value: PROPVARIANT = std::mem::zeroed();
+            value.vt = VT_LPWSTR as u16;
+            *value.data.pwszVal_mut() = title.as_mut_ptr();
+            check(store.SetValue(&PKEY_TITLE, &value), "Titel setzen")?;
+            check(store.Commit(), "Titel setzen")?;
+
+            check(tasks.AddObject(link.0 as *mut IUnknown), "Aufgabe hinzufügen")?;
+        }
+
+        check(list.AddUserTasks(tasks.0 as *mut IObjectArray), "Aufgaben hinzufügen")?;
+        check(list.CommitList(), "Sprungliste speichern")
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn register() -> Result<(), String> {
+    // Not implemented for non-Windows platforms
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// logging.rs - Protokolldatei für alle Meldungen, zusätzlich auf der Konsole
+// Eine Zeile pro Meldung: "2024-01-31 02:00:00 ERROR Text"
+use chrono::{Local, NaiveDate};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Ab dieser Größe wird beim Start eine neue Datei begonnen
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+struct FileLogger {
+    file: Mutex<Option<File>>,
+}
+
+static LOGGER: FileLogger = FileLogger { file: Mutex::new(None) };
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            Level::Error | Level::Warn => eprintln!("{}", record.args()),
+            _ => println!("{}", record.args()),
+        }
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let line = format!("{} {:<5} {}\n", Local::now().format("%Y-%m-%d %H:%M:%S"), record.level(), record.args());
+            file.write_all(line.as_bytes()).ok();
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            file.flush().ok();
+        }
+    }
+}
+
+pub fn log_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("BrowserBackup")
+        .join("browser_backup.log")
+}
+
+// Einmal beim Start, vor der ersten Meldung. Ist die Datei nicht beschreibbar,
+// gehen die Meldungen nur auf die Konsole.
+pub fn init() {
+    let path = log_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_SIZE) {
+        fs::rename(&path, path.with_extension("old.log")).ok();
+    }
+    let file = OpenOptions::new().create(true).append(true).open(&path);
+    if let Err(e) = &file {
+        eprintln!("Protokolldatei {} kann nicht geöffnet werden: {}", path.display(), e);
+    }
+
+    *LOGGER.file.lock().unwrap() = file.ok();
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+}
+
+pub struct LogLine {
+    pub level: Level,
+    pub text: String,
+}
+
+// Liest nur das Ende der Datei, die älteren Einträge interessieren in der Ansicht nicht
+pub fn read_tail(max_bytes: u64) -> Result<Vec<LogLine>, String> {
+    let path = log_path();
+    let mut file = File::open(&path)
+        .map_err(|e| format!("Protokoll {} nicht lesbar: {}", path.display(), e))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))
+        .map_err(|e| format!("Protokoll nicht lesbar: {}", e))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("Protokoll nicht lesbar: {}", e))?;
+
+    let content = String::from_utf8_lossy(&bytes);
+    let mut lines = content.lines();
+    // Mitten in der Datei begonnen: die erste Zeile ist abgeschnitten
+    if start > 0 {
+        lines.next();
+    }
+    // Fortsetzungszeilen mehrzeiliger Meldungen gehören zur Stufe davor
+    let mut level = Level::Info;
+    Ok(lines
+        .map(|line| {
+            let mut words = line.split_whitespace();
+            let dated = words.next().is_some_and(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok());
+            if let Some(parsed) = words.nth(1).filter(|_| dated).and_then(|l| l.parse().ok()) {
+                level = parsed;
+            }
+            LogLine { level, text: line.to_string() }
+        })
+        .collect())
+}
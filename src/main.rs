@@ -3,33 +3,174 @@ use eframe::egui;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use tray_icon::{
-    menu::{Menu, MenuEvent, MenuItem},
-    TrayIcon, TrayIconBuilder,
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
+    ClickType, TrayIconBuilder, TrayIconEvent,
 };
 
 mod backup_manager;
 mod ui;
 mod autostart;
+mod removable_drive;
+mod ntfs_compression;
+mod differential;
+mod storage;
+mod repository_format;
+mod schedule;
+mod session_events;
+mod catalog;
+mod browser_processes;
+mod idle;
+mod scheduler;
+mod bookmarks;
+mod export;
+mod favicons;
+mod mozlz4;
+mod md5;
+mod archive;
+mod destinations;
+mod proxy;
+mod cli;
+mod single_instance;
+mod ipc;
+mod rest_api;
+mod hooks;
+mod i18n;
+mod theme;
+mod logging;
+mod hotkey;
+mod history;
+mod cancel;
+mod jump_list;
+mod notification;
 
-use backup_manager::BackupManager;
+use backup_manager::{BackupManager, BackupResult};
+use clap::Parser;
+use history::RunTrigger;
+use i18n::tr;
+use notification::NotificationKind;
+use scheduler::Scheduler;
 use ui::{BackupApp, AppMessage};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    logging::init();
+    
+    // Mit Argumenten als Kommandozeilenprogramm ohne GUI und Tray
+    if std::env::args_os().len() > 1 {
+        std::process::exit(cli::run(cli::Cli::parse()));
+    }
+    
+    // Ein zweiter Start holt nur das vorhandene Fenster nach vorne
+    let _instance = match single_instance::acquire() {
+        Some(guard) => guard,
+        None => {
+            if !single_instance::focus_existing_window(WINDOW_TITLE) && ipc::send(ipc::Request::ShowWindow).is_err() {
+                log::warn!("Browser Favoriten Backup läuft bereits");
+            }
+            return Ok(());
+        }
+    };
+    
+    // "Backup jetzt" usw. im Kontextmenü des Taskleisten-Symbols
+    if let Err(e) = jump_list::register() {
+        log::warn!("Sprungliste nicht eingerichtet: {}", e);
+    }
+    
     // Shared state zwischen Tray und GUI
     let app_state = Arc::new(Mutex::new(AppState::default()));
     let app_state_tray = app_state.clone();
     
     // Shared BackupManager instance
     let backup_manager = Arc::new(Mutex::new(BackupManager::new()));
+    // Vor Tray und Fenster, damit beide gleich in der richtigen Sprache erscheinen
+    i18n::set_language(backup_manager.lock().unwrap().get_config().language);
     let backup_manager_tray = backup_manager.clone();
     
     // Start scheduled backups
-    BackupManager::start_scheduled_backups(backup_manager.clone(), 24);
+    let scheduler = Arc::new(Scheduler::start(backup_manager.clone()));
+    let scheduler_tray = scheduler.clone();
+    BackupManager::start_startup_backup(backup_manager.clone());
+    BackupManager::start_file_watcher(backup_manager.clone());
+    BackupManager::start_browser_exit_watcher(backup_manager.clone());
+    
+    // Backup beim Sperren der Arbeitsstation und beim Abmelden/Herunterfahren
+    let backup_manager_session = backup_manager.clone();
+    session_events::start_listener(move |event| match event {
+        session_events::SessionEvent::Locked => {
+            let backup_manager = backup_manager_session.clone();
+            thread::spawn(move || {
+                if let Some(manager) = BackupManager::snapshot(&backup_manager) {
+                    if manager.get_config().backup_on_lock && !manager.is_paused() {
+                        let results = manager.backup_all(RunTrigger::Event);
+                        log::info!("Backup beim Sperren durchgeführt: {:?}", results);
+                    }
+                }
+            });
+        }
+        session_events::SessionEvent::Ending => {
+            // Hier blockierend warten, sonst beendet Windows den Prozess vorher
+            let (enabled, timeout) = match backup_manager_session.try_lock() {
+                Ok(manager) => (
+                    manager.get_config().backup_on_shutdown,
+                    manager.get_config().shutdown_backup_timeout_secs,
+                ),
+                // Läuft gerade ein Backup, nicht zusätzlich warten
+                Err(_) => (false, 0),
+            };
+            if enabled {
+                match BackupManager::backup_with_timeout(backup_manager_session.clone(), std::time::Duration::from_secs(timeout)) {
+                    Some(results) => log::info!("Backup beim Herunterfahren durchgeführt: {:?}", results),
+                    None => log::error!("Backup beim Herunterfahren nach {} Sekunden abgebrochen", timeout),
+                }
+            }
+        }
+    });
+    BackupManager::start_removable_drive_watcher(backup_manager.clone());
+    
+    // Befehle von `bbb` oder anderen Programmen an diese Instanz
+    let backup_manager_ipc = backup_manager.clone();
+    let app_state_ipc = app_state.clone();
+    rest_api::start(backup_manager.clone());
+    ipc::start_server(move |request| match request {
+        ipc::Request::BackupNow => {
+            let manager = backup_manager_ipc.lock().unwrap().clone();
+            let results = manager.backup_all(RunTrigger::External);
+            serde_json::json!(results)
+        }
+        ipc::Request::ShowWindow => {
+            serde_json::json!({ "shown": show_window(&app_state_ipc, None) })
+        }
+        ipc::Request::ShowRestore => {
+            serde_json::json!({ "shown": show_window(&app_state_ipc, Some(AppMessage::ShowRestore)) })
+        }
+        ipc::Request::Status => {
+            let manager = backup_manager_ipc.lock().unwrap();
+            serde_json::json!(ipc::InstanceStatus {
+                paused: manager.is_paused(),
+                next_backup: manager.next_scheduled_backup(),
+                last_backup: manager.last_successful_backup(),
+            })
+        }
+    });
+    
+    // Tastenkürzel für ein sofortiges Backup, das Ergebnis erscheint als Meldung im Fenster
+    let backup_hotkey = backup_manager.lock().unwrap().get_config().backup_hotkey.clone();
+    if !backup_hotkey.trim().is_empty() {
+        let backup_manager_hotkey = backup_manager.clone();
+        let app_state_hotkey = app_state.clone();
+        let registered = hotkey::start(&backup_hotkey, move || {
+            spawn_backup(backup_manager_hotkey.clone(), app_state_hotkey.clone(), None, RunTrigger::Manual);
+        });
+        if let Err(e) = registered {
+            log::error!("{}", e);
+        }
+    }
     
     // Tray Icon in separatem Thread
     thread::spawn(move || {
-        if let Err(e) = run_tray(app_state_tray, backup_manager_tray) {
-            eprintln!("Tray error: {}", e);
+        match run_tray(app_state_tray, backup_manager_tray, scheduler_tray) {
+            // "Beenden" im Tray-Menü beendet das ganze Programm
+            Ok(()) => std::process::exit(0),
+            Err(e) => log::error!("Tray error: {}", e),
         }
     });
     
@@ -42,40 +183,176 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ..Default::default()
     };
     
+    let scheduler_ui = scheduler.clone();
     eframe::run_native(
-        "Browser Favoriten Backup",
+        WINDOW_TITLE,
         options,
         Box::new(move |cc| {
-            Box::new(BackupApp::new(cc, app_state.clone(), backup_manager.clone()))
+            Box::new(BackupApp::new(cc, app_state.clone(), backup_manager.clone(), scheduler_ui.clone()))
         }),
     )?;
     
+    scheduler.shutdown();
     Ok(())
 }
 
+const WINDOW_TITLE: &str = "Browser Favoriten Backup";
+
 #[derive(Default)]
 struct AppState {
-    show_window: bool,
+    // Vom Tray angefordert: Fenster zeigen (true) oder verstecken, setzt BackupApp um
+    show_window: Option<bool>,
+    // Von BackupApp gepflegt, damit ein Klick aufs Tray-Icon weiß, was er umschaltet
+    window_visible: bool,
     message_queue: Vec<AppMessage>,
+    // Ohne Tray-Icon beendet das Schließen des Fensters das Programm wie bisher
+    tray_available: bool,
+    // Zum Neuzeichnen, wenn andere Threads Nachrichten einreihen
+    egui_ctx: Option<egui::Context>,
+}
+
+impl AppState {
+    fn request_window(&mut self, visible: bool) {
+        self.show_window = Some(visible);
+        if let Some(ctx) = &self.egui_ctx {
+            ctx.request_repaint();
+        }
+    }
+}
+
+// Fenster anzeigen und optional gleich eine Ansicht öffnen
+fn show_window(app_state: &Mutex<AppState>, message: Option<AppMessage>) -> bool {
+    let mut state = app_state.lock().unwrap();
+    state.message_queue.extend(message);
+    state.request_window(true);
+    drop(state);
+    // Ein verstecktes Fenster zeichnet eframe nicht neu, daher auch direkt über Windows
+    single_instance::focus_existing_window(WINDOW_TITLE)
+}
+
+// Eintrag unter "Letzte Backups"; ohne Datei ist es der Platzhalter "Keine Backups"
+struct RecentBackup {
+    item: MenuItem,
+    browser: &'static str,
+    path: Option<std::path::PathBuf>,
+}
+
+// Ersetzt die Einträge unter "Letzte Backups" durch die aktuell neuesten
+fn fill_recent_backups(submenus: &[(&'static str, Submenu)], old: Vec<RecentBackup>, manager: &BackupManager) -> Vec<RecentBackup> {
+    for entry in old {
+        if let Some((_, submenu)) = submenus.iter().find(|(browser, _)| *browser == entry.browser) {
+            submenu.remove(&entry.item).ok();
+        }
+    }
+    
+    let mut entries = Vec::new();
+    for (browser, submenu) in submenus {
+        let mut backups = manager.get_backup_list(browser);
+        backups.sort_by_key(|b| std::cmp::Reverse(b.date));
+        let mut recent: Vec<RecentBackup> = backups.into_iter()
+            .take(3)
+            .map(|backup| RecentBackup {
+                item: MenuItem::new(backup.date.format("%d.%m.%Y %H:%M").to_string(), true, None),
+                browser,
+                path: Some(backup.path),
+            })
+            .collect();
+        if recent.is_empty() {
+            recent.push(RecentBackup { item: MenuItem::new(tr("Keine Backups"), false, None), browser, path: None });
+        }
+        for entry in &recent {
+            submenu.append(&entry.item).ok();
+        }
+        entries.extend(recent);
+    }
+    entries
 }
 
-fn run_tray(app_state: Arc<Mutex<AppState>>, backup_manager: Arc<Mutex<BackupManager>>) -> Result<(), Box<dyn std::error::Error>> {
+// Backup außerhalb der GUI (Tray, Tastenkürzel) in eigenem Thread, damit Tray-Menü und
+// Tastenkürzel weiter reagieren. Das Ergebnis erscheint als Meldung im Fenster, bei
+// verstecktem Fenster als Sprechblase im Infobereich; Tray-Icon und Tooltip zeigen es auch.
+fn spawn_backup(backup_manager: Arc<Mutex<BackupManager>>, app_state: Arc<Mutex<AppState>>, browser: Option<&'static str>, trigger: RunTrigger) {
+    thread::spawn(move || {
+        let results = {
+            let manager = backup_manager.lock().unwrap().clone();
+            match browser {
+                Some(browser) => manager.backup_browsers(&[browser], trigger),
+                None => manager.backup_all(trigger),
+            }
+        };
+        let mut state = app_state.lock().unwrap();
+        if !state.window_visible {
+            notify_backup_results(&results);
+        }
+        state.message_queue.push(AppMessage::BackupFinished(results));
+        if let Some(ctx) = &state.egui_ctx {
+            ctx.request_repaint();
+        }
+    });
+}
+
+fn notify_backup_results(results: &[BackupResult]) {
+    let success_count = results.iter().filter(|r| r.success).count();
+    let mut text = i18n::trf("Erfolgreich: {} von {}", &[&success_count, &results.len()]);
+    for result in results {
+        let icon = if result.success { "✔" } else { "✘" };
+        text.push_str(&format!("\n{} {}: {}", icon, result.browser, i18n::tr_message(&result.message)));
+    }
+    let kind = if success_count == results.len() { NotificationKind::Info } else { NotificationKind::Error };
+    notification::show(kind, tr("Backup abgeschlossen!"), &text);
+}
+
+fn run_tray(app_state: Arc<Mutex<AppState>>, backup_manager: Arc<Mutex<BackupManager>>, scheduler: Arc<Scheduler>) -> Result<(), Box<dyn std::error::Error>> {
     let menu = Menu::new();
-    let backup_now = MenuItem::new("Backup jetzt erstellen", true, None);
-    let restore = MenuItem::new("Wiederherstellen...", true, None);
-    let settings = MenuItem::new("Einstellungen", true, None);
-    let open_folder = MenuItem::new("Backup-Ordner öffnen", true, None);
-    let quit = MenuItem::new("Beenden", true, None);
+    // "Backup erstellen ▸ Chrome / Edge / Firefox / Alle"
+    let backup_menu = Submenu::new(tr("Backup erstellen"), true);
+    let backup_browser_items: Vec<(&str, MenuItem)> = ["Chrome", "Edge", "Firefox"]
+        .into_iter()
+        .map(|browser| (browser, MenuItem::new(browser, true, None)))
+        .collect();
+    let backup_now = MenuItem::new(tr("Alle"), true, None);
+    for (_, item) in &backup_browser_items {
+        backup_menu.append(item)?;
+    }
+    backup_menu.append(&PredefinedMenuItem::separator())?;
+    backup_menu.append(&backup_now)?;
+    // "Letzte Backups ▸ Chrome ▸ ..." mit den drei neuesten Backups je Browser
+    let recent_menu = Submenu::new(tr("Letzte Backups"), true);
+    let recent_browsers: Vec<(&'static str, Submenu)> = ["Chrome", "Edge", "Firefox"]
+        .into_iter()
+        .map(|browser| (browser, Submenu::new(browser, true)))
+        .collect();
+    for (_, submenu) in &recent_browsers {
+        recent_menu.append(submenu)?;
+    }
+    // "Neuestes Backup wiederherstellen ▸ Chrome / Edge / Firefox", ein Klick ohne Assistent
+    let restore_latest_menu = Submenu::new(tr("Neuestes Backup wiederherstellen"), true);
+    let restore_latest_items: Vec<(&str, MenuItem)> = ["Chrome", "Edge", "Firefox"]
+        .into_iter()
+        .map(|browser| (browser, MenuItem::new(browser, true, None)))
+        .collect();
+    for (_, item) in &restore_latest_items {
+        restore_latest_menu.append(item)?;
+    }
+    let restore = MenuItem::new(tr("Wiederherstellen..."), true, None);
+    let settings = MenuItem::new(tr("Einstellungen"), true, None);
+    let open_folder = MenuItem::new(tr("Backup-Ordner öffnen"), true, None);
+    // Häkchen gesetzt, solange der Zeitplan läuft (nicht pausiert)
+    let automatic = CheckMenuItem::new(tr("Automatische Backups"), true, true, None);
+    let quit = MenuItem::new(tr("Beenden"), true, None);
     
-    menu.append(&backup_now)?;
+    menu.append(&backup_menu)?;
+    menu.append(&recent_menu)?;
+    menu.append(&restore_latest_menu)?;
     menu.append(&restore)?;
-    menu.append(&MenuItem::separator())?;
+    menu.append(&PredefinedMenuItem::separator())?;
     menu.append(&settings)?;
     menu.append(&open_folder)?;
-    menu.append(&MenuItem::separator())?;
+    menu.append(&automatic)?;
+    menu.append(&PredefinedMenuItem::separator())?;
     menu.append(&quit)?;
     
-    let icon = create_tray_icon_image();
+    let icon = create_tray_icon_image(None);
     let tray = match TrayIconBuilder::new()
         .with_menu(Box::new(menu))
         .with_tooltip("Browser Favoriten Backup")
@@ -83,57 +360,145 @@ fn run_tray(app_state: Arc<Mutex<AppState>>, backup_manager: Arc<Mutex<BackupMan
         .build() {
         Ok(tray) => tray,
         Err(e) => {
-            eprintln!("Failed to create tray icon: {}", e);
+            log::error!("Failed to create tray icon: {}", e);
             return Err(Box::new(e));
         }
     };
+    app_state.lock().unwrap().tray_available = true;
     
     let menu_channel = MenuEvent::receiver();
+    let tray_channel = TrayIconEvent::receiver();
     
+    // Tooltip aus Ereignissen: Backup-Läufe vom BackupManager, geplanter Lauf vom Zeitplan-Thread
+    let (runs, mut last_run, mut recent) = {
+        let manager = backup_manager.lock().unwrap();
+        let recent = fill_recent_backups(&recent_browsers, Vec::new(), &manager);
+        (manager.subscribe_runs(), manager.last_successful_backup().map(|at| (at, true)), recent)
+    };
+    let next_runs = scheduler.subscribe();
+    let mut next_run = None;
+    let mut paused = false;
+    let mut tooltip = String::new();
+    let mut icon_status = None;
+    let mut menu_language = i18n::language();
     loop {
-        if let Ok(event) = menu_channel.recv() {
+        // Die Sprache kann in den Einstellungen gewechselt werden
+        if i18n::language() != menu_language {
+            menu_language = i18n::language();
+            backup_menu.set_text(tr("Backup erstellen"));
+            backup_now.set_text(tr("Alle"));
+            recent_menu.set_text(tr("Letzte Backups"));
+            recent = fill_recent_backups(&recent_browsers, recent, &backup_manager.lock().unwrap());
+            restore_latest_menu.set_text(tr("Neuestes Backup wiederherstellen"));
+            restore.set_text(tr("Wiederherstellen..."));
+            settings.set_text(tr("Einstellungen"));
+            open_folder.set_text(tr("Backup-Ordner öffnen"));
+            automatic.set_text(tr("Automatische Backups"));
+            quit.set_text(tr("Beenden"));
+            tooltip.clear();
+        }
+        
+        // Pausieren kann auch in den Einstellungen umgeschaltet werden
+        if let Ok(manager) = backup_manager.try_lock() {
+            paused = manager.is_paused();
+            automatic.set_checked(!paused);
+        }
+        let mut finished = false;
+        for summary in runs.try_iter() {
+            last_run = Some((summary.finished, summary.successful == summary.total));
+            finished = true;
+        }
+        if finished {
+            recent = fill_recent_backups(&recent_browsers, recent, &backup_manager.lock().unwrap());
+        }
+        for next in next_runs.try_iter() {
+            next_run = next;
+        }
+        
+        let now = chrono::Local::now();
+        let mut new_tooltip = "Browser Favoriten Backup".to_string();
+        if let Some((at, success)) = last_run {
+            let mark = if success { "✔" } else { "✘" };
+            new_tooltip.push_str(&format!("\n{}", i18n::trf("Letztes Backup: {} {}", &[&schedule::format_last_run(at, now), &mark])));
+        }
+        if paused {
+            new_tooltip.push_str(&format!("\n{}", tr("Automatische Backups pausiert")));
+        } else if let Some(next) = next_run {
+            new_tooltip.push_str(&format!("\n{}", i18n::trf("Nächstes Backup: {}", &[&schedule::format_next_run(next, now)])));
+        }
+        if new_tooltip != tooltip {
+            tray.set_tooltip(Some(&new_tooltip)).ok();
+            tooltip = new_tooltip;
+        }
+        
+        // Nach einem fehlgeschlagenen Lauf bleibt das Icon rot bis zum nächsten erfolgreichen
+        let status = last_run.map(|(_, success)| success);
+        if status != icon_status {
+            tray.set_icon(Some(create_tray_icon_image(status))).ok();
+            icon_status = status;
+        }
+        
+        // Linksklick auf das Icon blendet das Fenster ein oder aus
+        for event in tray_channel.try_iter() {
+            if event.click_type != ClickType::Left {
+                continue;
+            }
+            let visible = app_state.lock().unwrap().window_visible;
+            if visible {
+                app_state.lock().unwrap().request_window(false);
+            } else {
+                show_window(&app_state, None);
+            }
+        }
+        
+        // Kurz genug, dass der Klick aufs Icon ohne spürbare Verzögerung wirkt
+        if let Ok(event) = menu_channel.recv_timeout(std::time::Duration::from_millis(100)) {
             match event.id {
                 id if id == backup_now.id() => {
-                    let results = backup_manager.lock().unwrap().backup_all();
-                    // Notification anzeigen
-                    let success_count = results.iter().filter(|r| r.success).count();
-                    let message = format!(
-                        "Backup abgeschlossen!\nErfolgreich: {} von {}",
-                        success_count, results.len()
-                    );
-                    
-                    #[cfg(target_os = "windows")]
-                    {
-                        use winapi::um::winuser::{MessageBoxW, MB_OK, MB_ICONINFORMATION};
-                        use std::ptr;
-                        unsafe {
-                            let title: Vec<u16> = "Backup Status\0".encode_utf16().collect();
-                            let msg: Vec<u16> = format!("{}\0", message).encode_utf16().collect();
-                            MessageBoxW(ptr::null_mut(), msg.as_ptr(), title.as_ptr(), MB_OK | MB_ICONINFORMATION);
-                        }
+                    spawn_backup(backup_manager.clone(), app_state.clone(), None, RunTrigger::Tray);
+                }
+                id if backup_browser_items.iter().any(|(_, item)| *item.id() == id) => {
+                    let browser = backup_browser_items.iter()
+                        .find(|(_, item)| *item.id() == id)
+                        .map(|(browser, _)| *browser);
+                    spawn_backup(backup_manager.clone(), app_state.clone(), browser, RunTrigger::Tray);
+                }
+                id if recent.iter().any(|entry| *entry.item.id() == id) => {
+                    let Some((browser, path)) = recent.iter()
+                        .find(|entry| *entry.item.id() == id)
+                        .and_then(|entry| Some((entry.browser, entry.path.clone()?))) else {
+                        continue;
+                    };
+                    show_window(&app_state, Some(AppMessage::ShowBackup { browser: browser.to_string(), path }));
+                }
+                id if restore_latest_items.iter().any(|(_, item)| *item.id() == id) => {
+                    if let Some((browser, _)) = restore_latest_items.iter().find(|(_, item)| *item.id() == id) {
+                        show_window(&app_state, Some(AppMessage::RestoreLatest { browser: browser.to_string() }));
                     }
                 }
                 id if id == restore.id() => {
-                    let mut state = app_state.lock().unwrap();
-                    state.show_window = true;
-                    state.message_queue.push(AppMessage::ShowRestore);
+                    show_window(&app_state, Some(AppMessage::ShowRestore));
                 }
                 id if id == settings.id() => {
-                    let mut state = app_state.lock().unwrap();
-                    state.show_window = true;
-                    state.message_queue.push(AppMessage::ShowSettings);
+                    show_window(&app_state, Some(AppMessage::ShowSettings));
                 }
                 id if id == open_folder.id() => {
-                    let backup_dir = backup_manager.lock().unwrap().get_backup_directory().to_path_buf();
                     #[cfg(target_os = "windows")]
                     {
+                        let backup_dir = backup_manager.lock().unwrap().get_backup_directory().to_path_buf();
                         std::process::Command::new("explorer")
                             .arg(&backup_dir)
                             .spawn()
                             .ok();
                     }
                 }
+                id if id == automatic.id() => {
+                    // Das Häkchen wurde vom Menü bereits umgeschaltet
+                    backup_manager.lock().unwrap().set_paused(!automatic.is_checked());
+                    scheduler.reconfigure();
+                }
                 id if id == quit.id() => {
+                    scheduler.shutdown();
                     break;
                 }
                 _ => {}
@@ -144,7 +509,7 @@ fn run_tray(app_state: Arc<Mutex<AppState>>, backup_manager: Arc<Mutex<BackupMan
     Ok(())
 }
 
-fn create_icon() -> eframe::IconData {
+fn create_icon() -> egui::IconData {
     let size = 32;
     let mut pixels = vec![0u8; size * size * 4];
     
@@ -154,7 +519,7 @@ fn create_icon() -> eframe::IconData {
             let idx = (y * size + x) * 4;
             
             // Ordner-Form
-            if (x >= 4 && x < 28 && y >= 8 && y < 26) {
+            if (4..28).contains(&x) && (8..26).contains(&y) {
                 pixels[idx] = 33;     // R
                 pixels[idx + 1] = 150; // G
                 pixels[idx + 2] = 243; // B
@@ -162,8 +527,8 @@ fn create_icon() -> eframe::IconData {
             }
             
             // Pfeil
-            if ((x >= 14 && x < 18 && y >= 4 && y < 16) ||
-                (x >= 10 && x < 22 && y >= 12 && y < 16 && (x < 14 || x >= 18))) {
+            if ((14..18).contains(&x) && (4..16).contains(&y)) ||
+                ((10..22).contains(&x) && (12..16).contains(&y) && !(14..18).contains(&x)) {
                 pixels[idx] = 76;     // R
                 pixels[idx + 1] = 175; // G
                 pixels[idx + 2] = 80;  // B
@@ -172,19 +537,22 @@ fn create_icon() -> eframe::IconData {
         }
     }
     
-    eframe::IconData {
+    egui::IconData {
         rgba: pixels,
-        width: size,
-        height: size,
+        width: size as u32,
+        height: size as u32,
     }
 }
 
-fn create_tray_icon_image() -> tray_icon::Icon {
+// `status`: Ergebnis des letzten Laufs, None solange noch keiner bekannt ist
+fn create_tray_icon_image(status: Option<bool>) -> tray_icon::Icon {
     // Try to load from embedded resource first
     #[cfg(target_os = "windows")]
     {
-        if let Ok(icon) = tray_icon::Icon::from_resource(1, None) {
-            return icon;
+        if status.is_none() {
+            if let Ok(icon) = tray_icon::Icon::from_resource(1, None) {
+                return icon;
+            }
         }
     }
     
@@ -198,7 +566,7 @@ fn create_tray_icon_image() -> tray_icon::Icon {
             let idx = (y * size + x) * 4;
             
             // Blue folder shape
-            if (x >= 2 && x < 14 && y >= 4 && y < 13) {
+            if (2..14).contains(&x) && (4..13).contains(&y) {
                 pixels[idx] = 33;     // R
                 pixels[idx + 1] = 150; // G
                 pixels[idx + 2] = 243; // B
@@ -206,8 +574,8 @@ fn create_tray_icon_image() -> tray_icon::Icon {
             }
             
             // Green arrow pointing up
-            if ((x >= 7 && x < 9 && y >= 2 && y < 8) ||
-                (x >= 5 && x < 11 && y >= 6 && y < 8 && (x < 7 || x >= 9))) {
+            if ((7..9).contains(&x) && (2..8).contains(&y)) ||
+                ((5..11).contains(&x) && (6..8).contains(&y) && !(7..9).contains(&x)) {
                 pixels[idx] = 76;     // R
                 pixels[idx + 1] = 175; // G
                 pixels[idx + 2] = 80;  // B
@@ -216,6 +584,35 @@ fn create_tray_icon_image() -> tray_icon::Icon {
         }
     }
     
+    if let Some(success) = status {
+        draw_status_overlay(&mut pixels, size, success);
+    }
+    
     tray_icon::Icon::from_rgba(pixels, size as u32, size as u32)
         .expect("Failed to create icon")
+}
+
+// Punkt unten rechts im Tray-Icon: grün mit Haken oder rot mit Ausrufezeichen
+fn draw_status_overlay(pixels: &mut [u8], size: usize, success: bool) {
+    let color = if success { [46, 160, 67] } else { [211, 47, 47] };
+    let check = [(9, 12), (10, 13), (11, 12), (12, 11), (13, 10), (14, 9)];
+    let center = size as f32 - 4.5;
+    
+    for y in size / 2..size {
+        for x in size / 2..size {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            if dx * dx + dy * dy > 4.5 * 4.5 {
+                continue;
+            }
+            let mark = if success {
+                check.contains(&(x, y))
+            } else {
+                // Strich von y 8 bis 10, Punkt bei y 12 und 13
+                (11..13).contains(&x) && ((8..11).contains(&y) || (12..14).contains(&y))
+            };
+            let idx = (y * size + x) * 4;
+            let [r, g, b] = if mark { [255, 255, 255] } else { color };
+            pixels[idx..idx + 4].copy_from_slice(&[r, g, b, 255]);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,56 @@
+// mozlz4.rs - Firefox' komprimiertes JSON-Format (.jsonlz4)
+//
+// Aufbau: "mozLz40\0", Länge der entpackten Daten (u32, little endian),
+// danach ein einzelner LZ4-Block.
+const MAGIC: &[u8] = b"mozLz40\0";
+
+// Die Größe im Kopf wird vorab reserviert; eine beschädigte Datei soll nicht
+// Gigabytes anfordern. LZ4 packt höchstens etwa 1:255, Lesezeichen bleiben
+// weit unter der Obergrenze.
+const MAX_RATIO: usize = 255;
+const MAX_SIZE: usize = 256 * 1024 * 1024;
+
+pub fn is_mozlz4(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    if !is_mozlz4(data) || data.len() < MAGIC.len() + 4 {
+        return Err("Keine mozLz4-Datei".to_string());
+    }
+    let header = &data[MAGIC.len()..MAGIC.len() + 4];
+    let size = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    let block = &data[MAGIC.len() + 4..];
+    if size > MAX_SIZE || size > block.len().saturating_mul(MAX_RATIO) + 16 {
+        return Err(format!("mozLz4-Daten beschädigt: ungültige Größe {}", size));
+    }
+    lz4_flex::block::decompress(block, size)
+        .map_err(|e| format!("mozLz4-Daten beschädigt: {}", e))
+}
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&lz4_flex::block::compress(data));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let json = br#"{"title":"","children":[{"title":"Lesezeichen-Symbolleiste","uri":"https://example.org/"}]}"#.repeat(20);
+        let compressed = compress(&json);
+        assert!(is_mozlz4(&compressed));
+        assert_eq!(decompress(&compressed).unwrap(), json);
+    }
+
+    #[test]
+    fn rejects_oversized_header() {
+        let mut data = compress(b"{}");
+        data[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decompress(&data).is_err());
+    }
+}
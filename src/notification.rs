@@ -0,0 +1,115 @@
+// notification.rs - Sprechblase im Infobereich, auch bei verstecktem Fenster
+// Ohne Windows gibt es keine Sprechblasen, die Typen bleiben ungenutzt
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+// Wie lange das zusätzliche Symbol für die Sprechblase im Infobereich bleibt
+const DISPLAY_SECONDS: u64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationKind {
+    Info,
+    Error,
+}
+
+// Zeigt die Meldung in einem eigenen Thread an und kehrt sofort zurück. Das
+// Tray-Icon von tray-icon lässt keine Sprechblasen zu, daher kommt die Meldung
+// über ein eigenes, kurzlebiges Symbol; Windows 10/11 macht daraus eine Toast-Meldung.
+#[cfg(target_os = "windows")]
+pub fn show(kind: NotificationKind, title: &str, text: &str) {
+    let title = title.to_string();
+    let text = text.to_string();
+    std::thread::spawn(move || unsafe { show_balloon(kind, &title, &text) });
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn show(kind: NotificationKind, title: &str, text: &str) {
+    // Not implemented for non-Windows platforms
+    let _ = (kind, title, text);
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn show_balloon(kind: NotificationKind, title: &str, text: &str) {
+    use std::ptr;
+    use winapi::um::libloaderapi::GetModuleHandleW;
+    use winapi::um::shellapi::{
+        Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_TIP, NIIF_ERROR, NIIF_INFO, NIM_ADD, NIM_DELETE, NOTIFYICONDATAW,
+    };
+    use winapi::um::winuser::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, LoadIconW, RegisterClassW, IDI_APPLICATION, MAKEINTRESOURCEW,
+        WNDCLASSW,
+    };
+
+    let class_name: Vec<u16> = "BrowserBackupNotification\0".encode_utf16().collect();
+    let instance = GetModuleHandleW(ptr::null());
+
+    // Schlägt beim zweiten Aufruf fehl, weil die Klasse schon registriert ist
+    let window_class = WNDCLASSW {
+        style: 0,
+        lpfnWndProc: Some(DefWindowProcW),
+        cbClsExtra: 0,
+        cbWndExtra: 0,
+        hInstance: instance,
+        hIcon: ptr::null_mut(),
+        hCursor: ptr::null_mut(),
+        hbrBackground: ptr::null_mut(),
+        lpszMenuName: ptr::null(),
+        lpszClassName: class_name.as_ptr(),
+    };
+    RegisterClassW(&window_class);
+
+    let hwnd = CreateWindowExW(
+        0,
+        class_name.as_ptr(),
+        class_name.as_ptr(),
+        0,
+        0,
+        0,
+        0,
+        0,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        instance,
+        ptr::null_mut(),
+    );
+    if hwnd.is_null() {
+        log::error!("Fenster für die Benachrichtigung konnte nicht erstellt werden");
+        return;
+    }
+
+    // Programmsymbol aus build.rs (Ressource 1), sonst das Standardsymbol
+    let mut icon = LoadIconW(instance, MAKEINTRESOURCEW(1));
+    if icon.is_null() {
+        icon = LoadIconW(ptr::null_mut(), IDI_APPLICATION);
+    }
+
+    let mut data: NOTIFYICONDATAW = std::mem::zeroed();
+    data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = hwnd;
+    data.uID = 1;
+    data.uFlags = NIF_ICON | NIF_TIP | NIF_INFO;
+    data.hIcon = icon;
+    data.dwInfoFlags = match kind {
+        NotificationKind::Info => NIIF_INFO,
+        NotificationKind::Error => NIIF_ERROR,
+    };
+    copy_wide(title, &mut data.szTip);
+    copy_wide(title, &mut data.szInfoTitle);
+    copy_wide(text, &mut data.szInfo);
+
+    if Shell_NotifyIconW(NIM_ADD, &mut data) == 0 {
+        log::warn!("Benachrichtigung konnte nicht angezeigt werden: {}", title);
+    } else {
+        std::thread::sleep(std::time::Duration::from_secs(DISPLAY_SECONDS));
+        Shell_NotifyIconW(NIM_DELETE, &mut data);
+    }
+    DestroyWindow(hwnd);
+}
+
+// Kürzt bei Bedarf, das letzte Zeichen bleibt für die abschließende 0
+#[cfg(target_os = "windows")]
+fn copy_wide(text: &str, target: &mut [u16]) {
+    let limit = target.len() - 1;
+    for (slot, unit) in target.iter_mut().zip(text.encode_utf16().take(limit)) {
+        *slot = unit;
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::Path;
+
+// Setzt bzw. entfernt FILE_ATTRIBUTE_COMPRESSED für das Verzeichnis und alle
+// enthaltenen Dateien. Neue Dateien erben das Attribut vom Verzeichnis.
+pub fn set_compression_recursive(path: &Path, enable: bool) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut count = 0;
+    set_compression(path, enable)?;
+    count += 1;
+
+    if path.is_dir() {
+        for entry in fs::read_dir(path)?.flatten() {
+            count += set_compression_recursive(&entry.path(), enable)?;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(target_os = "windows")]
+fn set_compression(path: &Path, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr;
+    use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::ioapiset::DeviceIoControl;
+    use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+    use winapi::um::winioctl::FSCTL_SET_COMPRESSION;
+    use winapi::um::winnt::{
+        COMPRESSION_FORMAT_DEFAULT, COMPRESSION_FORMAT_NONE, FILE_SHARE_READ, FILE_SHARE_WRITE,
+        GENERIC_READ, GENERIC_WRITE,
+    };
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+
+    unsafe {
+        // FILE_FLAG_BACKUP_SEMANTICS wird benötigt, um Verzeichnisse zu öffnen
+        let handle = CreateFileW(
+            wide_path.as_ptr(),
+            GENERIC_READ | GENERIC_WRITE,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            ptr::null_mut(),
+            OPEN_EXISTING,
+            FILE_FLAG_BACKUP_SEMANTICS,
+            ptr::null_mut(),
+        );
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(Box::new(io::Error::last_os_error()));
+        }
+
+        let mut format: u16 = if enable { COMPRESSION_FORMAT_DEFAULT } else { COMPRESSION_FORMAT_NONE };
+        let mut bytes_returned: u32 = 0;
+        let ok = DeviceIoControl(
+            handle,
+            FSCTL_SET_COMPRESSION,
+            &mut format as *mut u16 as *mut _,
+            std::mem::size_of::<u16>() as u32,
+            ptr::null_mut(),
+            0,
+            &mut bytes_returned,
+            ptr::null_mut(),
+        );
+        let error = io::Error::last_os_error();
+        CloseHandle(handle);
+
+        if ok == 0 {
+            return Err(Box::new(error));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_compression(_path: &Path, _enable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    // Not implemented for non-Windows platforms
+    Ok(())
+}
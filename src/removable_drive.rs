@@ -0,0 +1,80 @@
+// removable_drive.rs - Sekundäres Backup-Ziel auf einem USB-Stick
+use chrono::{DateTime, Local};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// Laufwerksbezeichnung für die Anzeige, z.B. "E:"
+pub fn drive_name(target: &Path) -> String {
+    match target.components().next() {
+        Some(Component::Prefix(prefix)) => prefix.as_os_str().to_string_lossy().to_string(),
+        _ => target.display().to_string(),
+    }
+}
+
+// Wurzelverzeichnis des Laufwerks, z.B. "E:\"
+fn drive_root(target: &Path) -> PathBuf {
+    let mut root = PathBuf::new();
+    for component in target.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => root.push(component.as_os_str()),
+            _ => break,
+        }
+    }
+    root
+}
+
+pub fn is_present(target: &Path) -> bool {
+    let root = drive_root(target);
+    !root.as_os_str().is_empty() && root.exists()
+}
+
+// Kopiert alle Backups, die seit der letzten Synchronisation entstanden sind
+// oder auf dem Ziel noch fehlen. Dateien, für die `skip` zutrifft (z.B. die nach
+// jedem Backup überschriebene latest-Kopie), bleiben weg. Gibt die Anzahl kopierter Dateien zurück.
+pub fn sync_backups(
+    backup_dir: &Path,
+    target: &Path,
+    browsers: &[&str],
+    last_sync: Option<DateTime<Local>>,
+    skip: &dyn Fn(&Path) -> bool,
+) -> Result<usize, String> {
+    let mut copied = 0;
+
+    for browser in browsers {
+        let source_dir = backup_dir.join(browser);
+        let entries = match fs::read_dir(&source_dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        let target_dir = target.join(browser);
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() || skip(&path) {
+                continue;
+            }
+
+            let target_path = target_dir.join(entry.file_name());
+            let modified: Option<DateTime<Local>> = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .map(|t| t.into());
+            let is_new = match (last_sync, modified) {
+                (Some(last), Some(modified)) => modified > last,
+                _ => true,
+            };
+
+            if is_new || !target_path.exists() {
+                fs::copy(&path, &target_path)
+                    .map_err(|e| format!("Fehler beim Kopieren von {}: {}", path.display(), e))?;
+                copied += 1;
+            }
+        }
+    }
+
+    Ok(copied)
+}
@@ -0,0 +1,32 @@
+// repository_format.rs - Versionskennung für das Layout des Backup-Ordners
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Version 1: Browser-Ordner mit Zeitstempel-Dateien und config.json
+// Version 2: zusätzlich latest.<ext> pro Browser
+// Version 3: catalog.json mit allen Backups und dem letzten Erfolg pro Browser
+pub const CURRENT_VERSION: u32 = 3;
+
+const MARKER_FILE: &str = "repository.json";
+
+#[derive(Serialize, Deserialize)]
+struct RepositoryMarker {
+    version: u32,
+}
+
+pub fn read_version(backup_dir: &Path) -> Option<u32> {
+    let content = fs::read_to_string(backup_dir.join(MARKER_FILE)).ok()?;
+    serde_json::from_str::<RepositoryMarker>(&content).ok().map(|m| m.version)
+}
+
+pub fn write_version(backup_dir: &Path, version: u32) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(&RepositoryMarker { version })?;
+    fs::write(backup_dir.join(MARKER_FILE), content)
+}
+
+// Ein Ordner ohne Browser-Unterordner wurde noch nie benutzt
+pub fn is_new_repository(backup_dir: &Path, browsers: &[&str]) -> bool {
+    !browsers.iter().any(|browser| backup_dir.join(browser).is_dir())
+}
@@ -0,0 +1,170 @@
+// rest_api.rs - Lokale HTTP-Schnittstelle für Hausautomation und Monitoring
+//
+// GET  /status                 Zustand wie `bbb status`
+// POST /backup                 Backup aller aktivierten Browser
+// GET  /backups[?browser=...]  Vorhandene Backups je Browser
+// POST /restore                {"browser": "Chrome", "file": "<Name aus /backups>"}
+//
+// Jede Anfrage braucht "Authorization: Bearer <Token>", POST zusätzlich
+// "Content-Type: application/json". Webseiten im Browser kommen damit nicht durch:
+// fremde Origin und Host-Namen außer 127.0.0.1/localhost (DNS-Rebinding) werden abgewiesen.
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::backup_manager::BackupManager;
+use crate::history::RunTrigger;
+use crate::ipc::InstanceStatus;
+
+const BROWSERS: [&str; 3] = ["Chrome", "Edge", "Firefox"];
+
+// Startet den Server, falls er in den Einstellungen aktiviert ist.
+// Port und Token werden nur hier gelesen, Änderungen gelten nach einem Neustart.
+// Ohne Token wird eines erzeugt und gespeichert, ohne Anmeldung geht es nicht.
+pub fn start(backup_manager: Arc<Mutex<BackupManager>>) {
+    let (port, token) = {
+        let mut manager = backup_manager.lock().unwrap();
+        if !manager.get_config().rest_api_enabled {
+            return;
+        }
+        if manager.get_config().rest_api_token.trim().is_empty() {
+            let token = match generate_token() {
+                Ok(token) => token,
+                Err(e) => {
+                    log::error!("REST-API nicht gestartet, kein Token: {}", e);
+                    return;
+                }
+            };
+            let mut config = manager.get_config().clone();
+            config.rest_api_token = token;
+            manager.set_config(config);
+            log::info!("REST-API: neues Token erzeugt, es steht in den Einstellungen");
+        }
+        let config = manager.get_config();
+        (config.rest_api_port, config.rest_api_token.clone())
+    };
+
+    // Nur an 127.0.0.1 binden, von außen ist die API nicht erreichbar
+    let server = match Server::http(("127.0.0.1", port)) {
+        Ok(server) => server,
+        Err(e) => {
+            log::error!("REST-API konnte Port {} nicht öffnen: {}", port, e);
+            return;
+        }
+    };
+    log::info!("REST-API läuft auf http://127.0.0.1:{}", port);
+
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let authorized = header(&request, "Authorization") == Some(format!("Bearer {}", token).as_str());
+            let json_body = header(&request, "Content-Type")
+                .and_then(|value| value.split(';').next())
+                .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+
+            let (status, body) = if !is_local(&request, port) {
+                (403, json!({ "error": "Nur für Programme auf diesem Rechner" }))
+            } else if !authorized {
+                (401, json!({ "error": "Nicht angemeldet" }))
+            } else if *request.method() == Method::Post && !json_body {
+                (415, json!({ "error": "Content-Type muss application/json sein" }))
+            } else {
+                let mut content = String::new();
+                match request.as_reader().read_to_string(&mut content) {
+                    Ok(_) => handle(&backup_manager, request.method(), request.url(), &content),
+                    Err(e) => (400, json!({ "error": format!("Anfrage nicht lesbar: {}", e) })),
+                }
+            };
+
+            let response = Response::from_string(body.to_string())
+                .with_status_code(status)
+                .with_header(Header::from_bytes("Content-Type", "application/json; charset=utf-8").unwrap());
+            if let Err(e) = request.respond(response) {
+                log::error!("REST-API-Antwort fehlgeschlagen: {}", e);
+            }
+        }
+    });
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request.headers().iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+// Host muss 127.0.0.1 oder localhost sein, sonst könnte eine Webseite ihren
+// Namen auf 127.0.0.1 umbiegen. Origin schicken nur Browser mit, dann nur die eigene.
+fn is_local(request: &Request, port: u16) -> bool {
+    let allowed = |host: &str| {
+        let host = host.strip_suffix(&format!(":{}", port)).unwrap_or(host);
+        host == "127.0.0.1" || host.eq_ignore_ascii_case("localhost")
+    };
+    let host_ok = header(request, "Host").is_some_and(allowed);
+    let origin_ok = match header(request, "Origin") {
+        Some(origin) => origin.strip_prefix("http://").is_some_and(allowed),
+        None => true,
+    };
+    host_ok && origin_ok
+}
+
+// 32 zufällige Bytes als Hex, z.B. für `curl -H "Authorization: Bearer ..."`
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Zufallszahlen nicht verfügbar: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn handle(backup_manager: &Mutex<BackupManager>, method: &Method, url: &str, body: &str) -> (u16, Value) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    // Backup und Wiederherstellung laufen auf einer Kopie, ohne Fenster und Tray zu blockieren
+    let manager = backup_manager.lock().unwrap().clone();
+
+    match (method, path) {
+        (Method::Get, "/status") => (200, json!(InstanceStatus {
+            paused: manager.is_paused(),
+            next_backup: manager.next_scheduled_backup(),
+            last_backup: manager.last_successful_backup(),
+        })),
+        (Method::Post, "/backup") => {
+            let results = manager.backup_all(RunTrigger::External);
+            let status = if results.iter().all(|r| r.success) { 200 } else { 500 };
+            (status, json!(results))
+        }
+        (Method::Get, "/backups") => {
+            let filter = url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "browser")
+                .map(|(_, value)| value.to_string());
+            let backups: BTreeMap<&str, _> = BROWSERS.iter()
+                .filter(|browser| filter.as_deref().is_none_or(|f| f.eq_ignore_ascii_case(browser)))
+                .map(|browser| (*browser, manager.get_backup_list(browser)))
+                .collect();
+            (200, json!(backups))
+        }
+        (Method::Post, "/restore") => match restore(&manager, body) {
+            Ok(message) => (200, json!({ "message": message })),
+            Err((status, error)) => (status, json!({ "error": error })),
+        },
+        (_, "/status" | "/backup" | "/backups" | "/restore") => (405, json!({ "error": "Methode nicht erlaubt" })),
+        _ => (404, json!({ "error": "Unbekannter Pfad" })),
+    }
+}
+
+// Nur Dateinamen aus /backups, damit kein beliebiger Pfad wiederhergestellt werden kann
+fn restore(manager: &BackupManager, body: &str) -> Result<String, (u16, String)> {
+    let request: Value = serde_json::from_str(body)
+        .map_err(|e| (400, format!("Ungültiges JSON: {}", e)))?;
+    let field = |name: &str| request.get(name).and_then(Value::as_str)
+        .ok_or((400, format!("Feld \"{}\" fehlt", name)));
+    let name = field("browser")?;
+    let browser = BROWSERS.iter()
+        .find(|b| b.eq_ignore_ascii_case(name))
+        .ok_or((400, "Unbekannter Browser".to_string()))?;
+    let file = field("file")?;
+
+    let backup = manager.get_backup_list(browser)
+        .into_iter()
+        .find(|backup| backup.name == file)
+        .ok_or((404, format!("Backup {} nicht gefunden", file)))?;
+    manager.restore_backup(browser, &backup.path, false).map_err(|e| (500, e))
+}
@@ -0,0 +1,80 @@
+// schedule.rs - Berechnung der Zeitpunkte für automatische Backups
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ScheduleMode {
+    // Alle N Minuten seit dem letzten Lauf
+    Interval,
+    // Zu festen Uhrzeiten, z.B. 09:00 und 17:00
+    TimesOfDay,
+}
+
+fn at_time(date: chrono::NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&date.and_time(time)).earliest()
+}
+
+// Letzter geplanter Zeitpunkt, der nicht in der Zukunft liegt
+pub fn most_recent_slot(times: &[NaiveTime], now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let today = now.date_naive();
+    let yesterday = today - Duration::days(1);
+
+    times
+        .iter()
+        .filter_map(|time| at_time(today, *time).filter(|slot| *slot <= now))
+        .chain(times.iter().filter_map(|time| at_time(yesterday, *time)))
+        .max()
+}
+
+// Nächster geplanter Zeitpunkt nach `now`
+pub fn next_slot(times: &[NaiveTime], now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let today = now.date_naive();
+    let tomorrow = today + Duration::days(1);
+
+    times
+        .iter()
+        .filter_map(|time| at_time(today, *time).filter(|slot| *slot > now))
+        .chain(times.iter().filter_map(|time| at_time(tomorrow, *time)))
+        .min()
+}
+
+// Ein Backup ist fällig, wenn seit dem letzten geplanten Zeitpunkt noch keins lief
+pub fn is_time_of_day_due(times: &[NaiveTime], last_run: Option<DateTime<Local>>, now: DateTime<Local>) -> bool {
+    match (most_recent_slot(times, now), last_run) {
+        (Some(slot), Some(last_run)) => last_run < slot,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+pub fn next_interval_run(last_run: DateTime<Local>, interval_minutes: u64) -> DateTime<Local> {
+    last_run + Duration::minutes(interval_minutes.max(1) as i64)
+}
+
+// Nach Standby/Ruhezustand liegt der geplante Zeitpunkt deutlich in der Vergangenheit
+pub fn is_overdue(scheduled: DateTime<Local>, now: DateTime<Local>) -> bool {
+    now - scheduled > Duration::minutes(2)
+}
+
+// "heute 09:12", "gestern 21:00", sonst mit Datum
+pub fn format_last_run(last_run: DateTime<Local>, now: DateTime<Local>) -> String {
+    let days = (now.date_naive() - last_run.date_naive()).num_days();
+    match days {
+        0 => trf("heute {}", &[&last_run.format("%H:%M")]),
+        1 => trf("gestern {}", &[&last_run.format("%H:%M")]),
+        _ => last_run.format("%d.%m. %H:%M").to_string(),
+    }
+}
+
+// "14:00" für heute, sonst mit Datum; überfällige Läufe starten in Kürze
+pub fn format_next_run(next_run: DateTime<Local>, now: DateTime<Local>) -> String {
+    if next_run <= now {
+        tr("in Kürze").to_string()
+    } else if next_run.date_naive() == now.date_naive() {
+        next_run.format("%H:%M").to_string()
+    } else {
+        next_run.format("%d.%m. %H:%M").to_string()
+    }
+}
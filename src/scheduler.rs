@@ -0,0 +1,127 @@
+// scheduler.rs - Zeitplan-Dienst für automatische Backups
+//
+// Läuft in einem eigenen Thread und wartet bis zum nächsten geplanten Lauf.
+// UI und Tray können ihn über Befehle sofort neu planen lassen oder beenden.
+use crate::backup_manager::BackupManager;
+use crate::idle;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+// Spätestens nach dieser Zeit wird neu geprüft: nach Standby/Ruhezustand ist
+// der geplante Zeitpunkt überschritten und das Backup wird nachgeholt
+const MAX_WAIT: Duration = Duration::from_secs(30);
+
+enum SchedulerCommand {
+    // Konfiguration oder Pausenstatus hat sich geändert
+    Reconfigure,
+    Shutdown,
+}
+
+// Empfänger des nächsten geplanten Laufs (None = keiner geplant)
+type Listeners = Arc<Mutex<Vec<Sender<Option<DateTime<Local>>>>>>;
+
+pub struct Scheduler {
+    sender: Sender<SchedulerCommand>,
+    thread: Mutex<Option<JoinHandle<()>>>,
+    listeners: Listeners,
+}
+
+impl Scheduler {
+    pub fn start(backup_manager: Arc<Mutex<BackupManager>>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let listeners = Listeners::default();
+        let thread_listeners = listeners.clone();
+        
+        let thread = thread::spawn(move || {
+            // Wanduhrzeit statt Instant, siehe MAX_WAIT
+            let started = Local::now();
+            let mut last_runs: HashMap<&'static str, DateTime<Local>> = HashMap::new();
+            let mut wait = Duration::ZERO;
+            
+            loop {
+                match receiver.recv_timeout(wait) {
+                    Ok(SchedulerCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+                    Ok(SchedulerCommand::Reconfigure) | Err(RecvTimeoutError::Timeout) => {}
+                }
+                wait = MAX_WAIT;
+                
+                let now = Local::now();
+                let (due, next_run) = match backup_manager.lock() {
+                    Ok(mut manager) => {
+                        let due = manager.plan_scheduled_backups(&last_runs, started, now);
+                        (due, manager.next_scheduled_backup())
+                    }
+                    Err(_) => continue,
+                };
+                thread_listeners.lock().unwrap().retain(|listener| listener.send(next_run).is_ok());
+                
+                if due.is_empty() {
+                    if let Some(until_next) = next_run.and_then(|next| (next - now).to_std().ok()) {
+                        wait = wait.min(until_next);
+                    }
+                    continue;
+                }
+                
+                // Fällige Backups warten, bis der Rechner lange genug unbenutzt ist
+                let required_idle = match backup_manager.lock() {
+                    Ok(manager) if manager.get_config().idle_only => {
+                        Some(Duration::from_secs(manager.get_config().idle_minutes * 60))
+                    }
+                    _ => None,
+                };
+                if let Some(required_idle) = required_idle {
+                    if idle::idle_time().is_some_and(|idle| idle < required_idle) {
+                        continue;
+                    }
+                }
+                
+                for browser in &due {
+                    last_runs.insert(browser, now);
+                }
+                
+                // Der Lauf selbst auf einer Kopie, damit Fenster und Tray nicht warten
+                let manager = match backup_manager.lock() {
+                    Ok(mut manager) => {
+                        manager.mark_scheduled_run();
+                        manager.clone()
+                    }
+                    Err(_) => continue,
+                };
+                manager.run_scheduled_backup(&due);
+                // Direkt den nächsten Lauf berechnen
+                wait = Duration::ZERO;
+            }
+        });
+        
+        Self {
+            sender,
+            thread: Mutex::new(Some(thread)),
+            listeners,
+        }
+    }
+    
+    // Der erste Wert kommt sofort, danach nach jeder Planung
+    pub fn subscribe(&self) -> Receiver<Option<DateTime<Local>>> {
+        let (sender, receiver) = mpsc::channel();
+        self.listeners.lock().unwrap().push(sender);
+        self.reconfigure();
+        receiver
+    }
+    
+    // Nach Änderungen an Zeitplan oder Pause sofort neu planen
+    pub fn reconfigure(&self) {
+        self.sender.send(SchedulerCommand::Reconfigure).ok();
+    }
+    
+    // Beendet den Thread; ein laufendes Backup wird noch abgeschlossen
+    pub fn shutdown(&self) {
+        self.sender.send(SchedulerCommand::Shutdown).ok();
+        if let Some(thread) = self.thread.lock().ok().and_then(|mut t| t.take()) {
+            thread.join().ok();
+        }
+    }
+}
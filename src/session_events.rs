@@ -0,0 +1,150 @@
+// session_events.rs - Benachrichtigungen über Sitzungsereignisse (Sperren usw.)
+// Ohne Windows gibt es keine Sitzungsereignisse, die Typen bleiben ungenutzt
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionEvent {
+    // Arbeitsstation wurde gesperrt
+    Locked,
+    // Abmeldung oder Herunterfahren; der Prozess endet kurz nach Rückkehr des Handlers
+    Ending,
+}
+
+type Handler = Box<dyn Fn(SessionEvent) + Send + Sync>;
+
+static HANDLER: OnceLock<Handler> = OnceLock::new();
+
+fn dispatch(event: SessionEvent) {
+    if let Some(handler) = HANDLER.get() {
+        handler(event);
+    }
+}
+
+// Startet einen Thread mit einem unsichtbaren Fenster, das die
+// Sitzungsbenachrichtigungen von Windows empfängt. Der Handler läuft im
+// Fensterthread und sollte längere Arbeit selbst auslagern.
+#[cfg(target_os = "windows")]
+pub fn start_listener<F>(handler: F)
+where
+    F: Fn(SessionEvent) + Send + Sync + 'static,
+{
+    if HANDLER.set(Box::new(handler)).is_err() {
+        return;
+    }
+    std::thread::spawn(|| unsafe { run_message_loop() });
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start_listener<F>(handler: F)
+where
+    F: Fn(SessionEvent) + Send + Sync + 'static,
+{
+    // Not implemented for non-Windows platforms
+    let _ = HANDLER.set(Box::new(handler));
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn run_message_loop() {
+    use std::ptr;
+    use winapi::um::libloaderapi::GetModuleHandleW;
+    use winapi::um::winuser::{
+        CreateWindowExW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, MSG, WNDCLASSW,
+    };
+    use winapi::shared::minwindef::{BOOL, DWORD};
+    use winapi::shared::windef::HWND;
+
+    // winapi 0.3 enthält aus wtsapi32.h nur WTSQueryUserToken
+    const NOTIFY_FOR_THIS_SESSION: DWORD = 0;
+    #[link(name = "wtsapi32")]
+    extern "system" {
+        fn WTSRegisterSessionNotification(hWnd: HWND, dwFlags: DWORD) -> BOOL;
+        fn WTSUnRegisterSessionNotification(hWnd: HWND) -> BOOL;
+    }
+
+    let class_name: Vec<u16> = "BrowserBackupSessionListener\0".encode_utf16().collect();
+    let instance = GetModuleHandleW(ptr::null());
+
+    let window_class = WNDCLASSW {
+        style: 0,
+        lpfnWndProc: Some(window_proc),
+        cbClsExtra: 0,
+        cbWndExtra: 0,
+        hInstance: instance,
+        hIcon: ptr::null_mut(),
+        hCursor: ptr::null_mut(),
+        hbrBackground: ptr::null_mut(),
+        lpszMenuName: ptr::null(),
+        lpszClassName: class_name.as_ptr(),
+    };
+    if RegisterClassW(&window_class) == 0 {
+        log::error!("Fensterklasse für Sitzungsereignisse konnte nicht registriert werden");
+        return;
+    }
+
+    // Unsichtbares Top-Level-Fenster (kein Message-Only-Fenster), damit
+    // auch Broadcast-Nachrichten wie WM_QUERYENDSESSION ankommen
+    let hwnd = CreateWindowExW(
+        0,
+        class_name.as_ptr(),
+        class_name.as_ptr(),
+        0,
+        0,
+        0,
+        0,
+        0,
+        ptr::null_mut(),
+        ptr::null_mut(),
+        instance,
+        ptr::null_mut(),
+    );
+    if hwnd.is_null() {
+        log::error!("Fenster für Sitzungsereignisse konnte nicht erstellt werden");
+        return;
+    }
+
+    if WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) == 0 {
+        log::error!("Sitzungsbenachrichtigungen konnten nicht registriert werden");
+    }
+
+    let mut msg: MSG = std::mem::zeroed();
+    while GetMessageW(&mut msg, ptr::null_mut(), 0, 0) > 0 {
+        TranslateMessage(&msg);
+        DispatchMessageW(&msg);
+    }
+
+    WTSUnRegisterSessionNotification(hwnd);
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn window_proc(
+    hwnd: winapi::shared::windef::HWND,
+    msg: winapi::shared::minwindef::UINT,
+    wparam: winapi::shared::minwindef::WPARAM,
+    lparam: winapi::shared::minwindef::LPARAM,
+) -> winapi::shared::minwindef::LRESULT {
+    use winapi::um::winuser::{
+        DefWindowProcW, ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy, WM_ENDSESSION,
+        WM_QUERYENDSESSION, WM_WTSSESSION_CHANGE, WTS_SESSION_LOCK,
+    };
+
+    if msg == WM_WTSSESSION_CHANGE && wparam == WTS_SESSION_LOCK {
+        dispatch(SessionEvent::Locked);
+        return 0;
+    }
+
+    // Herunterfahren nie verhindern, nur beim eigentlichen Ende kurz verzögern
+    if msg == WM_QUERYENDSESSION {
+        return 1;
+    }
+
+    if msg == WM_ENDSESSION && wparam != 0 {
+        let reason: Vec<u16> = "Browser-Favoriten werden gesichert...\0".encode_utf16().collect();
+        ShutdownBlockReasonCreate(hwnd, reason.as_ptr());
+        dispatch(SessionEvent::Ending);
+        ShutdownBlockReasonDestroy(hwnd);
+        return 0;
+    }
+
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,68 @@
+// single_instance.rs - Nur eine laufende Instanz (sonst zwei Tray-Icons und zwei Scheduler)
+
+// Hält den benannten Mutex, solange die Instanz läuft
+pub struct InstanceGuard {
+    #[cfg(target_os = "windows")]
+    handle: winapi::um::winnt::HANDLE,
+}
+
+// None bedeutet: eine andere Instanz läuft bereits
+#[cfg(target_os = "windows")]
+pub fn acquire() -> Option<InstanceGuard> {
+    use winapi::shared::winerror::ERROR_ALREADY_EXISTS;
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::synchapi::CreateMutexW;
+
+    // "Local\" gilt pro Anmeldesitzung, andere Benutzer dürfen eine eigene Instanz haben
+    let name: Vec<u16> = "Local\\BrowserFavoritenBackup\0".encode_utf16().collect();
+    unsafe {
+        let handle = CreateMutexW(std::ptr::null_mut(), 0, name.as_ptr());
+        if handle.is_null() {
+            // Ohne Mutex lieber starten als gar nicht
+            return Some(InstanceGuard { handle });
+        }
+        if GetLastError() == ERROR_ALREADY_EXISTS {
+            CloseHandle(handle);
+            return None;
+        }
+        Some(InstanceGuard { handle })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn acquire() -> Option<InstanceGuard> {
+    // Not implemented for non-Windows platforms
+    Some(InstanceGuard {})
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { winapi::um::handleapi::CloseHandle(self.handle) };
+        }
+    }
+}
+
+// Holt das Fenster der laufenden Instanz nach vorne
+#[cfg(target_os = "windows")]
+pub fn focus_existing_window(title: &str) -> bool {
+    use winapi::um::winuser::{FindWindowW, SetForegroundWindow, ShowWindow, SW_RESTORE};
+
+    let title: Vec<u16> = format!("{}\0", title).encode_utf16().collect();
+    unsafe {
+        let hwnd = FindWindowW(std::ptr::null(), title.as_ptr());
+        if hwnd.is_null() {
+            return false;
+        }
+        ShowWindow(hwnd, SW_RESTORE);
+        SetForegroundWindow(hwnd) != 0
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn focus_existing_window(_title: &str) -> bool {
+    // Not implemented for non-Windows platforms
+    false
+}
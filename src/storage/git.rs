@@ -0,0 +1,209 @@
+// storage/git.rs - Backups als Commits in einem lokalen Git-Repository
+use rusqlite::{Connection, OpenFlags};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, PoisonError};
+
+// Browser-neutrale, zeilenweise diffbare Darstellung eines Lesezeichens
+#[derive(Serialize, Clone, PartialEq)]
+pub struct NormalizedBookmark {
+    pub folder: String,
+    pub title: String,
+    pub url: String,
+}
+
+pub struct GitRepository {
+    path: PathBuf,
+}
+
+impl GitRepository {
+    pub fn open_or_init(path: &Path) -> Result<Self, String> {
+        fs::create_dir_all(path)
+            .map_err(|e| format!("Fehler beim Erstellen des Repositorys: {}", e))?;
+        
+        let repository = Self { path: path.to_path_buf() };
+        if !path.join(".git").exists() {
+            repository.git(&["init"])?;
+        }
+        Ok(repository)
+    }
+    
+    // Schreibt die normalisierten Lesezeichen nach <Browser>/bookmarks.json und
+    // committet sie. Gibt die Commit-Nachricht zurück, oder None wenn sich nichts geändert hat.
+    pub fn commit_snapshot(&self, browser: &str, bookmarks: &[NormalizedBookmark]) -> Result<Option<String>, String> {
+        let browser_dir = self.path.join(browser);
+        fs::create_dir_all(&browser_dir)
+            .map_err(|e| format!("Fehler beim Erstellen des Verzeichnisses: {}", e))?;
+        let file = browser_dir.join("bookmarks.json");
+        
+        let previous: Vec<String> = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Vec<serde_json::Value>>(&content).ok())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|b| b.get("url").and_then(|u| u.as_str()).map(|u| u.to_string()))
+            .collect();
+        let current: Vec<String> = bookmarks.iter().map(|b| b.url.clone()).collect();
+        let (added, removed) = count_changes(&previous, &current);
+        
+        // Ein Eintrag pro Zeile, damit "git diff" lesbar bleibt
+        let mut content = String::from("[\n");
+        for (i, bookmark) in bookmarks.iter().enumerate() {
+            let line = serde_json::to_string(bookmark)
+                .map_err(|e| format!("Fehler beim Serialisieren: {}", e))?;
+            content.push_str("  ");
+            content.push_str(&line);
+            content.push_str(if i + 1 < bookmarks.len() { ",\n" } else { "\n" });
+        }
+        content.push_str("]\n");
+        fs::write(&file, content)
+            .map_err(|e| format!("Fehler beim Schreiben: {}", e))?;
+        
+        let relative = format!("{}/bookmarks.json", browser);
+        self.git(&["add", "--", &relative])?;
+        
+        // Keine Änderungen im Index: nichts zu committen
+        if self.git(&["diff", "--cached", "--quiet", "--", &relative]).is_ok() {
+            return Ok(None);
+        }
+        
+        let message = format!("{}: +{} −{} bookmarks", browser, added, removed);
+        let mut args: Vec<&str> = Vec::new();
+        if !self.has_identity() {
+            args.extend(["-c", "user.name=Browser Backup", "-c", "user.email=browser-backup@localhost"]);
+        }
+        args.extend(["commit", "-m", &message, "--", &relative]);
+        self.git(&args)?;
+        
+        Ok(Some(message))
+    }
+    
+    // Nacheinander, falls der vorige Push beim nächsten Backup noch läuft
+    pub fn push(&self) -> Result<(), String> {
+        static PUSHING: Mutex<()> = Mutex::new(());
+        let _pushing = PUSHING.lock().unwrap_or_else(PoisonError::into_inner);
+        self.git(&["push"]).map(|_| ())
+    }
+    
+    fn has_identity(&self) -> bool {
+        self.git(&["config", "user.email"]).is_ok()
+    }
+    
+    fn git(&self, args: &[&str]) -> Result<String, String> {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.path).args(args);
+        // Ohne Konsole kann niemand ein Passwort eingeben, Git würde ewig warten
+        command.env("GIT_TERMINAL_PROMPT", "0");
+        
+        #[cfg(target_os = "windows")]
+        {
+            // Kein Konsolenfenster für jeden Git-Aufruf öffnen
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+        }
+        
+        let output = command.output()
+            .map_err(|e| format!("Git konnte nicht gestartet werden: {}", e))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(format!("git {} fehlgeschlagen: {}", args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()))
+        }
+    }
+}
+
+fn count_changes(previous: &[String], current: &[String]) -> (usize, usize) {
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for url in previous {
+        *counts.entry(url).or_insert(0) -= 1;
+    }
+    for url in current {
+        *counts.entry(url).or_insert(0) += 1;
+    }
+    
+    let added = counts.values().filter(|c| **c > 0).map(|c| *c as usize).sum();
+    let removed = counts.values().filter(|c| **c < 0).map(|c| (-*c) as usize).sum();
+    (added, removed)
+}
+
+pub fn normalize_chromium(bookmarks: &serde_json::Value) -> Vec<NormalizedBookmark> {
+    fn walk(node: &serde_json::Value, folder: &str, result: &mut Vec<NormalizedBookmark>) {
+        let name = node.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        match node.get("type").and_then(|v| v.as_str()) {
+            Some("url") => {
+                if let Some(url) = node.get("url").and_then(|v| v.as_str()) {
+                    result.push(NormalizedBookmark {
+                        folder: folder.to_string(),
+                        title: name.to_string(),
+                        url: url.to_string(),
+                    });
+                }
+            }
+            Some("folder") => {
+                let path = if folder.is_empty() { name.to_string() } else { format!("{}/{}", folder, name) };
+                if let Some(children) = node.get("children").and_then(|v| v.as_array()) {
+                    for child in children {
+                        walk(child, &path, result);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    let mut result = Vec::new();
+    if let Some(roots) = bookmarks.get("roots").and_then(|v| v.as_object()) {
+        for (_, root) in roots {
+            walk(root, "", &mut result);
+        }
+    }
+    result
+}
+
+pub fn normalize_firefox(db_path: &Path) -> Result<Vec<NormalizedBookmark>, String> {
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Fehler beim Öffnen der Firefox-Datenbank: {}", e))?;
+    
+    let mut stmt = conn.prepare(
+        "SELECT b.id, b.parent, b.type, COALESCE(b.title, ''), p.url
+         FROM moz_bookmarks b
+         LEFT JOIN moz_places p ON b.fk = p.id
+         ORDER BY b.parent, b.position"
+    ).map_err(|e| format!("Fehler beim Vorbereiten der SQL-Abfrage: {}", e))?;
+    
+    let rows: Vec<(i64, i64, i64, String, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))
+        .map_err(|e| format!("Fehler beim Ausführen der SQL-Abfrage: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Fehler beim Lesen der Lesezeichen: {}", e))?;
+    
+    // Ordnerpfade über die Eltern-IDs auflösen (Typ 2 = Ordner)
+    let folders: HashMap<i64, (i64, String)> = rows.iter()
+        .filter(|row| row.2 == 2)
+        .map(|row| (row.0, (row.1, row.3.clone())))
+        .collect();
+    let folder_path = |mut id: i64| {
+        let mut parts = Vec::new();
+        while let Some((parent, title)) = folders.get(&id) {
+            if !title.is_empty() {
+                parts.push(title.clone());
+            }
+            id = *parent;
+        }
+        parts.reverse();
+        parts.join("/")
+    };
+    
+    Ok(rows.iter()
+        .filter(|row| row.2 == 1)
+        .filter_map(|row| row.4.as_ref().map(|url| NormalizedBookmark {
+            folder: folder_path(row.1),
+            title: row.3.clone(),
+            url: url.clone(),
+        }))
+        .collect())
+}
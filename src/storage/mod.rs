@@ -0,0 +1,3 @@
+// storage - Zusätzliche Ablageorte für Backups
+pub mod git;
+pub mod rclone;
@@ -0,0 +1,90 @@
+// storage/rclone.rs - Backup-Ordner per "rclone copy" auf ein rclone-Remote übertragen
+use crate::proxy::{Proxy, ProxyMode};
+use chrono::{DateTime, Local};
+use std::path::Path;
+use std::process::Command;
+
+// Ergebnis des letzten Laufs für die Statusanzeige
+#[derive(Clone, Debug)]
+pub struct RcloneStatus {
+    pub finished: DateTime<Local>,
+    pub result: Result<String, String>,
+}
+
+// Bleibt lokal: config*.json enthält Passwörter und Tokens der Ziele, der
+// Upload-Status gehört zu diesem Rechner, .tmp-Dateien sind noch nicht fertig
+const EXCLUDES: [&str; 3] = ["/config*.json", "/destinations_status.json", "*.tmp"];
+
+// Führt "rclone copy <backup_dir> <remote>" aus. Die Ausgabe landet zeilenweise
+// im Log; zurück kommt die letzte Statistikzeile bzw. die Fehlermeldung.
+pub fn copy(
+    executable: Option<&Path>,
+    source: &Path,
+    remote: &str,
+    proxy_mode: ProxyMode,
+    proxy: Option<&Proxy>,
+) -> Result<String, String> {
+    if remote.trim().is_empty() {
+        return Err("Kein rclone-Remote angegeben".to_string());
+    }
+    
+    let mut command = match executable {
+        Some(path) => Command::new(path),
+        None => Command::new("rclone"),
+    };
+    command.arg("copy").arg(source).arg(remote.trim()).args(["--stats-one-line", "-v"]);
+    for pattern in EXCLUDES {
+        command.arg("--exclude").arg(pattern);
+    }
+    
+    // rclone liest den Proxy nur aus den Umgebungsvariablen
+    const PROXY_VARIABLES: [&str; 4] = ["HTTPS_PROXY", "HTTP_PROXY", "https_proxy", "http_proxy"];
+    match proxy {
+        Some(proxy) => {
+            for name in PROXY_VARIABLES {
+                command.env(name, proxy.url());
+            }
+        }
+        None if proxy_mode == ProxyMode::Off => {
+            for name in PROXY_VARIABLES {
+                command.env_remove(name);
+            }
+        }
+        None => {}
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+    
+    log::info!("rclone copy {} {}", source.display(), remote.trim());
+    let output = command.output()
+        .map_err(|e| format!("rclone konnte nicht gestartet werden: {}", e))?;
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+        log::info!("rclone: {}", line);
+    }
+    // rclone schreibt Fortschritt und Fehler nach stderr
+    for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+        if output.status.success() {
+            log::info!("rclone: {}", line);
+        } else {
+            log::warn!("rclone: {}", line);
+        }
+    }
+    
+    let last_line = stderr.lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| l.trim().to_string())
+        .unwrap_or_default();
+    if output.status.success() {
+        Ok(last_line)
+    } else {
+        Err(format!("rclone beendet mit {}: {}", output.status, last_line))
+    }
+}
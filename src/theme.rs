@@ -0,0 +1,34 @@
+// theme.rs - Helles oder dunkles Erscheinungsbild des Fensters
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Theme {
+    // Wie in den Windows-Einstellungen ("App-Modus")
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => tr("System"),
+            Theme::Light => tr("Hell"),
+            Theme::Dark => tr("Dunkel"),
+        }
+    }
+
+    // None: das System meldet kein Design, dann bleibt es beim aktuellen
+    pub fn visuals(&self, system_theme: Option<eframe::Theme>) -> Option<egui::Visuals> {
+        match self {
+            Theme::System => system_theme.map(|theme| theme.egui_visuals()),
+            Theme::Light => Some(egui::Visuals::light()),
+            Theme::Dark => Some(egui::Visuals::dark()),
+        }
+    }
+}